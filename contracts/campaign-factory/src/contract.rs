@@ -1,27 +1,56 @@
 use crate::error::ContractError;
+use crate::events::{
+    campaign_created_event, claim_many_event, claim_result_event, create_campaign_event,
+    execute_proposal_event, propose_event, receipt_created_event, register_nfts_event,
+    set_creator_profile_event, set_governance_event, update_config_event,
+    update_verified_assets_event, vote_event,
+};
 use crate::state::{
-    Config, ConfigResponse, FactoryCampaign, ADDR_CAMPAIGNS, CONFIG, NUMBER_OF_CAMPAIGNS,
+    Config, ConfigResponse, CreatorProfile, FactoryCampaign, GlobalStats, Governance,
+    NftRegistration, Proposal, ProposalAction, UserPosition, UserPositionsResponse, Verification,
+    VerifiedAsset, VerifiedAssetsUpdate, ADDR_CAMPAIGNS, BALLOTS, CAMPAIGN_CREATORS, CAMPAIGN_KEYS,
+    CONFIG, CREATOR_CAMPAIGNS, CREATOR_END_TIMES, CREATOR_PROFILES, GOVERNANCE, NFT_REGISTRY,
+    NUMBER_OF_CAMPAIGNS, NUMBER_OF_PROPOSALS, PENDING_CREATOR, PENDING_RECEIPT, PROPOSALS,
+    REQUIRE_VERIFIED, REWARDS_DISTRIBUTED, TOTAL_NFT_STAKED, VERIFIED_ASSETS,
 };
 use crate::{
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{Cw4QueryMsg, ExecuteMsg, InstantiateMsg, MemberResponse, QueryMsg, TotalWeightResponse},
     state::CAMPAIGNS,
 };
-// use campaign::msg::ExecuteMsg as CampaignExecuteMsg;
+use campaign::msg::ExecuteMsg as CampaignExecuteMsg;
 use campaign::msg::InstantiateMsg as CampaignInstantiateMsg;
 use campaign::msg::QueryMsg as CampaignQueryMsg;
-use campaign::state::{AssetToken, CampaignInfoResult, LockupTerm};
+use campaign::state::{
+    AssetToken, CampaignInfoResult, ExclusivityRules, LockupTerm, StakedInfoResult, TermPolicy,
+    TokenInfo, TokenStaking,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, QuerierWrapper,
-    QueryRequest, Reply, ReplyOn, Response, StdError, StdResult, SubMsg, WasmMsg, WasmQuery,
+    to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    QuerierWrapper, QueryRequest, Reply, ReplyOn, Response, StdResult, SubMsg, Uint128, WasmMsg,
+    WasmQuery,
 };
 use cw2::set_contract_version;
+use cw721_base::msg::InstantiateMsg as Cw721InstantiateMsg;
+use cw_storage_plus::Bound;
 use cw_utils::parse_reply_instantiate_data;
+use std::cmp::{max, min};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:campaign-factory";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+// reply ids of instantiated contracts
+const CREATE_CAMPAIGN_REPLY_ID: u64 = 1;
+const CREATE_RECEIPT_REPLY_ID: u64 = 2;
+// pagination of list queries
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
+// campaigns queried per page of aggregated queries
+const AGGREGATE_DEFAULT_LIMIT: u32 = 10;
+const AGGREGATE_MAX_LIMIT: u32 = 30;
+// reply id of claim from a campaign is offset + campaign id
+const CLAIM_REPLY_ID_OFFSET: u64 = 1 << 32;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -32,9 +61,17 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    let term_policy = msg.term_policy.unwrap_or_default();
+    if !term_policy.is_valid() {
+        return Err(ContractError::InvalidTermPolicy {});
+    }
+
     let config = Config {
         owner: info.sender,
         campaign_code_id: msg.campaign_code_id,
+        term_policy,
+        receipt_code_id: msg.receipt_code_id,
+        max_active_campaigns: 0,
     };
 
     // init NUMBER_OF_CAMPAIGNS to 0
@@ -59,7 +96,19 @@ pub fn execute(
         ExecuteMsg::UpdateConfig {
             owner,
             campaign_code_id,
-        } => execute_update_config(deps, env, info, owner, campaign_code_id),
+            term_policy,
+            receipt_code_id,
+            max_active_campaigns,
+        } => execute_update_config(
+            deps,
+            env,
+            info,
+            owner,
+            campaign_code_id,
+            term_policy,
+            receipt_code_id,
+            max_active_campaigns,
+        ),
         ExecuteMsg::CreateCampaign {
             owner,
             campaign_name,
@@ -71,6 +120,10 @@ pub fn execute(
             reward_token_info,
             allowed_collection,
             lockup_term,
+            token_staking,
+            exclusivity,
+            soft_staking,
+            receipt,
         } => execute_create_campaign(
             deps,
             env,
@@ -85,27 +138,89 @@ pub fn execute(
             reward_token_info,
             allowed_collection,
             lockup_term,
+            token_staking,
+            exclusivity,
+            soft_staking,
+            receipt,
         ),
+        ExecuteMsg::RegisterNfts { token_ids } => execute_register_nfts(deps, env, info, token_ids),
+        ExecuteMsg::UnregisterNfts { token_ids } => {
+            execute_unregister_nfts(deps, env, info, token_ids)
+        }
+        ExecuteMsg::UpdateEndTime { end_time } => execute_update_end_time(deps, info, end_time),
+        ExecuteMsg::RecordClaim { amount } => execute_record_claim(deps, info, amount),
+        ExecuteMsg::SetGovernance {
+            group_addr,
+            quorum,
+            voting_period,
+            timelock,
+        } => execute_set_governance(deps, info, group_addr, quorum, voting_period, timelock),
+        ExecuteMsg::Propose { action } => execute_propose(deps, env, info, action),
+        ExecuteMsg::Vote { proposal_id } => execute_vote(deps, env, info, proposal_id),
+        ExecuteMsg::ExecuteProposal { proposal_id } => {
+            execute_execute_proposal(deps, env, proposal_id)
+        }
+        ExecuteMsg::UpdateVerifiedAssets(update) => {
+            execute_update_verified_assets(deps, env, info, update)
+        }
+        ExecuteMsg::SetCreatorProfile { display_name } => {
+            execute_set_creator_profile(deps, info, display_name)
+        }
+        ExecuteMsg::VerifyCreator { creator, verified } => {
+            execute_verify_creator(deps, info, creator, verified)
+        }
+        ExecuteMsg::ClaimMany { campaign_ids } => execute_claim_many(deps, info, campaign_ids),
     }
 }
 
-// Only owner can execute it
+// Only owner can execute it, config of governed factory is updated by proposals
+#[allow(clippy::too_many_arguments)]
 pub fn execute_update_config(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     owner: Option<String>,
     campaign_code_id: Option<u64>,
+    term_policy: Option<TermPolicy>,
+    receipt_code_id: Option<u64>,
+    max_active_campaigns: Option<u64>,
 ) -> Result<Response, ContractError> {
-    let mut config: Config = CONFIG.load(deps.storage)?;
+    let config: Config = CONFIG.load(deps.storage)?;
 
     // permission check
     if info.sender != config.owner {
         return Err(ContractError::Unauthorized {});
     }
+    if GOVERNANCE.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::GovernanceRequired {});
+    }
+
+    let config = update_config(
+        deps,
+        owner,
+        campaign_code_id,
+        term_policy,
+        receipt_code_id,
+        max_active_campaigns,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_event(update_config_event(&config)))
+}
+
+fn update_config(
+    deps: DepsMut,
+    owner: Option<String>,
+    campaign_code_id: Option<u64>,
+    term_policy: Option<TermPolicy>,
+    receipt_code_id: Option<u64>,
+    max_active_campaigns: Option<u64>,
+) -> Result<Config, ContractError> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
 
     // update owner if provided
-    if let Some(owner) = owner.clone() {
+    if let Some(owner) = owner {
         config.owner = deps.api.addr_validate(&owner)?;
     }
 
@@ -114,12 +229,464 @@ pub fn execute_update_config(
         config.campaign_code_id = campaign_code_id;
     }
 
+    // update term_policy if provided, existing campaigns keep their policy
+    if let Some(term_policy) = term_policy {
+        if !term_policy.is_valid() {
+            return Err(ContractError::InvalidTermPolicy {});
+        }
+        config.term_policy = term_policy;
+    }
+
+    // update receipt_code_id if provided
+    if let Some(receipt_code_id) = receipt_code_id {
+        config.receipt_code_id = Some(receipt_code_id);
+    }
+
+    // update max_active_campaigns if provided, existing campaigns are kept
+    if let Some(max_active_campaigns) = max_active_campaigns {
+        config.max_active_campaigns = max_active_campaigns;
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
+    Ok(config)
+}
+
+// Only owner can execute it once, then governance is changed by proposals
+pub fn execute_set_governance(
+    deps: DepsMut,
+    info: MessageInfo,
+    group_addr: String,
+    quorum: Decimal,
+    voting_period: u64,
+    timelock: u64,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    if GOVERNANCE.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::GovernanceRequired {});
+    }
+
+    let governance = Governance {
+        group_addr: deps.api.addr_validate(&group_addr)?,
+        quorum,
+        voting_period,
+        timelock,
+    };
+    validate_governance(deps.as_ref(), &governance)?;
+    GOVERNANCE.save(deps.storage, &governance)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_governance")
+        .add_event(set_governance_event(&governance)))
+}
+
+// quorum is a share of total weight, group must have members
+fn validate_governance(deps: Deps, governance: &Governance) -> Result<(), ContractError> {
+    if governance.quorum.is_zero()
+        || governance.quorum > Decimal::one()
+        || governance.voting_period == 0
+    {
+        return Err(ContractError::InvalidGovernance {});
+    }
+    deps.api.addr_validate(governance.group_addr.as_str())?;
+    if query_total_weight(&deps.querier, &governance.group_addr, None)? == 0 {
+        return Err(ContractError::InvalidGovernance {});
+    }
+    Ok(())
+}
+
+// Only members of group can execute it, proposer approves the proposal
+pub fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: ProposalAction,
+) -> Result<Response, ContractError> {
+    let governance = match GOVERNANCE.may_load(deps.storage)? {
+        Some(governance) => governance,
+        None => return Err(ContractError::NotGroupMember {}),
+    };
+
+    // reject actions which would fail on execution
+    match &action {
+        ProposalAction::UpdateConfig {
+            owner, term_policy, ..
+        } => {
+            if let Some(owner) = owner {
+                deps.api.addr_validate(owner)?;
+            }
+            if term_policy
+                .as_ref()
+                .map_or(false, |policy| !policy.is_valid())
+            {
+                return Err(ContractError::InvalidTermPolicy {});
+            }
+        }
+        ProposalAction::UpdateGovernance(governance) => {
+            validate_governance(deps.as_ref(), governance)?;
+        }
+        ProposalAction::VerifyCreator { creator, .. } => {
+            deps.api.addr_validate(creator)?;
+        }
+        ProposalAction::UpdateVerifiedAssets(update) => {
+            for asset_label in &update.add {
+                validate_verified_asset(deps.as_ref(), &asset_label.asset)?;
+            }
+        }
+    }
+
+    // weights are snapshotted at the height of proposal
+    let start_height = env.block.height;
+    let weight = query_member_weight(
+        &deps.querier,
+        &governance.group_addr,
+        &info.sender,
+        start_height,
+    )?;
+    if weight == 0 {
+        return Err(ContractError::NotGroupMember {});
+    }
+    let total_weight =
+        query_total_weight(&deps.querier, &governance.group_addr, Some(start_height))?;
+
+    let proposal_id = NUMBER_OF_PROPOSALS
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    let mut proposal = Proposal {
+        id: proposal_id,
+        proposer: info.sender.clone(),
+        action,
+        start_height,
+        expires_at: env.block.time.seconds() + governance.voting_period,
+        total_weight,
+        yes_weight: weight,
+        passed_at: None,
+        executed: false,
+    };
+    if is_passed(&governance, &proposal) {
+        proposal.passed_at = Some(env.block.time.seconds());
+    }
+
+    NUMBER_OF_PROPOSALS.save(deps.storage, &proposal_id)?;
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+    BALLOTS.save(deps.storage, (proposal_id, info.sender.clone()), &weight)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose")
+        .add_event(propose_event(&proposal))
+        .add_event(vote_event(&proposal, &info.sender, weight)))
+}
+
+// Only members of group at start height of proposal can execute it
+pub fn execute_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let governance = match GOVERNANCE.may_load(deps.storage)? {
+        Some(governance) => governance,
+        None => return Err(ContractError::NotGroupMember {}),
+    };
+    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+
+    if proposal.executed {
+        return Err(ContractError::ProposalExecuted { proposal_id });
+    }
+    if env.block.time.seconds() >= proposal.expires_at {
+        return Err(ContractError::ProposalExpired { proposal_id });
+    }
+    if BALLOTS.has(deps.storage, (proposal_id, info.sender.clone())) {
+        return Err(ContractError::AlreadyVoted { proposal_id });
+    }
+
+    let weight = query_member_weight(
+        &deps.querier,
+        &governance.group_addr,
+        &info.sender,
+        proposal.start_height,
+    )?;
+    if weight == 0 {
+        return Err(ContractError::NotGroupMember {});
+    }
+
+    // timelock starts when quorum is reached
+    proposal.yes_weight += weight;
+    if proposal.passed_at.is_none() && is_passed(&governance, &proposal) {
+        proposal.passed_at = Some(env.block.time.seconds());
+    }
+
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+    BALLOTS.save(deps.storage, (proposal_id, info.sender.clone()), &weight)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "vote")
+        .add_event(vote_event(&proposal, &info.sender, weight)))
+}
+
+// Anyone can execute a passed proposal after its timelock
+pub fn execute_execute_proposal(
+    deps: DepsMut,
+    env: Env,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let governance = match GOVERNANCE.may_load(deps.storage)? {
+        Some(governance) => governance,
+        None => return Err(ContractError::ProposalNotPassed { proposal_id }),
+    };
+    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+
+    if proposal.executed {
+        return Err(ContractError::ProposalExecuted { proposal_id });
+    }
+    let unlock_time = match proposal.passed_at {
+        Some(passed_at) => passed_at + governance.timelock,
+        None => return Err(ContractError::ProposalNotPassed { proposal_id }),
+    };
+    if env.block.time.seconds() < unlock_time {
+        return Err(ContractError::ProposalTimelocked {
+            proposal_id,
+            unlock_time,
+        });
+    }
+
+    proposal.executed = true;
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    let event = match proposal.action {
+        ProposalAction::UpdateConfig {
+            owner,
+            campaign_code_id,
+            term_policy,
+            receipt_code_id,
+            max_active_campaigns,
+        } => {
+            let config = update_config(
+                deps,
+                owner,
+                campaign_code_id,
+                term_policy,
+                receipt_code_id,
+                max_active_campaigns,
+            )?;
+            update_config_event(&config)
+        }
+        ProposalAction::VerifyCreator { creator, verified } => {
+            let creator = deps.api.addr_validate(&creator)?;
+            let profile = verify_creator(deps, &creator, verified)?;
+            set_creator_profile_event(&creator, &profile)
+        }
+        ProposalAction::UpdateGovernance(governance) => {
+            validate_governance(deps.as_ref(), &governance)?;
+            GOVERNANCE.save(deps.storage, &governance)?;
+            set_governance_event(&governance)
+        }
+        ProposalAction::UpdateVerifiedAssets(update) => {
+            let require_verified = update_verified_assets(deps, &env, &update)?;
+            update_verified_assets_event(require_verified, &update)
+        }
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_proposal")
+        .add_event(execute_proposal_event(proposal_id))
+        .add_event(event))
+}
+
+fn is_passed(governance: &Governance, proposal: &Proposal) -> bool {
+    Decimal::from_ratio(proposal.yes_weight, proposal.total_weight) >= governance.quorum
+}
+
+// Anyone can set display name of its profile, verified flag is kept
+pub fn execute_set_creator_profile(
+    deps: DepsMut,
+    info: MessageInfo,
+    display_name: String,
+) -> Result<Response, ContractError> {
+    let display_name = display_name.trim().to_string();
+    if display_name.is_empty() || display_name.len() > 100 {
+        return Err(ContractError::InvalidCreatorProfile {});
+    }
+
+    let mut profile = CREATOR_PROFILES
+        .may_load(deps.storage, info.sender.clone())?
+        .unwrap_or_default();
+    profile.display_name = display_name;
+    CREATOR_PROFILES.save(deps.storage, info.sender.clone(), &profile)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_creator_profile")
+        .add_event(set_creator_profile_event(&info.sender, &profile)))
+}
+
+// Only owner can execute it, creators of governed factory are verified by proposals
+pub fn execute_verify_creator(
+    deps: DepsMut,
+    info: MessageInfo,
+    creator: String,
+    verified: bool,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    if GOVERNANCE.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::GovernanceRequired {});
+    }
+
+    let creator = deps.api.addr_validate(&creator)?;
+    let profile = verify_creator(deps, &creator, verified)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "verify_creator")
+        .add_event(set_creator_profile_event(&creator, &profile)))
+}
+
+fn verify_creator(
+    deps: DepsMut,
+    creator: &Addr,
+    verified: bool,
+) -> Result<CreatorProfile, ContractError> {
+    let mut profile = CREATOR_PROFILES
+        .may_load(deps.storage, creator.clone())?
+        .unwrap_or_default();
+    profile.verified = verified;
+    CREATOR_PROFILES.save(deps.storage, creator.clone(), &profile)?;
+    Ok(profile)
+}
+
+// Anyone can claim its reward from campaigns, each claim is a submessage replied on failure too
+pub fn execute_claim_many(
+    deps: DepsMut,
+    info: MessageInfo,
+    campaign_ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    let mut distinct_ids = campaign_ids.clone();
+    distinct_ids.sort_unstable();
+    distinct_ids.dedup();
+    if campaign_ids.is_empty()
+        || distinct_ids.len() < campaign_ids.len()
+        || campaign_ids.len() > AGGREGATE_MAX_LIMIT as usize
+    {
+        return Err(ContractError::InvalidClaimMany {
+            max_campaigns: AGGREGATE_MAX_LIMIT,
+        });
+    }
+
+    let mut claim_msgs = vec![];
+    for campaign_id in &campaign_ids {
+        let campaign = CAMPAIGNS.load(deps.storage, *campaign_id)?;
+        claim_msgs.push(SubMsg {
+            id: CLAIM_REPLY_ID_OFFSET + campaign_id,
+            gas_limit: None,
+            msg: CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: campaign.campaign_addr.to_string(),
+                msg: to_binary(&CampaignExecuteMsg::ClaimFor {
+                    owner: info.sender.to_string(),
+                })?,
+                funds: vec![],
+            }),
+            reply_on: ReplyOn::Always,
+        });
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_many")
+        .add_event(claim_many_event(&info.sender, &campaign_ids))
+        .add_submessages(claim_msgs))
+}
+
+// Only owner can execute it, verified assets of governed factory are updated by proposals
+pub fn execute_update_verified_assets(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    update: VerifiedAssetsUpdate,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    if GOVERNANCE.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::GovernanceRequired {});
+    }
+
+    let require_verified = update_verified_assets(deps, &env, &update)?;
+
     Ok(Response::new()
-        .add_attribute("method", "update_config")
-        .add_attribute("owner", owner.unwrap())
-        .add_attribute("campaign_code_id", campaign_code_id.unwrap().to_string()))
+        .add_attribute("action", "update_verified_assets")
+        .add_event(update_verified_assets_event(require_verified, &update)))
+}
+
+fn update_verified_assets(
+    deps: DepsMut,
+    env: &Env,
+    update: &VerifiedAssetsUpdate,
+) -> Result<bool, ContractError> {
+    for asset in &update.remove {
+        VERIFIED_ASSETS.remove(deps.storage, asset.key());
+    }
+
+    for asset_label in &update.add {
+        let asset = validate_verified_asset(deps.as_ref(), &asset_label.asset)?;
+        VERIFIED_ASSETS.save(
+            deps.storage,
+            asset.key(),
+            &Verification {
+                asset,
+                label: asset_label.label.clone(),
+                verified_at: env.block.time.seconds(),
+            },
+        )?;
+    }
+
+    if let Some(require_verified) = update.require_verified {
+        REQUIRE_VERIFIED.save(deps.storage, &require_verified)?;
+    }
+
+    Ok(REQUIRE_VERIFIED.may_load(deps.storage)?.unwrap_or_default())
+}
+
+// addresses are validated so keys match the assets of created campaigns
+fn validate_verified_asset(
+    deps: Deps,
+    asset: &VerifiedAsset,
+) -> Result<VerifiedAsset, ContractError> {
+    let invalid_asset = || ContractError::InvalidVerifiedAsset { asset: asset.key() };
+    match asset {
+        VerifiedAsset::Token(TokenInfo::Token { contract_addr }) => {
+            let contract_addr = deps
+                .api
+                .addr_validate(contract_addr)
+                .map_err(|_| invalid_asset())?;
+            Ok(VerifiedAsset::Token(TokenInfo::Token {
+                contract_addr: contract_addr.to_string(),
+            }))
+        }
+        VerifiedAsset::Token(TokenInfo::NativeToken { denom }) => {
+            if denom.is_empty() {
+                return Err(invalid_asset());
+            }
+            Ok(asset.clone())
+        }
+        VerifiedAsset::Collection(collection) => {
+            let collection = deps
+                .api
+                .addr_validate(collection)
+                .map_err(|_| invalid_asset())?;
+            Ok(VerifiedAsset::Collection(collection.to_string()))
+        }
+    }
 }
 
 // Anyone can execute it to create a new pool
@@ -127,7 +694,7 @@ pub fn execute_update_config(
 pub fn execute_create_campaign(
     deps: DepsMut,
     env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     owner: String,
     campaign_name: String,
     campaign_image: String,
@@ -138,9 +705,64 @@ pub fn execute_create_campaign(
     reward_token_info: AssetToken,
     allowed_collection: String,
     lockup_term: Vec<LockupTerm>,
+    token_staking: Option<TokenStaking>,
+    exclusivity: Option<ExclusivityRules>,
+    soft_staking: Option<bool>,
+    receipt: Option<bool>,
 ) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
+    // receipt collection is instantiated in reply of the created campaign
+    let receipt = receipt.unwrap_or_default();
+    if receipt {
+        if config.receipt_code_id.is_none() {
+            return Err(ContractError::ReceiptDisabled {});
+        }
+        if soft_staking.unwrap_or_default() {
+            return Err(ContractError::InvalidReceipt {});
+        }
+    }
+    PENDING_RECEIPT.save(deps.storage, &receipt)?;
+
+    // campaigns which have not ended count toward the limit of creator
+    if config.max_active_campaigns > 0 {
+        let start = Bound::exclusive((env.block.time.seconds(), u64::MAX));
+        let active_campaigns = CREATOR_END_TIMES
+            .sub_prefix(info.sender.clone())
+            .keys(deps.storage, Some(start), None, Order::Ascending)
+            .take(config.max_active_campaigns as usize)
+            .collect::<StdResult<Vec<_>>>()?
+            .len() as u64;
+        if active_campaigns >= config.max_active_campaigns {
+            return Err(ContractError::CreatorCampaignLimit {
+                max_active_campaigns: config.max_active_campaigns,
+            });
+        }
+    }
+    PENDING_CREATOR.save(deps.storage, &(info.sender.clone(), end_time))?;
+
+    // unverified assets are flagged in event, or rejected if verification is required
+    let verified_reward_token = VERIFIED_ASSETS.has(
+        deps.storage,
+        VerifiedAsset::Token(reward_token_info.info.clone()).key(),
+    );
+    let verified_collection = VERIFIED_ASSETS.has(
+        deps.storage,
+        VerifiedAsset::Collection(allowed_collection.clone()).key(),
+    );
+    if REQUIRE_VERIFIED.may_load(deps.storage)?.unwrap_or_default() {
+        if !verified_reward_token {
+            return Err(ContractError::UnverifiedAsset {
+                asset: reward_token_info.info.to_string(),
+            });
+        }
+        if !verified_collection {
+            return Err(ContractError::UnverifiedAsset {
+                asset: allowed_collection,
+            });
+        }
+    }
+
     // // get current time
     // let current_time = env.block.time.seconds();
 
@@ -159,21 +781,19 @@ pub fn execute_create_campaign(
     // }
 
     Ok(Response::new()
-        .add_attributes(vec![
-            ("method", "create_campaign"),
-            ("campaign_owner", owner.as_str()),
-            ("campaign_name", campaign_name.as_str()),
-            ("campaign_image", campaign_image.as_str()),
-            ("campaign_description", campaign_description.as_str()),
-            ("start_time", start_time.to_string().as_str()),
-            ("end_time", end_time.to_string().as_str()),
-            ("limit_per_staker", limit_per_staker.to_string().as_str()),
-            ("reward_token_info", &format!("{}", reward_token_info)),
-            ("allowed_collection", allowed_collection.as_str()),
-            ("lockup_term", &format!("{:?}", &lockup_term)),
-        ])
+        .add_attribute("action", "create_campaign")
+        .add_event(create_campaign_event(
+            &owner,
+            &campaign_name,
+            &allowed_collection,
+            &reward_token_info.info,
+            start_time,
+            end_time,
+            verified_reward_token,
+            verified_collection,
+        ))
         .add_submessage(SubMsg {
-            id: 1,
+            id: CREATE_CAMPAIGN_REPLY_ID,
             gas_limit: None,
             msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
                 code_id: config.campaign_code_id,
@@ -191,16 +811,194 @@ pub fn execute_create_campaign(
                     lockup_term,
                     start_time,
                     end_time,
+                    term_policy: Some(config.term_policy),
+                    token_staking,
+                    factory: Some(env.contract.address.to_string()),
+                    exclusivity,
+                    soft_staking,
                 })?,
             }),
             reply_on: ReplyOn::Success,
         }))
 }
 
+// campaign created by factory which sends the callback
+fn load_sender_campaign(deps: Deps, info: &MessageInfo) -> Result<FactoryCampaign, ContractError> {
+    match CAMPAIGN_KEYS.may_load(deps.storage, info.sender.clone())? {
+        Some(campaign_key) => Ok(CAMPAIGNS.load(deps.storage, campaign_key)?),
+        None => Err(ContractError::Unauthorized {}),
+    }
+}
+
+// Only campaigns created by factory can execute it.
+// If the campaign it joins or the campaign it left has exclusivity rules, an nft cannot be
+// staked in both and cannot switch campaign of its collection during switch_cooldown of either.
+pub fn execute_register_nfts(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_ids: Vec<String>,
+) -> Result<Response, ContractError> {
+    let campaign = load_sender_campaign(deps.as_ref(), &info)?;
+    let current_time = env.block.time.seconds();
+
+    let exclusivity = query_exclusivity_rules(&deps.querier, &campaign.campaign_addr)?;
+
+    for token_id in &token_ids {
+        let key = (campaign.allowed_collection.clone(), token_id.clone());
+        if let Some(registration) = NFT_REGISTRY.may_load(deps.storage, key.clone())? {
+            if registration.campaign_addr != campaign.campaign_addr {
+                let previous_exclusivity =
+                    query_exclusivity_rules(&deps.querier, &registration.campaign_addr)?;
+                if exclusivity.is_some() || previous_exclusivity.is_some() {
+                    if registration.staked {
+                        return Err(ContractError::NftStakedInCampaign {
+                            token_id: token_id.clone(),
+                            campaign_addr: registration.campaign_addr.to_string(),
+                        });
+                    }
+                    let switch_cooldown = max(
+                        exclusivity
+                            .as_ref()
+                            .map_or(0, |rules| rules.switch_cooldown),
+                        previous_exclusivity.map_or(0, |rules| rules.switch_cooldown),
+                    );
+                    let available_time = registration.updated_at + switch_cooldown;
+                    if current_time < available_time {
+                        return Err(ContractError::SwitchCooldown {
+                            token_id: token_id.clone(),
+                            available_time,
+                        });
+                    }
+                }
+            }
+        }
+
+        NFT_REGISTRY.save(
+            deps.storage,
+            key,
+            &NftRegistration {
+                campaign_addr: campaign.campaign_addr.clone(),
+                staked: true,
+                updated_at: current_time,
+            },
+        )?;
+    }
+    let total_nft_staked = TOTAL_NFT_STAKED.may_load(deps.storage)?.unwrap_or_default();
+    TOTAL_NFT_STAKED.save(deps.storage, &(total_nft_staked + token_ids.len() as u64))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_nfts")
+        .add_event(register_nfts_event(
+            "factory-register-nfts",
+            &campaign.campaign_addr,
+            &token_ids,
+        )))
+}
+
+// Only campaigns created by factory can execute it
+pub fn execute_unregister_nfts(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_ids: Vec<String>,
+) -> Result<Response, ContractError> {
+    let campaign = load_sender_campaign(deps.as_ref(), &info)?;
+
+    for token_id in &token_ids {
+        let key = (campaign.allowed_collection.clone(), token_id.clone());
+        // nft registered by another campaign is kept
+        if let Some(mut registration) = NFT_REGISTRY.may_load(deps.storage, key.clone())? {
+            if registration.campaign_addr == campaign.campaign_addr {
+                registration.staked = false;
+                registration.updated_at = env.block.time.seconds();
+                NFT_REGISTRY.save(deps.storage, key, &registration)?;
+            }
+        }
+    }
+    let total_nft_staked = TOTAL_NFT_STAKED.may_load(deps.storage)?.unwrap_or_default();
+    TOTAL_NFT_STAKED.save(
+        deps.storage,
+        &total_nft_staked.saturating_sub(token_ids.len() as u64),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unregister_nfts")
+        .add_event(register_nfts_event(
+            "factory-unregister-nfts",
+            &campaign.campaign_addr,
+            &token_ids,
+        )))
+}
+
+// Only campaigns created by factory can execute it
+pub fn execute_update_end_time(
+    deps: DepsMut,
+    info: MessageInfo,
+    end_time: u64,
+) -> Result<Response, ContractError> {
+    let campaign_key = match CAMPAIGN_KEYS.may_load(deps.storage, info.sender.clone())? {
+        Some(campaign_key) => campaign_key,
+        None => return Err(ContractError::Unauthorized {}),
+    };
+    if let Some(creator) = CAMPAIGN_CREATORS.may_load(deps.storage, campaign_key)? {
+        let old_end_time = CREATOR_CAMPAIGNS.load(deps.storage, (creator.clone(), campaign_key))?;
+        CREATOR_END_TIMES.remove(deps.storage, (creator.clone(), old_end_time, campaign_key));
+        CREATOR_END_TIMES.save(
+            deps.storage,
+            (creator.clone(), end_time, campaign_key),
+            &Empty {},
+        )?;
+        CREATOR_CAMPAIGNS.save(deps.storage, (creator, campaign_key), &end_time)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "update_end_time"))
+}
+
+// Only campaigns created by factory can execute it
+pub fn execute_record_claim(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let campaign = load_sender_campaign(deps.as_ref(), &info)?;
+
+    REWARDS_DISTRIBUTED.update(
+        deps.storage,
+        campaign.reward_token.to_string(),
+        |reward| -> StdResult<_> {
+            let mut reward = reward.unwrap_or(AssetToken {
+                info: campaign.reward_token.clone(),
+                amount: Uint128::zero(),
+            });
+            reward.amount = reward.amount.checked_add(amount)?;
+            Ok(reward)
+        },
+    )?;
+
+    Ok(Response::new().add_attribute("action", "record_claim"))
+}
+
 /// This just stores the result for future query
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
-    let reply = parse_reply_instantiate_data(msg).unwrap();
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        CREATE_RECEIPT_REPLY_ID => reply_create_receipt(deps, msg),
+        id if id > CLAIM_REPLY_ID_OFFSET => reply_claim(id - CLAIM_REPLY_ID_OFFSET, msg),
+        _ => reply_create_campaign(deps, env, msg),
+    }
+}
+
+// failed claim is reverted alone and reported
+fn reply_claim(campaign_id: u64, msg: Reply) -> Result<Response, ContractError> {
+    Ok(Response::new().add_event(claim_result_event(
+        campaign_id,
+        msg.result.into_result().err(),
+    )))
+}
+
+fn reply_create_campaign(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let reply = parse_reply_instantiate_data(msg)?;
 
     let campaign_contract = &reply.contract_address;
     let campaign_info: CampaignInfoResult =
@@ -221,17 +1019,81 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
 
     // increase campaign count
     NUMBER_OF_CAMPAIGNS.save(deps.storage, &(campaign_key))?;
+    CAMPAIGN_KEYS.save(
+        deps.storage,
+        deps.api.addr_validate(campaign_contract)?,
+        &campaign_key,
+    )?;
 
     let mut addr_campaigns = ADDR_CAMPAIGNS.load(deps.storage)?;
     addr_campaigns.push(campaign_contract.clone());
     ADDR_CAMPAIGNS.save(deps.storage, &addr_campaigns)?;
 
-    Ok(Response::new().add_attributes([
-        ("action", "reply_on_create_campaign_success"),
-        ("campaign_key", campaign_key.to_string().as_str()),
-        ("campaign_contract_addr", campaign_contract),
-        ("owner", campaign_info.owner.as_ref()),
-    ]))
+    // track campaign of its creator
+    if let Some((creator, end_time)) = PENDING_CREATOR.may_load(deps.storage)? {
+        PENDING_CREATOR.remove(deps.storage);
+        CREATOR_CAMPAIGNS.save(deps.storage, (creator.clone(), campaign_key), &end_time)?;
+        CREATOR_END_TIMES.save(
+            deps.storage,
+            (creator.clone(), end_time, campaign_key),
+            &Empty {},
+        )?;
+        CAMPAIGN_CREATORS.save(deps.storage, campaign_key, &creator)?;
+    }
+
+    let mut res = Response::new();
+
+    // instantiate receipt collection minted by campaign
+    if PENDING_RECEIPT.may_load(deps.storage)?.unwrap_or_default() {
+        PENDING_RECEIPT.remove(deps.storage);
+        let config: Config = CONFIG.load(deps.storage)?;
+        res = res.add_submessage(SubMsg {
+            id: CREATE_RECEIPT_REPLY_ID,
+            gas_limit: None,
+            msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
+                code_id: config.receipt_code_id.unwrap(),
+                funds: vec![],
+                admin: Some(env.contract.address.to_string()),
+                label: "receipt".to_string(),
+                msg: to_binary(&Cw721InstantiateMsg {
+                    name: format!("{} receipt", campaign_info.campaign_name),
+                    symbol: "RECEIPT".to_string(),
+                    minter: campaign_contract.clone(),
+                })?,
+            }),
+            reply_on: ReplyOn::Success,
+        });
+    }
+
+    Ok(res
+        .add_attribute("action", "reply_on_create_campaign_success")
+        .add_event(campaign_created_event(
+            campaign_key,
+            campaign_contract,
+            &campaign_info.owner,
+        )))
+}
+
+// receipt collection belongs to the last created campaign
+fn reply_create_receipt(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let reply = parse_reply_instantiate_data(msg)?;
+
+    let campaign_key = NUMBER_OF_CAMPAIGNS.load(deps.storage)?;
+    let campaign = CAMPAIGNS.load(deps.storage, campaign_key)?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: campaign.campaign_addr.to_string(),
+            msg: to_binary(&CampaignExecuteMsg::SetReceiptCollection {
+                receipt_collection: reply.contract_address.clone(),
+            })?,
+            funds: vec![],
+        })
+        .add_attribute("action", "reply_on_create_receipt_success")
+        .add_event(receipt_created_event(
+            &campaign.campaign_addr,
+            &reply.contract_address,
+        )))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -243,6 +1105,45 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_binary(&query_campaigns(deps, start_after, limit)?)
         }
         QueryMsg::CampaignAddrs {} => to_binary(&query_addr_campaigns(deps)?),
+        QueryMsg::NftRegistration {
+            collection,
+            token_id,
+        } => to_binary(&query_nft_registration(deps, collection, token_id)?),
+        QueryMsg::Governance {} => to_binary(&GOVERNANCE.may_load(deps.storage)?),
+        QueryMsg::Proposal { proposal_id } => {
+            to_binary(&PROPOSALS.load(deps.storage, proposal_id)?)
+        }
+        QueryMsg::Verification { asset } => {
+            to_binary(&VERIFIED_ASSETS.may_load(deps.storage, asset.key())?)
+        }
+        QueryMsg::Verifications { start_after, limit } => {
+            to_binary(&query_verifications(deps, start_after, limit)?)
+        }
+        QueryMsg::RequireVerified {} => {
+            to_binary(&REQUIRE_VERIFIED.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::CreatorProfile { creator } => {
+            let creator = deps.api.addr_validate(&creator)?;
+            to_binary(&CREATOR_PROFILES.may_load(deps.storage, creator)?)
+        }
+        QueryMsg::CampaignsByCreator {
+            creator,
+            start_after,
+            limit,
+        } => to_binary(&query_campaigns_by_creator(
+            deps,
+            creator,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::UserPositions {
+            user,
+            start_after,
+            limit,
+        } => to_binary(&query_user_positions(deps, user, start_after, limit)?),
+        QueryMsg::GlobalStats { start_after, limit } => {
+            to_binary(&query_global_stats(deps, start_after, limit)?)
+        }
     }
 }
 
@@ -251,6 +1152,9 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let resp = ConfigResponse {
         owner: state.owner.to_string(),
         campaign_code_id: state.campaign_code_id,
+        term_policy: state.term_policy,
+        receipt_code_id: state.receipt_code_id,
+        max_active_campaigns: state.max_active_campaigns,
     };
 
     Ok(resp)
@@ -267,7 +1171,7 @@ pub fn query_campaigns(
     limit: Option<u32>,
 ) -> StdResult<Vec<FactoryCampaign>> {
     let start_after = start_after.unwrap_or(0);
-    let limit = limit.unwrap_or(30) as usize;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let campaign_count = NUMBER_OF_CAMPAIGNS.load(deps.storage)?;
 
     let campaigns = (start_after..campaign_count)
@@ -283,6 +1187,168 @@ pub fn query_addr_campaigns(deps: Deps) -> StdResult<Vec<String>> {
     Ok(addr_campaigns)
 }
 
+pub fn query_nft_registration(
+    deps: Deps,
+    collection: String,
+    token_id: String,
+) -> StdResult<Option<NftRegistration>> {
+    let collection = deps.api.addr_validate(&collection)?;
+    NFT_REGISTRY.may_load(deps.storage, (collection, token_id))
+}
+
+pub fn query_campaigns_by_creator(
+    deps: Deps,
+    creator: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<FactoryCampaign>> {
+    let creator = deps.api.addr_validate(&creator)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    CREATOR_CAMPAIGNS
+        .prefix(creator)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|campaign_key| CAMPAIGNS.load(deps.storage, campaign_key?))
+        .collect()
+}
+
+// keys of campaigns in a page of aggregated queries and start of next page
+fn aggregate_page(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<(Vec<u64>, Option<u64>)> {
+    let limit = limit
+        .unwrap_or(AGGREGATE_DEFAULT_LIMIT)
+        .min(AGGREGATE_MAX_LIMIT) as u64;
+    let campaign_count = NUMBER_OF_CAMPAIGNS.load(deps.storage)?;
+    let start = start_after.unwrap_or(0);
+    let end = min(start.saturating_add(limit), campaign_count);
+
+    let next_start_after = if end < campaign_count {
+        Some(end)
+    } else {
+        None
+    };
+    Ok(((start + 1..=end).collect(), next_start_after))
+}
+
+pub fn query_user_positions(
+    deps: Deps,
+    user: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<UserPositionsResponse> {
+    let user = deps.api.addr_validate(&user)?;
+    let (campaign_keys, next_start_after) = aggregate_page(deps, start_after, limit)?;
+
+    let mut positions = vec![];
+    for campaign_key in campaign_keys {
+        let campaign = CAMPAIGNS.load(deps.storage, campaign_key)?;
+        let staked_info: StakedInfoResult = deps.querier.query_wasm_smart(
+            campaign.campaign_addr.to_string(),
+            &CampaignQueryMsg::NftStaked {
+                owner: user.clone(),
+            },
+        )?;
+        if staked_info.nfts.is_empty() && staked_info.reward_debt.is_zero() {
+            continue;
+        }
+        let nft_count = staked_info.nfts.len() as u64;
+        positions.push(UserPosition {
+            campaign_key,
+            campaign_addr: campaign.campaign_addr,
+            reward_token: campaign.reward_token,
+            nfts: staked_info
+                .nfts
+                .into_iter()
+                .take(MAX_LIMIT as usize)
+                .collect(),
+            nft_count,
+            claimable_reward: staked_info.reward_debt,
+        });
+    }
+
+    Ok(UserPositionsResponse {
+        positions,
+        next_start_after,
+    })
+}
+
+pub fn query_global_stats(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<GlobalStats> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    Ok(GlobalStats {
+        total_campaigns: NUMBER_OF_CAMPAIGNS.load(deps.storage)?,
+        total_nft_staked: TOTAL_NFT_STAKED.may_load(deps.storage)?.unwrap_or_default(),
+        rewards_distributed: REWARDS_DISTRIBUTED
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, reward)| reward))
+            .collect::<StdResult<Vec<_>>>()?,
+    })
+}
+
+pub fn query_verifications(
+    deps: Deps,
+    start_after: Option<VerifiedAsset>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Verification>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|asset| Bound::exclusive(asset.key()));
+
+    VERIFIED_ASSETS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, verification)| verification))
+        .collect()
+}
+
+fn query_exclusivity_rules(
+    querier: &QuerierWrapper,
+    campaign_addr: &Addr,
+) -> StdResult<Option<ExclusivityRules>> {
+    querier.query_wasm_smart(
+        campaign_addr.to_string(),
+        &CampaignQueryMsg::ExclusivityRules {},
+    )
+}
+
+fn query_member_weight(
+    querier: &QuerierWrapper,
+    group_addr: &Addr,
+    member: &Addr,
+    height: u64,
+) -> StdResult<u64> {
+    let member: MemberResponse = querier.query_wasm_smart(
+        group_addr.to_string(),
+        &Cw4QueryMsg::Member {
+            addr: member.to_string(),
+            at_height: Some(height),
+        },
+    )?;
+    Ok(member.weight.unwrap_or_default())
+}
+
+fn query_total_weight(
+    querier: &QuerierWrapper,
+    group_addr: &Addr,
+    height: Option<u64>,
+) -> StdResult<u64> {
+    let total_weight: TotalWeightResponse = querier.query_wasm_smart(
+        group_addr.to_string(),
+        &Cw4QueryMsg::TotalWeight { at_height: height },
+    )?;
+    Ok(total_weight.weight)
+}
+
 fn query_pair_info_from_pair(
     querier: &QuerierWrapper,
     pair_contract: Addr,
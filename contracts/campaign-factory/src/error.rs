@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use cw_utils::ParseReplyError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,6 +7,69 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
     #[error("## Unauthorized ##")]
     Unauthorized {},
+
+    #[error("## Invalid term policy ##")]
+    InvalidTermPolicy {},
+
+    #[error("## Receipt code id is not set ##")]
+    ReceiptDisabled {},
+
+    #[error("## Receipts are not issued for soft staked nfts ##")]
+    InvalidReceipt {},
+
+    #[error("## Privileged actions are governed by proposals ##")]
+    GovernanceRequired {},
+
+    #[error("## Invalid governance ##")]
+    InvalidGovernance {},
+
+    #[error("## Not a member of the governance group ##")]
+    NotGroupMember {},
+
+    #[error("## Already voted on proposal {proposal_id:?} ##")]
+    AlreadyVoted { proposal_id: u64 },
+
+    #[error("## Voting period of proposal {proposal_id:?} is over ##")]
+    ProposalExpired { proposal_id: u64 },
+
+    #[error("## Proposal {proposal_id:?} has not passed ##")]
+    ProposalNotPassed { proposal_id: u64 },
+
+    #[error("## Proposal {proposal_id:?} is timelocked until {unlock_time:?} ##")]
+    ProposalTimelocked { proposal_id: u64, unlock_time: u64 },
+
+    #[error("## Proposal {proposal_id:?} is already executed ##")]
+    ProposalExecuted { proposal_id: u64 },
+
+    #[error("## Invalid verified asset {asset:?} ##")]
+    InvalidVerifiedAsset { asset: String },
+
+    #[error("## Asset {asset:?} is not verified ##")]
+    UnverifiedAsset { asset: String },
+
+    #[error("## Creator has {max_active_campaigns:?} active campaigns ##")]
+    CreatorCampaignLimit { max_active_campaigns: u64 },
+
+    #[error("## Invalid creator profile ##")]
+    InvalidCreatorProfile {},
+
+    #[error("## Claim from 1 to {max_campaigns:?} distinct campaigns ##")]
+    InvalidClaimMany { max_campaigns: u32 },
+
+    #[error("## Nft {token_id:?} is staked in campaign {campaign_addr:?} ##")]
+    NftStakedInCampaign {
+        token_id: String,
+        campaign_addr: String,
+    },
+
+    #[error("## Nft {token_id:?} cannot switch campaign until {available_time:?} ##")]
+    SwitchCooldown {
+        token_id: String,
+        available_time: u64,
+    },
 }
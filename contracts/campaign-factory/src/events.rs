@@ -0,0 +1,171 @@
+//! Factory events, following the `wasm-stakify-*` schema documented in `campaign::events`.
+
+use campaign::events::{or_none, stakify_event};
+use campaign::state::TokenInfo;
+use cosmwasm_std::{Addr, Event};
+
+use crate::state::{Config, CreatorProfile, Governance, Proposal, VerifiedAssetsUpdate};
+
+pub fn update_config_event(config: &Config) -> Event {
+    let term_policy = &config.term_policy;
+    stakify_event("factory-update-config").add_attributes([
+        ("owner", config.owner.to_string()),
+        ("campaign_code_id", config.campaign_code_id.to_string()),
+        ("min_term", term_policy.min_term.to_string()),
+        ("max_term", term_policy.max_term.to_string()),
+        (
+            "allowed_terms",
+            or_none(
+                term_policy
+                    .allowed_terms
+                    .iter()
+                    .map(|term| term.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+        ),
+        ("allow_flexible", term_policy.allow_flexible.to_string()),
+        (
+            "receipt_code_id",
+            or_none(
+                config
+                    .receipt_code_id
+                    .map(|receipt_code_id| receipt_code_id.to_string())
+                    .unwrap_or_default(),
+            ),
+        ),
+        (
+            "max_active_campaigns",
+            config.max_active_campaigns.to_string(),
+        ),
+    ])
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_campaign_event(
+    owner: &str,
+    campaign_name: &str,
+    allowed_collection: &str,
+    reward_token: &TokenInfo,
+    start_time: u64,
+    end_time: u64,
+    verified_reward_token: bool,
+    verified_collection: bool,
+) -> Event {
+    stakify_event("factory-create-campaign").add_attributes([
+        ("owner", owner.to_string()),
+        ("campaign_name", campaign_name.to_string()),
+        ("allowed_collection", allowed_collection.to_string()),
+        ("reward_token", reward_token.to_string()),
+        ("start_time", start_time.to_string()),
+        ("end_time", end_time.to_string()),
+        ("verified_reward_token", verified_reward_token.to_string()),
+        ("verified_collection", verified_collection.to_string()),
+    ])
+}
+
+pub fn register_nfts_event(name: &str, campaign_addr: &Addr, token_ids: &[String]) -> Event {
+    stakify_event(name).add_attributes([
+        ("campaign_addr", campaign_addr.to_string()),
+        ("token_ids", or_none(token_ids.join(","))),
+    ])
+}
+
+pub fn receipt_created_event(campaign_addr: &Addr, receipt_collection: &str) -> Event {
+    stakify_event("factory-receipt-created").add_attributes([
+        ("campaign_addr", campaign_addr.to_string()),
+        ("receipt_collection", receipt_collection.to_string()),
+    ])
+}
+
+pub fn campaign_created_event(campaign_key: u64, campaign_addr: &str, owner: &Addr) -> Event {
+    stakify_event("factory-campaign-created").add_attributes([
+        ("campaign_key", campaign_key.to_string()),
+        ("campaign_addr", campaign_addr.to_string()),
+        ("owner", owner.to_string()),
+    ])
+}
+
+pub fn set_governance_event(governance: &Governance) -> Event {
+    stakify_event("factory-set-governance").add_attributes([
+        ("group_addr", governance.group_addr.to_string()),
+        ("quorum", governance.quorum.to_string()),
+        ("voting_period", governance.voting_period.to_string()),
+        ("timelock", governance.timelock.to_string()),
+    ])
+}
+
+pub fn propose_event(proposal: &Proposal) -> Event {
+    stakify_event("factory-propose").add_attributes([
+        ("proposal_id", proposal.id.to_string()),
+        ("proposer", proposal.proposer.to_string()),
+        ("expires_at", proposal.expires_at.to_string()),
+    ])
+}
+
+pub fn vote_event(proposal: &Proposal, voter: &Addr, weight: u64) -> Event {
+    stakify_event("factory-vote").add_attributes([
+        ("proposal_id", proposal.id.to_string()),
+        ("voter", voter.to_string()),
+        ("weight", weight.to_string()),
+        ("yes_weight", proposal.yes_weight.to_string()),
+        ("passed", proposal.passed_at.is_some().to_string()),
+    ])
+}
+
+pub fn execute_proposal_event(proposal_id: u64) -> Event {
+    stakify_event("factory-execute-proposal")
+        .add_attributes([("proposal_id", proposal_id.to_string())])
+}
+
+pub fn update_verified_assets_event(
+    require_verified: bool,
+    update: &VerifiedAssetsUpdate,
+) -> Event {
+    let added = update
+        .add
+        .iter()
+        .map(|asset_label| asset_label.asset.key())
+        .collect::<Vec<_>>();
+    let removed = update
+        .remove
+        .iter()
+        .map(|asset| asset.key())
+        .collect::<Vec<_>>();
+    stakify_event("factory-update-verified-assets").add_attributes([
+        ("require_verified", require_verified.to_string()),
+        ("added", or_none(added.join(","))),
+        ("removed", or_none(removed.join(","))),
+    ])
+}
+
+pub fn set_creator_profile_event(creator: &Addr, profile: &CreatorProfile) -> Event {
+    // creator verified without a profile has no display name
+    stakify_event("factory-set-creator-profile").add_attributes([
+        ("creator", creator.to_string()),
+        ("display_name", or_none(profile.display_name.clone())),
+        ("verified", profile.verified.to_string()),
+    ])
+}
+
+pub fn claim_many_event(claimer: &Addr, campaign_ids: &[u64]) -> Event {
+    stakify_event("factory-claim-many").add_attributes([
+        ("claimer", claimer.to_string()),
+        (
+            "campaign_ids",
+            campaign_ids
+                .iter()
+                .map(|campaign_id| campaign_id.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+    ])
+}
+
+pub fn claim_result_event(campaign_id: u64, error: Option<String>) -> Event {
+    stakify_event("factory-claim-result").add_attributes([
+        ("campaign_id", campaign_id.to_string()),
+        ("success", error.is_none().to_string()),
+        ("error", or_none(error.unwrap_or_default())),
+    ])
+}
@@ -1,11 +1,20 @@
-use crate::state::{ConfigResponse, FactoryCampaign};
-use campaign::state::{AssetToken, LockupTerm};
+use crate::state::{
+    ConfigResponse, CreatorProfile, FactoryCampaign, GlobalStats, Governance, NftRegistration,
+    Proposal, ProposalAction, UserPositionsResponse, Verification, VerifiedAsset,
+    VerifiedAssetsUpdate,
+};
+use campaign::state::{AssetToken, ExclusivityRules, LockupTerm, TermPolicy, TokenStaking};
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Decimal, Uint128};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     /// Campaign code ID
     pub campaign_code_id: u64,
+    /// Allowed lockup terms of campaigns, custom durations if not set
+    pub term_policy: Option<TermPolicy>,
+    /// Code ID of cw721-base for receipts of staked positions, no receipts if not set
+    pub receipt_code_id: Option<u64>,
 }
 
 #[cw_serde]
@@ -14,6 +23,9 @@ pub enum ExecuteMsg {
     UpdateConfig {
         owner: Option<String>,
         campaign_code_id: Option<u64>,
+        term_policy: Option<TermPolicy>,
+        receipt_code_id: Option<u64>,
+        max_active_campaigns: Option<u64>,
     },
     /// CreateCampaign instantiates pair contract
     CreateCampaign {
@@ -27,10 +39,65 @@ pub enum ExecuteMsg {
 
         limit_per_staker: u64,
         // status: String, // pending | upcoming | active | ended
-        reward_token_info: AssetToken, // reward token
-        allowed_collection: String,    // staking collection nft
-        lockup_term: Vec<LockupTerm>,  // flexible, 15days, 30days, 60days
+        reward_token_info: AssetToken,         // reward token
+        allowed_collection: String,            // staking collection nft
+        lockup_term: Vec<LockupTerm>,          // flexible (value 0), 15days, 30days, 60days
+        token_staking: Option<TokenStaking>,   // cw20 token staked alongside nfts
+        exclusivity: Option<ExclusivityRules>, // opt into exclusivity of nft registry
+        soft_staking: Option<bool>,            // nfts stay in wallets of stakers if true
+        receipt: Option<bool>,                 // mint receipt nft of each staked position if true
     },
+    /// RegisterNfts is called by a campaign when nfts are staked
+    RegisterNfts { token_ids: Vec<String> },
+    /// UnregisterNfts is called by a campaign when nfts are unstaked
+    UnregisterNfts { token_ids: Vec<String> },
+    /// UpdateEndTime is called by a campaign when its end time is updated
+    UpdateEndTime { end_time: u64 },
+    /// RecordClaim is called by a campaign when reward is claimed
+    RecordClaim { amount: Uint128 },
+    /// SetGovernance hands privileged actions of owner to a cw4 group, only once
+    SetGovernance {
+        group_addr: String,
+        quorum: Decimal,
+        voting_period: u64,
+        timelock: u64,
+    },
+    /// Propose opens a proposal approved by the proposer, only members of group
+    Propose { action: ProposalAction },
+    /// Vote approves an open proposal with weight of member at its start height
+    Vote { proposal_id: u64 },
+    /// ExecuteProposal applies a passed proposal after its timelock, anyone can execute it
+    ExecuteProposal { proposal_id: u64 },
+    /// UpdateVerifiedAssets adds or removes verified reward tokens and collections
+    UpdateVerifiedAssets(VerifiedAssetsUpdate),
+    /// SetCreatorProfile sets display name of sender
+    SetCreatorProfile { display_name: String },
+    /// VerifyCreator sets verified flag of creator profile, only owner
+    VerifyCreator { creator: String, verified: bool },
+    /// ClaimMany claims all reward of sender from campaigns, failed claims are reported in events
+    ClaimMany { campaign_ids: Vec<u64> },
+}
+
+/// queries of cw4 group contracts used by governance
+#[cw_serde]
+pub enum Cw4QueryMsg {
+    Member {
+        addr: String,
+        at_height: Option<u64>,
+    },
+    TotalWeight {
+        at_height: Option<u64>,
+    },
+}
+
+#[cw_serde]
+pub struct MemberResponse {
+    pub weight: Option<u64>,
+}
+
+#[cw_serde]
+pub struct TotalWeightResponse {
+    pub weight: u64,
 }
 
 #[cw_serde]
@@ -42,6 +109,7 @@ pub enum QueryMsg {
     #[returns(FactoryCampaign)]
     Campaign { campaign_id: u64 },
 
+    /// campaigns ordered by campaign key, default 30 and max 100 per page
     #[returns(Vec<FactoryCampaign>)]
     Campaigns {
         start_after: Option<u64>,
@@ -50,4 +118,60 @@ pub enum QueryMsg {
 
     #[returns(Vec<String>)]
     CampaignAddrs {},
+
+    /// last campaign which staked the nft of collection
+    #[returns(Option<NftRegistration>)]
+    NftRegistration {
+        collection: String,
+        token_id: String,
+    },
+
+    /// cw4 group governing the factory, owner if not set
+    #[returns(Option<Governance>)]
+    Governance {},
+
+    #[returns(Proposal)]
+    Proposal { proposal_id: u64 },
+
+    /// verification of reward token or collection, none if unverified
+    #[returns(Option<Verification>)]
+    Verification { asset: VerifiedAsset },
+
+    /// default 30 and max 100 verifications per page
+    #[returns(Vec<Verification>)]
+    Verifications {
+        start_after: Option<VerifiedAsset>,
+        limit: Option<u32>,
+    },
+
+    /// campaigns with unverified assets are rejected
+    #[returns(bool)]
+    RequireVerified {},
+
+    #[returns(Option<CreatorProfile>)]
+    CreatorProfile { creator: String },
+
+    /// campaigns created by creator ordered by campaign key, default 30 and max 100 per page
+    #[returns(Vec<FactoryCampaign>)]
+    CampaignsByCreator {
+        creator: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// positions of user in campaigns after start_after, default 10 and max 30 campaigns per page
+    #[returns(UserPositionsResponse)]
+    UserPositions {
+        user: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// totals of all campaigns, rewards distributed are ordered by reward token after
+    /// start_after, default 30 and max 100 reward tokens per page
+    #[returns(GlobalStats)]
+    GlobalStats {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
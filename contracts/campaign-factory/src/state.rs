@@ -1,12 +1,16 @@
-use campaign::state::TokenInfo;
+use campaign::state::{AssetToken, NftInfo, TermPolicy, TokenInfo};
+pub use campaign::state::{Metadata, Trait};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Decimal, Empty, Uint128};
 use cw_storage_plus::{Item, Map};
 
 #[cw_serde]
 pub struct Config {
     pub owner: Addr,
     pub campaign_code_id: u64,
+    pub term_policy: TermPolicy, // allowed lockup terms of new campaigns
+    pub receipt_code_id: Option<u64>, // cw721-base of receipts, no receipts if not set
+    pub max_active_campaigns: u64, // per creator, 0 is unlimited
 }
 
 // We define a custom struct for each query response
@@ -14,6 +18,9 @@ pub struct Config {
 pub struct ConfigResponse {
     pub owner: String,
     pub campaign_code_id: u64,
+    pub term_policy: TermPolicy,
+    pub receipt_code_id: Option<u64>,
+    pub max_active_campaigns: u64,
 }
 
 #[cw_serde]
@@ -24,36 +31,150 @@ pub struct FactoryCampaign {
     pub allowed_collection: Addr,
 }
 
-pub const CONFIG: Item<Config> = Item::new("config");
-pub const CAMPAIGNS: Map<u64, FactoryCampaign> = Map::new("campaigns");
-pub const NUMBER_OF_CAMPAIGNS: Item<u64> = Item::new("number_of_campaigns");
-pub const ADDR_CAMPAIGNS: Item<Vec<String>> = Item::new("addr_campaigns");
+// campaign which staked an nft last, updated by callbacks of campaigns
+#[cw_serde]
+pub struct NftRegistration {
+    pub campaign_addr: Addr,
+    pub staked: bool,    // nft is staked in campaign_addr
+    pub updated_at: u64, // time of stake or unstake
+}
+
+// cw4 group voting on privileged actions instead of owner
+#[cw_serde]
+pub struct Governance {
+    pub group_addr: Addr,
+    pub quorum: Decimal,    // share of total weight of group approving a proposal
+    pub voting_period: u64, // seconds a proposal is open for votes
+    pub timelock: u64,      // seconds between passing and execution of a proposal
+}
+
+#[cw_serde]
+pub enum ProposalAction {
+    UpdateConfig {
+        owner: Option<String>,
+        campaign_code_id: Option<u64>,
+        term_policy: Option<TermPolicy>,
+        receipt_code_id: Option<u64>,
+        max_active_campaigns: Option<u64>,
+    },
+    UpdateGovernance(Governance),
+    VerifyCreator {
+        creator: String,
+        verified: bool,
+    },
+    UpdateVerifiedAssets(VerifiedAssetsUpdate),
+}
 
 #[cw_serde]
-pub struct Trait {
-    pub display_type: Option<String>,
-    pub trait_type: String,
-    pub value: String,
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Addr,
+    pub action: ProposalAction,
+    pub start_height: u64, // weights of members are snapshotted at this height
+    pub expires_at: u64,   // end of voting period
+    pub total_weight: u64, // total weight of group at start_height
+    pub yes_weight: u64,   // weight of members who approved
+    pub passed_at: Option<u64>, // time quorum is reached, timelock starts
+    pub executed: bool,
+}
+
+// reward token or nft collection verified by factory
+#[cw_serde]
+pub enum VerifiedAsset {
+    Token(TokenInfo),
+    Collection(String),
+}
+
+impl VerifiedAsset {
+    pub fn key(&self) -> String {
+        match self {
+            VerifiedAsset::Token(token) => format!("token:{}", token),
+            VerifiedAsset::Collection(collection) => format!("collection:{}", collection),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct AssetLabel {
+    pub asset: VerifiedAsset,
+    pub label: String, // verified name shown on listing page
+}
+
+#[cw_serde]
+pub struct VerifiedAssetsUpdate {
+    pub require_verified: Option<bool>, // reject campaigns with unverified assets
+    pub add: Vec<AssetLabel>,
+    pub remove: Vec<VerifiedAsset>,
+}
+
+#[cw_serde]
+pub struct Verification {
+    pub asset: VerifiedAsset,
+    pub label: String,
+    pub verified_at: u64,
 }
 
-// see: https://docs.opensea.io/docs/metadata-standards
 #[cw_serde]
 #[derive(Default)]
-pub struct Metadata {
-    pub image: Option<String>,
-    pub image_data: Option<String>,
-    pub external_url: Option<String>,
-    pub description: Option<String>,
-    pub name: Option<String>,
-    pub attributes: Option<Vec<Trait>>,
-    pub background_color: Option<String>,
-    pub animation_url: Option<String>,
-    pub youtube_url: Option<String>,
-    /// This is how much the minter takes as a cut when sold
-    /// royalties are owed on this token if it is Some
-    pub royalty_percentage: Option<u64>,
-    /// The payment address, may be different to or the same
-    /// as the minter addr
-    /// question: how do we validate this?
-    pub royalty_payment_address: Option<String>,
+pub struct CreatorProfile {
+    pub display_name: String, // set by creator
+    pub verified: bool,       // set by owner or governance
 }
+
+// staked nfts and claimable reward of user in a campaign
+#[cw_serde]
+pub struct UserPosition {
+    pub campaign_key: u64,
+    pub campaign_addr: Addr,
+    pub reward_token: TokenInfo,
+    pub nfts: Vec<NftInfo>, // at most 100 nfts of user in campaign
+    pub nft_count: u64,     // all nfts of user in campaign
+    pub claimable_reward: Uint128,
+}
+
+#[cw_serde]
+pub struct UserPositionsResponse {
+    pub positions: Vec<UserPosition>, // campaigns without position of user are skipped
+    pub next_start_after: Option<u64>, // none if all campaigns are scanned
+}
+
+// totals of all campaigns, kept up to date by callbacks of campaigns
+#[cw_serde]
+pub struct GlobalStats {
+    pub total_campaigns: u64,
+    pub total_nft_staked: u64,
+    pub rewards_distributed: Vec<AssetToken>, // claimed reward per reward token in the page
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const CREATOR_PROFILES: Map<Addr, CreatorProfile> = Map::new("creator_profiles");
+// end time of campaigns by (creator, campaign_key), creator is sender of CreateCampaign
+pub const CREATOR_CAMPAIGNS: Map<(Addr, u64), u64> = Map::new("creator_campaigns");
+// campaigns by (creator, end_time, campaign_key), active campaigns of creator end after now
+pub const CREATOR_END_TIMES: Map<(Addr, u64, u64), Empty> = Map::new("creator_end_times");
+// creator of campaign by campaign_key
+pub const CAMPAIGN_CREATORS: Map<u64, Addr> = Map::new("campaign_creators");
+// creator and end time of the campaign being created
+pub const PENDING_CREATOR: Item<(Addr, u64)> = Item::new("pending_creator");
+// verified assets by VerifiedAsset::key
+pub const VERIFIED_ASSETS: Map<String, Verification> = Map::new("verified_assets");
+// campaigns must use verified reward token and collection, only flagged if not set
+pub const REQUIRE_VERIFIED: Item<bool> = Item::new("require_verified");
+pub const GOVERNANCE: Item<Governance> = Item::new("governance");
+pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
+pub const NUMBER_OF_PROPOSALS: Item<u64> = Item::new("number_of_proposals");
+// weight of votes by (proposal_id, voter)
+pub const BALLOTS: Map<(u64, Addr), u64> = Map::new("ballots");
+pub const CAMPAIGNS: Map<u64, FactoryCampaign> = Map::new("campaigns");
+pub const NUMBER_OF_CAMPAIGNS: Item<u64> = Item::new("number_of_campaigns");
+pub const ADDR_CAMPAIGNS: Item<Vec<String>> = Item::new("addr_campaigns");
+// receipt collection is instantiated for the campaign being created
+pub const PENDING_RECEIPT: Item<bool> = Item::new("pending_receipt");
+// key of campaigns by address, only these campaigns can update the registry
+pub const CAMPAIGN_KEYS: Map<Addr, u64> = Map::new("campaign_keys");
+// number of nfts staked in all campaigns
+pub const TOTAL_NFT_STAKED: Item<u64> = Item::new("total_nft_staked");
+// reward claimed from all campaigns by reward token
+pub const REWARDS_DISTRIBUTED: Map<String, AssetToken> = Map::new("rewards_distributed");
+// registry of nfts by (collection, token_id)
+pub const NFT_REGISTRY: Map<(Addr, String), NftRegistration> = Map::new("nft_registry");
@@ -1,8 +1,19 @@
 #[cfg(test)]
 pub mod env {
-    use cosmwasm_std::{Addr, Empty};
-    use cw20::MinterResponse;
+    use campaign::msg::ExecuteMsg as CampaignExecuteMsg;
+    use campaign::state::{AssetToken, ExclusivityRules, LockupTerm, TokenInfo, TokenStaking};
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{
+        to_binary, Addr, Binary, BlockInfo, Deps, DepsMut, Empty, Env, MessageInfo, Order, Record,
+        Response, StdError, StdResult, Storage, Uint128,
+    };
+    use cw20::{Cw20ExecuteMsg, MinterResponse};
+    use cw721_base::{
+        ContractError as Cw721ContractError, MintMsg as Cw721MintMsg, QueryMsg as Cw721QueryMsg,
+    };
     use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+    use cw_storage_plus::Map;
+    use std::cell::Cell;
 
     use crate::contract::{
         execute as FactoryExecute, instantiate as FactoryInstantiate, query as FactoryQuery,
@@ -15,20 +26,25 @@ pub mod env {
 
     use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
 
-    use cw721_base::entry::{
-        execute as Cw721Execute, instantiate as Cw721Instantiate, query as Cw721Query,
-    };
-
     use cw721_base::msg::InstantiateMsg as Cw721InstantiateMsg;
 
     use campaign::contract::{execute as Execute, instantiate as Instantiate, query as Query};
 
-    use crate::msg::InstantiateMsg as FactoryInstantiateMsg;
+    use crate::msg::{
+        Cw4QueryMsg, ExecuteMsg as FactoryExecuteMsg, InstantiateMsg as FactoryInstantiateMsg,
+        MemberResponse, TotalWeightResponse,
+    };
+    use crate::state::Metadata;
+
+    pub type Cw721ExecuteMsg = cw721_base::ExecuteMsg<Option<Metadata>, Empty>;
+    pub type Cw721MetadataContract<'a> =
+        cw721_base::Cw721Contract<'a, Option<Metadata>, Empty, Empty, Empty>;
 
     pub const ADMIN: &str = "aura1000000000000000000000000000000000admin";
     pub const USER_1: &str = "aura1000000000000000000000000000000000user1";
     pub const USER_2: &str = "aura1000000000000000000000000000000000user2";
 
+    #[allow(dead_code)]
     pub struct ContractInfo {
         pub contract_addr: String,
         pub contract_code_id: u64,
@@ -50,9 +66,64 @@ pub mod env {
         Box::new(contract)
     }
 
+    thread_local! {
+        static STORAGE_READS: Cell<u64> = const { Cell::new(0) };
+    }
+
+    // number of storage entries read by campaign executes of the test
+    pub fn storage_reads() -> u64 {
+        STORAGE_READS.with(|reads| reads.get())
+    }
+
+    // storage of campaign counting the entries read
+    struct CountingStorage<'a>(&'a mut dyn Storage);
+
+    fn count_read() {
+        STORAGE_READS.with(|reads| reads.set(reads.get() + 1));
+    }
+
+    impl Storage for CountingStorage<'_> {
+        fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+            count_read();
+            self.0.get(key)
+        }
+
+        fn range<'a>(
+            &'a self,
+            start: Option<&[u8]>,
+            end: Option<&[u8]>,
+            order: Order,
+        ) -> Box<dyn Iterator<Item = Record> + 'a> {
+            Box::new(self.0.range(start, end, order).inspect(|_| count_read()))
+        }
+
+        fn set(&mut self, key: &[u8], value: &[u8]) {
+            self.0.set(key, value)
+        }
+
+        fn remove(&mut self, key: &[u8]) {
+            self.0.remove(key)
+        }
+    }
+
+    fn campaign_execute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: CampaignExecuteMsg,
+    ) -> Result<Response, campaign::ContractError> {
+        let mut storage = CountingStorage(deps.storage);
+        let deps = DepsMut {
+            storage: &mut storage,
+            api: deps.api,
+            querier: deps.querier,
+        };
+        Execute(deps, env, info, msg)
+    }
+
     // campaign contract
     fn contract_template() -> Box<dyn Contract<Empty>> {
-        let contract = ContractWrapper::new(Execute, Instantiate, Query);
+        let contract = ContractWrapper::new(campaign_execute, Instantiate, Query);
         Box::new(contract)
     }
 
@@ -63,9 +134,99 @@ pub mod env {
         Box::new(contract)
     }
 
+    // collection contract entry points keeping on-chain metadata
+    fn cw721_instantiate(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: Cw721InstantiateMsg,
+    ) -> StdResult<Response> {
+        Cw721MetadataContract::default().instantiate(deps, env, info, msg)
+    }
+
+    fn cw721_execute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: Cw721ExecuteMsg,
+    ) -> Result<Response, Cw721ContractError> {
+        Cw721MetadataContract::default().execute(deps, env, info, msg)
+    }
+
+    fn cw721_query(deps: Deps, env: Env, msg: Cw721QueryMsg<Empty>) -> StdResult<Binary> {
+        Cw721MetadataContract::default().query(deps, env, msg)
+    }
+
+    // cw4 group with fixed members, weights are the same at every height
+    const GROUP_MEMBERS: Map<String, u64> = Map::new("members");
+
+    #[cw_serde]
+    pub struct GroupInstantiateMsg {
+        pub members: Vec<(String, u64)>,
+    }
+
+    fn group_instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: GroupInstantiateMsg,
+    ) -> StdResult<Response> {
+        for (addr, weight) in msg.members {
+            GROUP_MEMBERS.save(deps.storage, addr, &weight)?;
+        }
+        Ok(Response::new())
+    }
+
+    fn group_execute(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> Result<Response, StdError> {
+        Ok(Response::new())
+    }
+
+    fn group_query(deps: Deps, _env: Env, msg: Cw4QueryMsg) -> StdResult<Binary> {
+        match msg {
+            Cw4QueryMsg::Member { addr, .. } => to_binary(&MemberResponse {
+                weight: GROUP_MEMBERS.may_load(deps.storage, addr)?,
+            }),
+            Cw4QueryMsg::TotalWeight { .. } => to_binary(&TotalWeightResponse {
+                weight: GROUP_MEMBERS
+                    .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+                    .map(|member| member.map(|(_, weight)| weight))
+                    .sum::<StdResult<u64>>()?,
+            }),
+        }
+    }
+
+    // instantiate cw4 group of members with their weights
+    pub fn instantiate_group(app: &mut App, members: &[(&str, u64)]) -> String {
+        let group_code_id = app.store_code(Box::new(ContractWrapper::new(
+            group_execute,
+            group_instantiate,
+            group_query,
+        )));
+        app.instantiate_contract(
+            group_code_id,
+            Addr::unchecked(ADMIN),
+            &GroupInstantiateMsg {
+                members: members
+                    .iter()
+                    .map(|(addr, weight)| (addr.to_string(), *weight))
+                    .collect(),
+            },
+            &[],
+            "group",
+            None,
+        )
+        .unwrap()
+        .to_string()
+    }
+
     // collection contract
     fn collection_contract_template() -> Box<dyn Contract<Empty>> {
-        let contract = ContractWrapper::new(Cw721Execute, Cw721Instantiate, Cw721Query);
+        let contract = ContractWrapper::new(cw721_execute, cw721_instantiate, cw721_query);
         Box::new(contract)
     }
 
@@ -84,6 +245,8 @@ pub mod env {
         // create instantiate message for contract
         let factory_instantiate_msg = FactoryInstantiateMsg {
             campaign_code_id: app.store_code(contract_template()),
+            term_policy: None,
+            receipt_code_id: Some(collection_contract_code_id),
         };
 
         // factory instantiate contract
@@ -164,4 +327,204 @@ pub mod env {
 
         (app, contracts)
     }
+    // mint nfts to owner and approve them to spender
+    pub fn mint_nfts(
+        app: &mut App,
+        collection_contract: &str,
+        owner: &str,
+        spender: &str,
+        token_ids: &[u64],
+    ) {
+        for id in token_ids {
+            mint_nft(
+                app,
+                collection_contract,
+                owner,
+                spender,
+                *id,
+                Metadata::default(),
+            );
+        }
+    }
+
+    // mint nft with metadata to owner and approve it to spender
+    pub fn mint_nft(
+        app: &mut App,
+        collection_contract: &str,
+        owner: &str,
+        spender: &str,
+        token_id: u64,
+        metadata: Metadata,
+    ) {
+        let mint_nft_msg = Cw721ExecuteMsg::Mint(Cw721MintMsg {
+            token_id: token_id.to_string(),
+            owner: owner.to_string(),
+            token_uri: None,
+            extension: Some(metadata),
+        });
+        app.execute_contract(
+            Addr::unchecked(ADMIN),
+            Addr::unchecked(collection_contract),
+            &mint_nft_msg,
+            &[],
+        )
+        .unwrap();
+
+        let approve_msg = Cw721ExecuteMsg::Approve {
+            spender: spender.to_string(),
+            token_id: token_id.to_string(),
+            expires: None,
+        };
+        app.execute_contract(
+            Addr::unchecked(owner),
+            Addr::unchecked(collection_contract),
+            &approve_msg,
+            &[],
+        )
+        .unwrap();
+    }
+
+    // create a campaign running from now + 10s to now + 110s by factory contract,
+    // add `reward_amount` reward token to it then move to the start of the campaign.
+    // returns the campaign contract address
+    pub fn create_active_campaign(
+        app: &mut App,
+        contracts: &[ContractInfo],
+        lockup_term: Vec<LockupTerm>,
+        reward_amount: u128,
+    ) -> String {
+        let campaign_contract = create_campaign(app, contracts, lockup_term);
+        fund_campaign(app, contracts, &campaign_contract, reward_amount);
+
+        campaign_contract
+    }
+
+    // create a campaign starting in 10s and ending in 110s by ADMIN, without reward
+    pub fn create_campaign(
+        app: &mut App,
+        contracts: &[ContractInfo],
+        lockup_term: Vec<LockupTerm>,
+    ) -> String {
+        try_create_campaign(app, contracts, lockup_term).unwrap()
+    }
+
+    // create_campaign returning the error message if factory or campaign rejects it
+    pub fn try_create_campaign(
+        app: &mut App,
+        contracts: &[ContractInfo],
+        lockup_term: Vec<LockupTerm>,
+    ) -> Result<String, String> {
+        try_create_custom_campaign(app, contracts, lockup_term, None, None, false, false)
+    }
+
+    // try_create_campaign with cw20 token staking, exclusivity rules, soft staking and receipts
+    pub fn try_create_custom_campaign(
+        app: &mut App,
+        contracts: &[ContractInfo],
+        lockup_term: Vec<LockupTerm>,
+        token_staking: Option<TokenStaking>,
+        exclusivity: Option<ExclusivityRules>,
+        soft_staking: bool,
+        receipt: bool,
+    ) -> Result<String, String> {
+        let factory_contract = &contracts[0].contract_addr;
+        let token_contract = &contracts[1].contract_addr;
+        let collection_contract = &contracts[2].contract_addr;
+
+        let current_block_time = app.block_info().time.seconds();
+        let create_campaign_msg = FactoryExecuteMsg::CreateCampaign {
+            owner: ADMIN.to_string(),
+            campaign_name: "campaign name".to_string(),
+            campaign_image: "campaign image".to_string(),
+            campaign_description: "campaign description".to_string(),
+            start_time: current_block_time + 10,
+            end_time: current_block_time + 110,
+            limit_per_staker: 0,
+            reward_token_info: AssetToken {
+                info: TokenInfo::Token {
+                    contract_addr: token_contract.to_string(),
+                },
+                amount: Uint128::zero(),
+            },
+            allowed_collection: collection_contract.to_string(),
+            lockup_term,
+            token_staking,
+            exclusivity,
+            soft_staking: Some(soft_staking),
+            receipt: Some(receipt),
+        };
+        let response = app
+            .execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(factory_contract),
+                &create_campaign_msg,
+                &[],
+            )
+            .map_err(|err| err.root_cause().to_string())?;
+
+        Ok(response
+            .events
+            .iter()
+            .filter(|event| event.ty == "wasm-stakify-factory-campaign-created")
+            .flat_map(|event| event.attributes.iter())
+            .find(|attr| attr.key == "campaign_addr")
+            .unwrap()
+            .value
+            .clone())
+    }
+
+    // deposit reward to campaign and increase 20 seconds to make it active
+    pub fn fund_campaign(
+        app: &mut App,
+        contracts: &[ContractInfo],
+        campaign_contract: &str,
+        reward_amount: u128,
+    ) {
+        let token_contract = &contracts[1].contract_addr;
+
+        // mint reward token to ADMIN
+        app.execute_contract(
+            Addr::unchecked(ADMIN),
+            Addr::unchecked(token_contract),
+            &Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(reward_amount),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // deposit reward token to campaign
+        app.execute_contract(
+            Addr::unchecked(ADMIN),
+            Addr::unchecked(token_contract),
+            &Cw20ExecuteMsg::IncreaseAllowance {
+                spender: campaign_contract.to_string(),
+                amount: Uint128::from(reward_amount),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(ADMIN),
+            Addr::unchecked(campaign_contract),
+            &CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(reward_amount),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // increase 20 second to make active campaign
+        increase_time(app, 20);
+    }
+
+    pub fn increase_time(app: &mut App, seconds: u64) {
+        app.set_block(BlockInfo {
+            time: app.block_info().time.plus_seconds(seconds),
+            height: app.block_info().height + seconds,
+            chain_id: app.block_info().chain_id,
+        });
+    }
 }
@@ -20,11 +20,11 @@ mod tests {
         };
         use campaign::state::{
             AssetToken, CampaignInfoResult, CampaignInfoUpdate, LockupTerm, NftInfo, NftStake,
-            StakedInfoResult, TokenInfo,
+            StakedInfoResult, TermFill, TokenInfo,
         };
         use campaign::{
             msg::{ExecuteMsg as CampaignExecuteMsg, QueryMsg as CampaignQueryMsg},
-            utils::{add_reward, calc_reward_in_time, sub_reward},
+            utils::{add_reward, calc_index_reward, calc_weighted_reward_in_time, sub_reward},
         };
         use cosmwasm_std::{Addr, BlockInfo, Empty, Uint128};
         use cw20::{BalanceResponse, Cw20ExecuteMsg};
@@ -230,12 +230,20 @@ mod tests {
                     LockupTerm {
                         value: 10,
                         percent: Uint128::new(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     LockupTerm {
                         value: 30,
                         percent: Uint128::new(70u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                 ],
+                token_staking: None,
+                exclusivity: None,
+                soft_staking: None,
+                receipt: None,
             };
 
             // Execute create campaign
@@ -297,10 +305,14 @@ mod tests {
                         LockupTerm {
                             value: 10,
                             percent: Uint128::new(30u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         LockupTerm {
                             value: 30,
                             percent: Uint128::new(70u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                     ],
                     total_nft_staked: 0,
@@ -310,6 +322,22 @@ mod tests {
                     time_calc_nft: 0,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    term_fills: vec![
+                        TermFill {
+                            lockup_term: 10,
+                            nft_count: 0,
+                            max_nfts: None,
+                            accrued_reward: Uint128::zero(),
+                            max_reward: None,
+                        },
+                        TermFill {
+                            lockup_term: 30,
+                            nft_count: 0,
+                            max_nfts: None,
+                            accrued_reward: Uint128::zero(),
+                            max_reward: None,
+                        },
+                    ],
                 }
             );
 
@@ -363,10 +391,14 @@ mod tests {
                         LockupTerm {
                             value: 10,
                             percent: Uint128::new(30u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         LockupTerm {
                             value: 30,
                             percent: Uint128::new(70u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                     ],
                     total_nft_staked: 0,
@@ -376,6 +408,22 @@ mod tests {
                     time_calc_nft: 0,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    term_fills: vec![
+                        TermFill {
+                            lockup_term: 10,
+                            nft_count: 0,
+                            max_nfts: None,
+                            accrued_reward: Uint128::zero(),
+                            max_reward: None,
+                        },
+                        TermFill {
+                            lockup_term: 30,
+                            nft_count: 0,
+                            max_nfts: None,
+                            accrued_reward: Uint128::zero(),
+                            max_reward: None,
+                        },
+                    ],
                 }
             );
 
@@ -487,7 +535,9 @@ mod tests {
                 nfts: vec![NftStake {
                     token_id: "1".to_string(),
                     lockup_term: 10,
+                    weight_proof: None,
                 }],
+                bonus: None,
             };
             let start_time_1 = app.block_info().time.seconds();
 
@@ -520,11 +570,14 @@ mod tests {
                     pending_reward: Uint128::from(0u128),
                     lockup_term: LockupTerm {
                         value: 10,
-                        percent: Uint128::from(30u128)
+                        percent: Uint128::from(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     is_end_reward: false,
                     start_time: start_time_1,
-                    end_time: start_time_1 + 10
+                    end_time: start_time_1 + 10,
+                    weight: 100,
                 }
             );
 
@@ -548,14 +601,18 @@ mod tests {
                         pending_reward: Uint128::from(0u128),
                         lockup_term: LockupTerm {
                             value: 10,
-                            percent: Uint128::from(30u128)
+                            percent: Uint128::from(30u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         is_end_reward: false,
                         start_time: start_time_1,
-                        end_time: start_time_1 + 10
+                        end_time: start_time_1 + 10,
+                        weight: 100,
                     }],
                     reward_debt: Uint128::zero(),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    set_multiplier: 100,
                 }
             );
 
@@ -585,11 +642,14 @@ mod tests {
                     pending_reward: Uint128::from(3000u128),
                     lockup_term: LockupTerm {
                         value: 10,
-                        percent: Uint128::from(30u128)
+                        percent: Uint128::from(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     is_end_reward: false,
                     start_time: start_time_1,
-                    end_time: start_time_1 + 10
+                    end_time: start_time_1 + 10,
+                    weight: 100,
                 }
             );
 
@@ -598,7 +658,9 @@ mod tests {
                 nfts: vec![NftStake {
                     token_id: "2".to_string(),
                     lockup_term: 10,
+                    weight_proof: None,
                 }],
+                bonus: None,
             };
             let start_time_2 = app.block_info().time.seconds();
 
@@ -631,11 +693,14 @@ mod tests {
                     pending_reward: Uint128::from(3000u128),
                     lockup_term: LockupTerm {
                         value: 10,
-                        percent: Uint128::from(30u128)
+                        percent: Uint128::from(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     is_end_reward: false,
                     start_time: start_time_1,
-                    end_time: start_time_1 + 10
+                    end_time: start_time_1 + 10,
+                    weight: 100,
                 }
             );
 
@@ -658,11 +723,14 @@ mod tests {
                     pending_reward: Uint128::from(0u128),
                     lockup_term: LockupTerm {
                         value: 10,
-                        percent: Uint128::from(30u128)
+                        percent: Uint128::from(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     is_end_reward: false,
                     start_time: start_time_2,
-                    end_time: start_time_2 + 10
+                    end_time: start_time_2 + 10,
+                    weight: 100,
                 }
             );
 
@@ -695,10 +763,13 @@ mod tests {
                             lockup_term: LockupTerm {
                                 value: 10,
                                 percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_1,
-                            end_time: start_time_1 + 10
+                            end_time: start_time_1 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "2".to_string(),
@@ -707,20 +778,25 @@ mod tests {
                             lockup_term: LockupTerm {
                                 value: 10,
                                 percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_2,
-                            end_time: start_time_2 + 10
+                            end_time: start_time_2 + 10,
+                            weight: 100,
                         },
                     ],
                     reward_debt: Uint128::from(21000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    set_multiplier: 100,
                 }
             );
 
             // USER_1 claim reward msg
             let claim_reward_msg = CampaignExecuteMsg::ClaimReward {
                 amount: Uint128::from(21000u128),
+                owner: None,
             };
 
             // Execute claim reward
@@ -755,10 +831,13 @@ mod tests {
                             lockup_term: LockupTerm {
                                 value: 10,
                                 percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_1,
-                            end_time: start_time_1 + 10
+                            end_time: start_time_1 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "2".to_string(),
@@ -767,14 +846,18 @@ mod tests {
                             lockup_term: LockupTerm {
                                 value: 10,
                                 percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_2,
-                            end_time: start_time_2 + 10
+                            end_time: start_time_2 + 10,
+                            weight: 100,
                         },
                     ],
                     reward_debt: Uint128::from(0u128),
                     reward_claimed: Uint128::from(21000u128),
+                    set_multiplier: 100,
                 }
             );
 
@@ -821,13 +904,17 @@ mod tests {
                         lockup_term: LockupTerm {
                             value: 10,
                             percent: Uint128::from(30u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         is_end_reward: false,
                         start_time: start_time_2,
-                        end_time: start_time_2 + 10
+                        end_time: start_time_2 + 10,
+                        weight: 100,
                     },],
                     reward_debt: Uint128::from(9000u128),
                     reward_claimed: Uint128::from(21000u128),
+                    set_multiplier: 100,
                 }
             );
 
@@ -868,13 +955,17 @@ mod tests {
                         lockup_term: LockupTerm {
                             value: 10,
                             percent: Uint128::from(30u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         is_end_reward: true,
                         start_time: start_time_2,
-                        end_time: start_time_2 + 10
+                        end_time: start_time_2 + 10,
+                        weight: 100,
                     },],
                     reward_debt: Uint128::from(12000u128),
-                    reward_claimed: Uint128::from(21000u128)
+                    reward_claimed: Uint128::from(21000u128),
+                    set_multiplier: 100,
                 }
             );
 
@@ -927,10 +1018,14 @@ mod tests {
                         LockupTerm {
                             value: 10,
                             percent: Uint128::new(30u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         LockupTerm {
                             value: 30,
                             percent: Uint128::new(70u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                     ],
                     total_nft_staked: 1,
@@ -940,6 +1035,22 @@ mod tests {
                     time_calc_nft: current_block_time + 110,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    term_fills: vec![
+                        TermFill {
+                            lockup_term: 10,
+                            nft_count: 1,
+                            max_nfts: None,
+                            accrued_reward: Uint128::new(33000u128),
+                            max_reward: None,
+                        },
+                        TermFill {
+                            lockup_term: 30,
+                            nft_count: 0,
+                            max_nfts: None,
+                            accrued_reward: Uint128::zero(),
+                            max_reward: None,
+                        },
+                    ],
                 }
             );
 
@@ -964,13 +1075,17 @@ mod tests {
                         lockup_term: LockupTerm {
                             value: 10,
                             percent: Uint128::from(30u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         is_end_reward: true,
                         start_time: start_time_2,
-                        end_time: start_time_2 + 10
+                        end_time: start_time_2 + 10,
+                        weight: 100,
                     },],
                     reward_debt: Uint128::from(12000u128),
                     reward_claimed: Uint128::from(21000u128),
+                    set_multiplier: 100,
                 }
             );
 
@@ -1195,12 +1310,20 @@ mod tests {
                     LockupTerm {
                         value: 10,
                         percent: Uint128::new(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     LockupTerm {
                         value: 30,
                         percent: Uint128::new(70u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                 ],
+                token_staking: None,
+                exclusivity: None,
+                soft_staking: None,
+                receipt: None,
             };
 
             // Execute create campaign
@@ -1262,10 +1385,14 @@ mod tests {
                         LockupTerm {
                             value: 10,
                             percent: Uint128::new(30u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         LockupTerm {
                             value: 30,
                             percent: Uint128::new(70u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                     ],
                     total_nft_staked: 0,
@@ -1275,6 +1402,22 @@ mod tests {
                     time_calc_nft: 0,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    term_fills: vec![
+                        TermFill {
+                            lockup_term: 10,
+                            nft_count: 0,
+                            max_nfts: None,
+                            accrued_reward: Uint128::zero(),
+                            max_reward: None,
+                        },
+                        TermFill {
+                            lockup_term: 30,
+                            nft_count: 0,
+                            max_nfts: None,
+                            accrued_reward: Uint128::zero(),
+                            max_reward: None,
+                        },
+                    ],
                 }
             );
 
@@ -1328,10 +1471,14 @@ mod tests {
                         LockupTerm {
                             value: 10,
                             percent: Uint128::new(30u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         LockupTerm {
                             value: 30,
                             percent: Uint128::new(70u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                     ],
                     total_nft_staked: 0,
@@ -1341,6 +1488,22 @@ mod tests {
                     time_calc_nft: 0,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    term_fills: vec![
+                        TermFill {
+                            lockup_term: 10,
+                            nft_count: 0,
+                            max_nfts: None,
+                            accrued_reward: Uint128::zero(),
+                            max_reward: None,
+                        },
+                        TermFill {
+                            lockup_term: 30,
+                            nft_count: 0,
+                            max_nfts: None,
+                            accrued_reward: Uint128::zero(),
+                            max_reward: None,
+                        },
+                    ],
                 }
             );
 
@@ -1452,7 +1615,9 @@ mod tests {
                 nfts: vec![NftStake {
                     token_id: "1".to_string(),
                     lockup_term: 10,
+                    weight_proof: None,
                 }],
+                bonus: None,
             };
             let start_time_1 = app.block_info().time.seconds();
 
@@ -1471,7 +1636,9 @@ mod tests {
                 nfts: vec![NftStake {
                     token_id: "2".to_string(),
                     lockup_term: 10,
+                    weight_proof: None,
                 }],
+                bonus: None,
             };
             let start_time_2 = app.block_info().time.seconds();
 
@@ -1504,11 +1671,14 @@ mod tests {
                     pending_reward: Uint128::from(0u128),
                     lockup_term: LockupTerm {
                         value: 10,
-                        percent: Uint128::from(30u128)
+                        percent: Uint128::from(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     is_end_reward: false,
                     start_time: start_time_1,
-                    end_time: start_time_1 + 10
+                    end_time: start_time_1 + 10,
+                    weight: 100,
                 }
             );
 
@@ -1531,11 +1701,14 @@ mod tests {
                     pending_reward: Uint128::from(0u128),
                     lockup_term: LockupTerm {
                         value: 10,
-                        percent: Uint128::from(30u128)
+                        percent: Uint128::from(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     is_end_reward: false,
                     start_time: start_time_2,
-                    end_time: start_time_2 + 10
+                    end_time: start_time_2 + 10,
+                    weight: 100,
                 }
             );
 
@@ -1560,11 +1733,14 @@ mod tests {
                             pending_reward: Uint128::from(0u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_1,
-                            end_time: start_time_1 + 10
+                            end_time: start_time_1 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "2".to_string(),
@@ -1572,15 +1748,19 @@ mod tests {
                             pending_reward: Uint128::from(0u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_2,
-                            end_time: start_time_2 + 10
+                            end_time: start_time_2 + 10,
+                            weight: 100,
                         }
                     ],
                     reward_debt: Uint128::zero(),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    set_multiplier: 100,
                 },
             );
 
@@ -1596,7 +1776,9 @@ mod tests {
                 nfts: vec![NftStake {
                     token_id: "6".to_string(),
                     lockup_term: 30,
+                    weight_proof: None,
                 }],
+                bonus: None,
             };
             let start_time_6 = app.block_info().time.seconds();
 
@@ -1631,11 +1813,14 @@ mod tests {
                             pending_reward: Uint128::from(7500u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_1,
-                            end_time: start_time_1 + 10
+                            end_time: start_time_1 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "2".to_string(),
@@ -1643,15 +1828,19 @@ mod tests {
                             pending_reward: Uint128::from(7500u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_2,
-                            end_time: start_time_2 + 10
+                            end_time: start_time_2 + 10,
+                            weight: 100,
                         }
                     ],
                     reward_debt: Uint128::from(15000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    set_multiplier: 100,
                 },
             );
 
@@ -1675,14 +1864,18 @@ mod tests {
                         pending_reward: Uint128::from(0u128),
                         lockup_term: LockupTerm {
                             value: 30,
-                            percent: Uint128::from(70u128)
+                            percent: Uint128::from(70u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         is_end_reward: false,
                         start_time: start_time_6,
-                        end_time: start_time_6 + 30
+                        end_time: start_time_6 + 30,
+                        weight: 100,
                     }],
                     reward_debt: Uint128::zero(),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    set_multiplier: 100,
                 },
             );
 
@@ -1698,7 +1891,9 @@ mod tests {
                 nfts: vec![NftStake {
                     token_id: "3".to_string(),
                     lockup_term: 30,
+                    weight_proof: None,
                 }],
+                bonus: None,
             };
             let start_time_3 = app.block_info().time.seconds();
 
@@ -1733,11 +1928,14 @@ mod tests {
                             pending_reward: Uint128::from(15000u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_1,
-                            end_time: start_time_1 + 10
+                            end_time: start_time_1 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "2".to_string(),
@@ -1745,11 +1943,14 @@ mod tests {
                             pending_reward: Uint128::from(15000u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_2,
-                            end_time: start_time_2 + 10
+                            end_time: start_time_2 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "3".to_string(),
@@ -1757,15 +1958,19 @@ mod tests {
                             pending_reward: Uint128::from(0u128),
                             lockup_term: LockupTerm {
                                 value: 30,
-                                percent: Uint128::from(70u128)
+                                percent: Uint128::from(70u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_3,
-                            end_time: start_time_3 + 30
+                            end_time: start_time_3 + 30,
+                            weight: 100,
                         }
                     ],
                     reward_debt: Uint128::from(30000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    set_multiplier: 100,
                 },
             );
 
@@ -1789,14 +1994,18 @@ mod tests {
                         pending_reward: Uint128::from(35000u128),
                         lockup_term: LockupTerm {
                             value: 30,
-                            percent: Uint128::from(70u128)
+                            percent: Uint128::from(70u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         is_end_reward: false,
                         start_time: start_time_6,
-                        end_time: start_time_6 + 30
+                        end_time: start_time_6 + 30,
+                        weight: 100,
                     }],
                     reward_debt: Uint128::from(35000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    set_multiplier: 100,
                 },
             );
 
@@ -1828,11 +2037,14 @@ mod tests {
                             pending_reward: Uint128::from(15000u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_1,
-                            end_time: start_time_1 + 10
+                            end_time: start_time_1 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "2".to_string(),
@@ -1840,11 +2052,14 @@ mod tests {
                             pending_reward: Uint128::from(15000u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_2,
-                            end_time: start_time_2 + 10
+                            end_time: start_time_2 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "3".to_string(),
@@ -1852,21 +2067,26 @@ mod tests {
                             pending_reward: Uint128::from(17500u128),
                             lockup_term: LockupTerm {
                                 value: 30,
-                                percent: Uint128::from(70u128)
+                                percent: Uint128::from(70u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_3,
-                            end_time: start_time_3 + 30
+                            end_time: start_time_3 + 30,
+                            weight: 100,
                         }
                     ],
                     reward_debt: Uint128::from(47500u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    set_multiplier: 100,
                 },
             );
 
             // USER_1 claim reward msg
             let claim_reward_msg = CampaignExecuteMsg::ClaimReward {
                 amount: Uint128::from(47500u128),
+                owner: None,
             };
 
             // Execute claim reward
@@ -1900,11 +2120,14 @@ mod tests {
                             pending_reward: Uint128::from(0u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_1,
-                            end_time: start_time_1 + 10
+                            end_time: start_time_1 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "2".to_string(),
@@ -1912,11 +2135,14 @@ mod tests {
                             pending_reward: Uint128::from(0u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_2,
-                            end_time: start_time_2 + 10
+                            end_time: start_time_2 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "3".to_string(),
@@ -1924,15 +2150,19 @@ mod tests {
                             pending_reward: Uint128::from(0u128),
                             lockup_term: LockupTerm {
                                 value: 30,
-                                percent: Uint128::from(70u128)
+                                percent: Uint128::from(70u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_3,
-                            end_time: start_time_3 + 30
+                            end_time: start_time_3 + 30,
+                            weight: 100,
                         }
                     ],
                     reward_debt: Uint128::from(0u128),
-                    reward_claimed: Uint128::from(47500u128)
+                    reward_claimed: Uint128::from(47500u128),
+                    set_multiplier: 100,
                 },
             );
 
@@ -1948,7 +2178,9 @@ mod tests {
                 nfts: vec![NftStake {
                     token_id: "7".to_string(),
                     lockup_term: 30,
+                    weight_proof: None,
                 }],
+                bonus: None,
             };
             let start_time_7 = app.block_info().time.seconds();
 
@@ -1981,11 +2213,14 @@ mod tests {
                     pending_reward: Uint128::from(0u128),
                     lockup_term: LockupTerm {
                         value: 30,
-                        percent: Uint128::from(70u128)
+                        percent: Uint128::from(70u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     is_end_reward: false,
                     start_time: start_time_7,
-                    end_time: start_time_7 + 30
+                    end_time: start_time_7 + 30,
+                    weight: 100,
                 }
             );
 
@@ -2018,10 +2253,14 @@ mod tests {
                         LockupTerm {
                             value: 10,
                             percent: Uint128::new(30u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         LockupTerm {
                             value: 30,
                             percent: Uint128::new(70u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                     ],
                     total_nft_staked: 5,
@@ -2031,23 +2270,48 @@ mod tests {
                     time_calc_nft: current_block_time + 90,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    term_fills: vec![
+                        TermFill {
+                            lockup_term: 10,
+                            nft_count: 2,
+                            max_nfts: None,
+                            accrued_reward: Uint128::new(30000u128),
+                            max_reward: None,
+                        },
+                        TermFill {
+                            lockup_term: 30,
+                            nft_count: 3,
+                            max_nfts: None,
+                            accrued_reward: Uint128::new(245000u128),
+                            max_reward: None,
+                        },
+                    ],
                 }
             );
 
             // query token_ids
             let token_ids: Vec<String> = app
                 .wrap()
-                .query_wasm_smart(Addr::unchecked("contract3"), &CampaignQueryMsg::TokenIds {})
+                .query_wasm_smart(
+                    Addr::unchecked("contract3"),
+                    &CampaignQueryMsg::TokenIds {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
                 .unwrap();
 
-            assert_eq!(token_ids, vec!["1", "2", "6", "3", "7"]);
+            // token_ids are listed in ascending order
+            assert_eq!(token_ids, vec!["1", "2", "3", "6", "7"]);
 
             // stake nft token_id 8
             let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
                 nfts: vec![NftStake {
                     token_id: "8".to_string(),
                     lockup_term: 10,
+                    weight_proof: None,
                 }],
+                bonus: None,
             };
             let start_time_8 = app.block_info().time.seconds();
 
@@ -2088,10 +2352,14 @@ mod tests {
                         LockupTerm {
                             value: 10,
                             percent: Uint128::new(30u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         LockupTerm {
                             value: 30,
                             percent: Uint128::new(70u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                     ],
                     total_nft_staked: 6,
@@ -2101,6 +2369,22 @@ mod tests {
                     time_calc_nft: current_block_time + 90,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    term_fills: vec![
+                        TermFill {
+                            lockup_term: 10,
+                            nft_count: 3,
+                            max_nfts: None,
+                            accrued_reward: Uint128::new(30000u128),
+                            max_reward: None,
+                        },
+                        TermFill {
+                            lockup_term: 30,
+                            nft_count: 3,
+                            max_nfts: None,
+                            accrued_reward: Uint128::new(245000u128),
+                            max_reward: None,
+                        },
+                    ],
                 }
             );
 
@@ -2125,11 +2409,14 @@ mod tests {
                     pending_reward: Uint128::from(0u128),
                     lockup_term: LockupTerm {
                         value: 30,
-                        percent: Uint128::from(70u128)
+                        percent: Uint128::from(70u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     is_end_reward: false,
                     start_time: start_time_7,
-                    end_time: start_time_7 + 30
+                    end_time: start_time_7 + 30,
+                    weight: 100,
                 }
             );
 
@@ -2154,11 +2441,14 @@ mod tests {
                             pending_reward: Uint128::from(0u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_8,
-                            end_time: start_time_8 + 10
+                            end_time: start_time_8 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "6".to_string(),
@@ -2166,11 +2456,14 @@ mod tests {
                             pending_reward: Uint128::from(122500u128),
                             lockup_term: LockupTerm {
                                 value: 30,
-                                percent: Uint128::from(70u128)
+                                percent: Uint128::from(70u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_6,
-                            end_time: start_time_6 + 30
+                            end_time: start_time_6 + 30,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "7".to_string(),
@@ -2178,15 +2471,19 @@ mod tests {
                             pending_reward: Uint128::from(0u128),
                             lockup_term: LockupTerm {
                                 value: 30,
-                                percent: Uint128::from(70u128)
+                                percent: Uint128::from(70u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_7,
-                            end_time: start_time_7 + 30
+                            end_time: start_time_7 + 30,
+                            weight: 100,
                         }
                     ],
                     reward_debt: Uint128::from(122500u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    set_multiplier: 100,
                 },
             );
 
@@ -2209,11 +2506,14 @@ mod tests {
                     pending_reward: Uint128::from(0u128),
                     lockup_term: LockupTerm {
                         value: 30,
-                        percent: Uint128::from(70u128)
+                        percent: Uint128::from(70u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     is_end_reward: false,
                     start_time: start_time_7,
-                    end_time: start_time_7 + 30
+                    end_time: start_time_7 + 30,
+                    weight: 100,
                 }
             );
 
@@ -2236,11 +2536,14 @@ mod tests {
                     pending_reward: Uint128::from(0u128),
                     lockup_term: LockupTerm {
                         value: 10,
-                        percent: Uint128::from(30u128)
+                        percent: Uint128::from(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     is_end_reward: false,
                     start_time: start_time_8,
-                    end_time: start_time_8 + 10
+                    end_time: start_time_8 + 10,
+                    weight: 100,
                 }
             );
 
@@ -2265,11 +2568,14 @@ mod tests {
                             pending_reward: Uint128::from(0u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_8,
-                            end_time: start_time_8 + 10
+                            end_time: start_time_8 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "6".to_string(),
@@ -2277,11 +2583,14 @@ mod tests {
                             pending_reward: Uint128::from(122500u128),
                             lockup_term: LockupTerm {
                                 value: 30,
-                                percent: Uint128::from(70u128)
+                                percent: Uint128::from(70u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_6,
-                            end_time: start_time_6 + 30
+                            end_time: start_time_6 + 30,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "7".to_string(),
@@ -2289,15 +2598,19 @@ mod tests {
                             pending_reward: Uint128::from(0u128),
                             lockup_term: LockupTerm {
                                 value: 30,
-                                percent: Uint128::from(70u128)
+                                percent: Uint128::from(70u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_7,
-                            end_time: start_time_7 + 30
+                            end_time: start_time_7 + 30,
+                            weight: 100,
                         }
                     ],
                     reward_debt: Uint128::from(122500u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    set_multiplier: 100,
                 },
             );
 
@@ -2313,7 +2626,9 @@ mod tests {
                 nfts: vec![NftStake {
                     token_id: "4".to_string(),
                     lockup_term: 30,
+                    weight_proof: None,
                 }],
+                bonus: None,
             };
             let start_time_4 = app.block_info().time.seconds();
 
@@ -2355,11 +2670,14 @@ mod tests {
                             pending_reward: Uint128::from(0u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_1,
-                            end_time: start_time_1 + 10
+                            end_time: start_time_1 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "2".to_string(),
@@ -2367,11 +2685,14 @@ mod tests {
                             pending_reward: Uint128::from(0u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_2,
-                            end_time: start_time_2 + 10
+                            end_time: start_time_2 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "3".to_string(),
@@ -2379,11 +2700,14 @@ mod tests {
                             pending_reward: Uint128::from(105000u128),
                             lockup_term: LockupTerm {
                                 value: 30,
-                                percent: Uint128::from(70u128)
+                                percent: Uint128::from(70u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_3,
-                            end_time: start_time_3 + 30
+                            end_time: start_time_3 + 30,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "4".to_string(),
@@ -2391,15 +2715,19 @@ mod tests {
                             pending_reward: Uint128::from(35000u128),
                             lockup_term: LockupTerm {
                                 value: 30,
-                                percent: Uint128::from(70u128)
+                                percent: Uint128::from(70u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_4,
-                            end_time: start_time_4 + 30
+                            end_time: start_time_4 + 30,
+                            weight: 100,
                         }
                     ],
                     reward_debt: Uint128::from(140000u128),
-                    reward_claimed: Uint128::from(47500u128)
+                    reward_claimed: Uint128::from(47500u128),
+                    set_multiplier: 100,
                 },
             );
 
@@ -2424,11 +2752,14 @@ mod tests {
                             pending_reward: Uint128::from(30000u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_8,
-                            end_time: start_time_8 + 10
+                            end_time: start_time_8 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "6".to_string(),
@@ -2436,11 +2767,14 @@ mod tests {
                             pending_reward: Uint128::from(122500u128),
                             lockup_term: LockupTerm {
                                 value: 30,
-                                percent: Uint128::from(70u128)
+                                percent: Uint128::from(70u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_6,
-                            end_time: start_time_6 + 30
+                            end_time: start_time_6 + 30,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "7".to_string(),
@@ -2448,15 +2782,19 @@ mod tests {
                             pending_reward: Uint128::from(105000u128),
                             lockup_term: LockupTerm {
                                 value: 30,
-                                percent: Uint128::from(70u128)
+                                percent: Uint128::from(70u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_7,
-                            end_time: start_time_7 + 30
+                            end_time: start_time_7 + 30,
+                            weight: 100,
                         }
                     ],
                     reward_debt: Uint128::from(257500u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    set_multiplier: 100,
                 },
             );
 
@@ -2581,7 +2919,8 @@ mod tests {
                 StakedInfoResult {
                     nfts: vec![],
                     reward_debt: Uint128::from(140000u128),
-                    reward_claimed: Uint128::from(47500u128)
+                    reward_claimed: Uint128::from(47500u128),
+                    set_multiplier: 100,
                 },
             );
 
@@ -2601,7 +2940,8 @@ mod tests {
                 StakedInfoResult {
                     nfts: vec![],
                     reward_debt: Uint128::from(257500u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    set_multiplier: 100,
                 },
             );
 
@@ -2681,10 +3021,14 @@ mod tests {
                         LockupTerm {
                             value: 10,
                             percent: Uint128::new(30u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         LockupTerm {
                             value: 30,
                             percent: Uint128::new(70u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                     ],
                     total_nft_staked: 0,
@@ -2694,6 +3038,22 @@ mod tests {
                     time_calc_nft: current_block_time + 110,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    term_fills: vec![
+                        TermFill {
+                            lockup_term: 10,
+                            nft_count: 0,
+                            max_nfts: None,
+                            accrued_reward: Uint128::new(60000u128),
+                            max_reward: None,
+                        },
+                        TermFill {
+                            lockup_term: 30,
+                            nft_count: 0,
+                            max_nfts: None,
+                            accrued_reward: Uint128::new(385000u128),
+                            max_reward: None,
+                        },
+                    ],
                 }
             );
         }
@@ -2885,12 +3245,20 @@ mod tests {
                     LockupTerm {
                         value: 10,
                         percent: Uint128::new(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     LockupTerm {
                         value: 30,
                         percent: Uint128::new(70u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                 ],
+                token_staking: None,
+                exclusivity: None,
+                soft_staking: None,
+                receipt: None,
             };
 
             // Execute create campaign
@@ -2921,12 +3289,20 @@ mod tests {
                     LockupTerm {
                         value: 10,
                         percent: Uint128::new(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     LockupTerm {
                         value: 30,
                         percent: Uint128::new(70u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                 ],
+                token_staking: None,
+                exclusivity: None,
+                soft_staking: None,
+                receipt: None,
             };
 
             // Execute create campaign
@@ -2957,12 +3333,20 @@ mod tests {
                     LockupTerm {
                         value: 10,
                         percent: Uint128::new(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     LockupTerm {
                         value: 30,
                         percent: Uint128::new(70u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                 ],
+                token_staking: None,
+                exclusivity: None,
+                soft_staking: None,
+                receipt: None,
             };
 
             // Execute create campaign
@@ -2993,12 +3377,20 @@ mod tests {
                     LockupTerm {
                         value: 10,
                         percent: Uint128::new(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     LockupTerm {
                         value: 30,
                         percent: Uint128::new(70u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                 ],
+                token_staking: None,
+                exclusivity: None,
+                soft_staking: None,
+                receipt: None,
             };
 
             // Execute create campaign
@@ -3029,12 +3421,20 @@ mod tests {
                     LockupTerm {
                         value: 10,
                         percent: Uint128::new(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     LockupTerm {
                         value: 30,
                         percent: Uint128::new(70u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                 ],
+                token_staking: None,
+                exclusivity: None,
+                soft_staking: None,
+                receipt: None,
             };
 
             // Execute create campaign
@@ -3065,12 +3465,20 @@ mod tests {
                     LockupTerm {
                         value: 10,
                         percent: Uint128::new(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     LockupTerm {
                         value: 30,
                         percent: Uint128::new(70u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                 ],
+                token_staking: None,
+                exclusivity: None,
+                soft_staking: None,
+                receipt: None,
             };
 
             // Execute create campaign
@@ -3101,12 +3509,20 @@ mod tests {
                     LockupTerm {
                         value: 10,
                         percent: Uint128::new(30u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                     LockupTerm {
                         value: 30,
                         percent: Uint128::new(70u128),
+                        max_nfts: None,
+                        max_reward: None,
                     },
                 ],
+                token_staking: None,
+                exclusivity: None,
+                soft_staking: None,
+                receipt: None,
             };
 
             // Execute create campaign
@@ -3180,7 +3596,9 @@ mod tests {
                 nfts: vec![NftStake {
                     token_id: "1".to_string(),
                     lockup_term: 10,
+                    weight_proof: None,
                 }],
+                bonus: None,
             };
             let _start_time_1 = app.block_info().time.seconds();
 
@@ -3214,6 +3632,7 @@ mod tests {
             // USER_1 claim reward msg
             let claim_reward_msg = CampaignExecuteMsg::ClaimReward {
                 amount: Uint128::from(20000u128),
+                owner: None,
             };
 
             // Execute claim reward
@@ -3232,7 +3651,9 @@ mod tests {
                 nfts: vec![NftStake {
                     token_id: "1".to_string(),
                     lockup_term: 10,
+                    weight_proof: None,
                 }],
+                bonus: None,
             };
             let _start_time_1 = app.block_info().time.seconds();
 
@@ -3253,12 +3674,15 @@ mod tests {
                     NftStake {
                         token_id: "1".to_string(),
                         lockup_term: 10,
+                        weight_proof: None,
                     },
                     NftStake {
                         token_id: "2".to_string(),
                         lockup_term: 10,
+                        weight_proof: None,
                     },
                 ],
+                bonus: None,
             };
             let start_time_1 = app.block_info().time.seconds();
 
@@ -3285,16 +3709,19 @@ mod tests {
                     NftStake {
                         token_id: "3".to_string(),
                         lockup_term: 10,
+                        weight_proof: None,
                     },
                     NftStake {
                         token_id: "4".to_string(),
                         lockup_term: 10,
+                        weight_proof: None,
                     },
                     // NftStake {
                     //     token_id: "4".to_string(),
                     //     lockup_term: 10,
-                    // },
+                    //, weight_proof: None },
                 ],
+                bonus: None,
             };
             let start_time_3 = app.block_info().time.seconds();
 
@@ -3329,11 +3756,14 @@ mod tests {
                             pending_reward: Uint128::from(7500u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_1,
-                            end_time: start_time_1 + 10
+                            end_time: start_time_1 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "2".to_string(),
@@ -3341,11 +3771,14 @@ mod tests {
                             pending_reward: Uint128::from(7500u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_1,
-                            end_time: start_time_1 + 10
+                            end_time: start_time_1 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "3".to_string(),
@@ -3353,11 +3786,14 @@ mod tests {
                             pending_reward: Uint128::from(0u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_3,
-                            end_time: start_time_3 + 10
+                            end_time: start_time_3 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "4".to_string(),
@@ -3365,21 +3801,26 @@ mod tests {
                             pending_reward: Uint128::from(0u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_3,
-                            end_time: start_time_3 + 10
+                            end_time: start_time_3 + 10,
+                            weight: 100,
                         }
                     ],
                     reward_debt: Uint128::from(15000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    set_multiplier: 100,
                 },
             );
 
             // USER_1 claim reward msg
             let claim_reward_msg = CampaignExecuteMsg::ClaimReward {
                 amount: Uint128::from(20000u128),
+                owner: None,
             };
 
             // Execute claim reward
@@ -3421,11 +3862,14 @@ mod tests {
                             pending_reward: Uint128::from(11250u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_1,
-                            end_time: start_time_1 + 10
+                            end_time: start_time_1 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "2".to_string(),
@@ -3433,11 +3877,14 @@ mod tests {
                             pending_reward: Uint128::from(11250u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_1,
-                            end_time: start_time_1 + 10
+                            end_time: start_time_1 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "3".to_string(),
@@ -3445,11 +3892,14 @@ mod tests {
                             pending_reward: Uint128::from(3750u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_3,
-                            end_time: start_time_3 + 10
+                            end_time: start_time_3 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "4".to_string(),
@@ -3457,15 +3907,19 @@ mod tests {
                             pending_reward: Uint128::from(3750u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_3,
-                            end_time: start_time_3 + 10
+                            end_time: start_time_3 + 10,
+                            weight: 100,
                         }
                     ],
                     reward_debt: Uint128::from(30000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    set_multiplier: 100,
                 },
             );
 
@@ -3522,11 +3976,14 @@ mod tests {
                             pending_reward: Uint128::from(11250u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: true,
                             start_time: start_time_1,
-                            end_time: start_time_1 + 10
+                            end_time: start_time_1 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "3".to_string(),
@@ -3534,11 +3991,14 @@ mod tests {
                             pending_reward: Uint128::from(3750u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_3,
-                            end_time: start_time_3 + 10
+                            end_time: start_time_3 + 10,
+                            weight: 100,
                         },
                         NftInfo {
                             token_id: "4".to_string(),
@@ -3546,21 +4006,31 @@ mod tests {
                             pending_reward: Uint128::from(3750u128),
                             lockup_term: LockupTerm {
                                 value: 10,
-                                percent: Uint128::from(30u128)
+                                percent: Uint128::from(30u128),
+                                max_nfts: None,
+                                max_reward: None,
                             },
                             is_end_reward: false,
                             start_time: start_time_3,
-                            end_time: start_time_3 + 10
+                            end_time: start_time_3 + 10,
+                            weight: 100,
                         }
                     ],
                     reward_debt: Uint128::from(30000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    set_multiplier: 100,
                 },
             );
 
             let nfts: Vec<NftInfo> = app
                 .wrap()
-                .query_wasm_smart("contract3", &CampaignQueryMsg::Nfts { limit: None })
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::Nfts {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
                 .unwrap();
 
             assert_eq!(
@@ -3572,11 +4042,14 @@ mod tests {
                         pending_reward: Uint128::from(11250u128),
                         lockup_term: LockupTerm {
                             value: 10,
-                            percent: Uint128::from(30u128)
+                            percent: Uint128::from(30u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         is_end_reward: true,
                         start_time: start_time_1,
-                        end_time: start_time_1 + 10
+                        end_time: start_time_1 + 10,
+                        weight: 100,
                     },
                     NftInfo {
                         token_id: "3".to_string(),
@@ -3584,11 +4057,14 @@ mod tests {
                         pending_reward: Uint128::from(3750u128),
                         lockup_term: LockupTerm {
                             value: 10,
-                            percent: Uint128::from(30u128)
+                            percent: Uint128::from(30u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         is_end_reward: false,
                         start_time: start_time_3,
-                        end_time: start_time_3 + 10
+                        end_time: start_time_3 + 10,
+                        weight: 100,
                     },
                     NftInfo {
                         token_id: "4".to_string(),
@@ -3596,11 +4072,14 @@ mod tests {
                         pending_reward: Uint128::from(3750u128),
                         lockup_term: LockupTerm {
                             value: 10,
-                            percent: Uint128::from(30u128)
+                            percent: Uint128::from(30u128),
+                            max_nfts: None,
+                            max_reward: None,
                         },
                         is_end_reward: false,
                         start_time: start_time_3,
-                        end_time: start_time_3 + 10
+                        end_time: start_time_3 + 10,
+                        weight: 100,
                     }
                 ]
             );
@@ -3680,18 +4159,24 @@ mod tests {
             let end_time: u64 = 20;
             let reward_per_second: Uint128 = Uint128::from(10u128);
             let percent: Uint128 = Uint128::from(70u128);
-            let nft_count: u128 = 1;
-
-            // check response calc_reward_in_time
-            let response =
-                calc_reward_in_time(start_time, end_time, reward_per_second, percent, nft_count);
+            let total_weight: u128 = 100;
+
+            // check response calc_weighted_reward_in_time
+            let response = calc_weighted_reward_in_time(
+                start_time,
+                end_time,
+                reward_per_second,
+                percent,
+                total_weight,
+            );
             assert!(response.is_ok());
 
-            let calc_reward = response.unwrap();
+            let calc_reward = calc_index_reward(response.unwrap(), total_weight).unwrap();
             assert_eq!(calc_reward, Uint128::from(70u128));
 
-            // check response calc_reward_in_time error
-            let response = calc_reward_in_time(start_time, end_time, reward_per_second, percent, 0);
+            // check response calc_weighted_reward_in_time error
+            let response =
+                calc_weighted_reward_in_time(start_time, end_time, reward_per_second, percent, 0);
             assert!(response.is_err());
 
             // add_reward
@@ -3715,4 +4200,4304 @@ mod tests {
             assert!(response.is_err());
         }
     }
+
+    mod campaign_queries {
+        use crate::msg::{ExecuteMsg as FactoryExecuteMsg, QueryMsg as FactoryQueryMsg};
+        use crate::state::{
+            AssetLabel, ConfigResponse, CreatorProfile, FactoryCampaign, GlobalStats, Governance,
+            NftRegistration, Proposal, ProposalAction, UserPositionsResponse, Verification,
+            VerifiedAsset, VerifiedAssetsUpdate,
+        };
+        use crate::tests::env_setup::env::{
+            create_active_campaign, create_campaign, fund_campaign, increase_time,
+            instantiate_contracts, instantiate_group, mint_nft, mint_nfts, storage_reads,
+            try_create_campaign, try_create_custom_campaign, Cw721ExecuteMsg, ADMIN, USER_1,
+            USER_2,
+        };
+        use campaign::msg::{
+            Cw20HookMsg, ExecuteMsg as CampaignExecuteMsg, QueryMsg as CampaignQueryMsg,
+            ReceiptHookMsg,
+        };
+        use campaign::state::{
+            AssetToken, CampaignInfoResult, CampaignInfoUpdate, EligibilityResult,
+            ExclusivityRules, LedgerAction, LedgerEntry, ListKind, LockupTerm, MerkleRoots,
+            Metadata, NftDashboard, NftInfo, NftStake, RewardSnapshot, SetBonus, SetRule,
+            SimulateClaimResult, SimulateStakeResult, SimulateUnstakeResult, StakedInfoResult,
+            StakerBonusProof, StakerDashboardResult, StakerInfoResult, StakingRules, TermPolicy,
+            TokenDeposit, TokenIdRange, TokenInfo, TokenStaking, TokenWeightProof, Trait,
+            TraitGroup, UnbondingNft,
+        };
+        use cosmwasm_std::{to_binary, Addr, Decimal, Empty, Event, Uint128};
+        use cw20::{BalanceResponse, Cw20ExecuteMsg};
+        use cw721::{Cw721QueryMsg, OperatorsResponse, OwnerOfResponse};
+        use cw_multi_test::{App, AppResponse, Executor};
+        use sha2::Digest;
+
+        // - USER_1 stakes token_id 1, 2, 3 and USER_2 stakes token_id 6, 7
+        // - list nfts, token_ids and stakers page by page
+        #[test]
+        fn paginated_queries() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_active_campaign(
+                &mut app,
+                &contracts,
+                vec![
+                    LockupTerm {
+                        value: 10,
+                        percent: Uint128::new(30u128),
+                        max_nfts: None,
+                        max_reward: None,
+                    },
+                    LockupTerm {
+                        value: 30,
+                        percent: Uint128::new(70u128),
+                        max_nfts: None,
+                        max_reward: None,
+                    },
+                ],
+                1_000_000,
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1, 2, 3],
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_2,
+                &campaign_contract,
+                &[6, 7],
+            );
+
+            for (user, token_ids, lockup_term) in [
+                (USER_1, vec!["1", "2", "3"], 10),
+                (USER_2, vec!["6", "7"], 30),
+            ] {
+                let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                    nfts: token_ids
+                        .iter()
+                        .map(|token_id| NftStake {
+                            token_id: token_id.to_string(),
+                            lockup_term,
+                            weight_proof: None,
+                        })
+                        .collect(),
+                    bonus: None,
+                };
+                app.execute_contract(
+                    Addr::unchecked(user),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &stake_nft_msg,
+                    &[],
+                )
+                .unwrap();
+            }
+
+            increase_time(&mut app, 5);
+
+            // first page of nfts
+            let nfts: Vec<NftInfo> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::Nfts {
+                        start_after: None,
+                        limit: Some(2),
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                nfts.iter()
+                    .map(|nft| nft.token_id.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["1", "2"]
+            );
+
+            // pending reward is calculated: 5(s) * 10.000 * 30 / 100 / 3 (nft_count) = 5.000
+            assert_eq!(nfts[0].pending_reward, Uint128::new(5_000u128));
+
+            // next page of nfts
+            let nfts: Vec<NftInfo> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::Nfts {
+                        start_after: Some("2".to_string()),
+                        limit: Some(2),
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                nfts.iter()
+                    .map(|nft| nft.token_id.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["3", "6"]
+            );
+
+            // 5(s) * 10.000 * 70 / 100 / 2 (nft_count) = 17.500
+            assert_eq!(nfts[1].pending_reward, Uint128::new(17_500u128));
+
+            // token_ids after the last one
+            let token_ids: Vec<String> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::TokenIds {
+                        start_after: Some("6".to_string()),
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(token_ids, vec!["7".to_string()]);
+
+            // stakers are listed by address with live reward
+            let stakers: Vec<StakerInfoResult> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::Stakers {
+                        start_after: None,
+                        limit: Some(1),
+                    },
+                )
+                .unwrap();
+            let staked_info: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_1),
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                stakers,
+                vec![StakerInfoResult {
+                    owner: Addr::unchecked(USER_1),
+                    token_ids: vec!["1".to_string(), "2".to_string(), "3".to_string()],
+                    reward_debt: staked_info.reward_debt,
+                    reward_claimed: Uint128::zero(),
+                }]
+            );
+            assert_eq!(staked_info.reward_debt, Uint128::new(15_000u128));
+
+            let stakers: Vec<StakerInfoResult> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract,
+                    &CampaignQueryMsg::Stakers {
+                        start_after: Some(USER_1.to_string()),
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(stakers.len(), 1);
+            assert_eq!(stakers[0].owner, Addr::unchecked(USER_2));
+            assert_eq!(stakers[0].reward_debt, Uint128::new(35_000u128));
+        }
+
+        // - USER_1 stakes 65 nfts, USER_2 stakes 65 nfts 5s later
+        // - nfts of USER_1 stop earning, list all nfts and stakers page by page
+        #[test]
+        fn paginated_queries_over_max_limit() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_active_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+                1_000_000,
+            );
+            for (owner, token_ids) in [(USER_1, 1..=65u64), (USER_2, 66..=130u64)] {
+                let token_ids = token_ids.collect::<Vec<_>>();
+                mint_nfts(
+                    &mut app,
+                    &collection_contract,
+                    owner,
+                    &campaign_contract,
+                    &token_ids,
+                );
+                app.execute_contract(
+                    Addr::unchecked(owner),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::StakeNfts {
+                        nfts: token_ids
+                            .iter()
+                            .map(|token_id| NftStake {
+                                token_id: token_id.to_string(),
+                                lockup_term: 10,
+                                weight_proof: None,
+                            })
+                            .collect(),
+                        bonus: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+                increase_time(&mut app, 5);
+            }
+            increase_time(&mut app, 3);
+
+            // pages hold at most MAX_LIMIT nfts
+            let mut nfts: Vec<NftInfo> = vec![];
+            loop {
+                let page: Vec<NftInfo> = app
+                    .wrap()
+                    .query_wasm_smart(
+                        campaign_contract.clone(),
+                        &CampaignQueryMsg::Nfts {
+                            start_after: nfts.last().map(|nft| nft.token_id.clone()),
+                            limit: Some(200),
+                        },
+                    )
+                    .unwrap();
+                assert!(page.len() <= 100);
+                if page.is_empty() {
+                    break;
+                }
+                nfts.extend(page);
+            }
+            assert_eq!(nfts.len(), 130);
+            assert_eq!(nfts.iter().filter(|nft| nft.is_end_reward).count(), 65);
+
+            // each nft of a page has its live pending reward
+            for nft in nfts.iter() {
+                let nft_info: NftInfo = app
+                    .wrap()
+                    .query_wasm_smart(
+                        campaign_contract.clone(),
+                        &CampaignQueryMsg::NftInfo {
+                            token_id: nft.token_id.clone(),
+                        },
+                    )
+                    .unwrap();
+                assert!(!nft_info.pending_reward.is_zero());
+                assert_eq!(nft, &nft_info);
+            }
+
+            // reward_debt of stakers includes pending reward of their nfts
+            let stakers: Vec<StakerInfoResult> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::Stakers {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(stakers.len(), 2);
+            for staker in stakers {
+                let staked_info: StakedInfoResult = app
+                    .wrap()
+                    .query_wasm_smart(
+                        campaign_contract.clone(),
+                        &CampaignQueryMsg::NftStaked {
+                            owner: staker.owner.clone(),
+                        },
+                    )
+                    .unwrap();
+                assert_eq!(staker.token_ids.len(), 65);
+                assert_eq!(staker.reward_debt, staked_info.reward_debt);
+                assert_eq!(
+                    staker.reward_debt,
+                    nfts.iter()
+                        .filter(|nft| nft.owner == staker.owner)
+                        .map(|nft| nft.pending_reward)
+                        .sum::<Uint128>()
+                );
+            }
+        }
+
+        // - USER_1 stakes token_id 1, 2 and USER_2 stakes token_id 6
+        // - USER_2 cannot unstake nft of USER_1
+        // - total_nft_staked and nfts of staker follow unstake
+        #[test]
+        fn staked_nfts_indexes() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_active_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+                1_000_000,
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1, 2],
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_2,
+                &campaign_contract,
+                &[6],
+            );
+
+            for (user, token_ids) in [(USER_1, vec!["1", "2"]), (USER_2, vec!["6"])] {
+                let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                    nfts: token_ids
+                        .iter()
+                        .map(|token_id| NftStake {
+                            token_id: token_id.to_string(),
+                            lockup_term: 10,
+                            weight_proof: None,
+                        })
+                        .collect(),
+                    bonus: None,
+                };
+                app.execute_contract(
+                    Addr::unchecked(user),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &stake_nft_msg,
+                    &[],
+                )
+                .unwrap();
+            }
+
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::CampaignInfo {},
+                )
+                .unwrap();
+            assert_eq!(campaign_info.total_nft_staked, 3);
+
+            increase_time(&mut app, 10);
+
+            // USER_2 un stake nft of USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            );
+            assert!(response.is_err());
+
+            // USER_1 un stake nft
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::CampaignInfo {},
+                )
+                .unwrap();
+            assert_eq!(campaign_info.total_nft_staked, 2);
+
+            let staked_info: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract,
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_1),
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                staked_info
+                    .nfts
+                    .iter()
+                    .map(|nft| nft.token_id.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["2"]
+            );
+        }
+
+        // - USER_1 stakes token_id 1, 2 with lockup_term = 10s, percent = 100%
+        // - after 4s each nft earns 4(s) * 10.000 / 2 (nft_count) = 20.000
+        // - accrual rate of each nft = 10.000 / 2 = 5.000, projected reward at unlock = 50.000
+        #[test]
+        fn staker_dashboard() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_active_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+                1_000_000,
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1, 2],
+            );
+
+            let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                nfts: vec![
+                    NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 10,
+                        weight_proof: None,
+                    },
+                    NftStake {
+                        token_id: "2".to_string(),
+                        lockup_term: 10,
+                        weight_proof: None,
+                    },
+                ],
+                bonus: None,
+            };
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &stake_nft_msg,
+                &[],
+            )
+            .unwrap();
+            let unlock_time = app.block_info().time.seconds() + 10;
+
+            increase_time(&mut app, 4);
+
+            let dashboard: StakerDashboardResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::StakerDashboard {
+                        owner: Addr::unchecked(USER_1),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                dashboard.nfts[0],
+                NftDashboard {
+                    token_id: "1".to_string(),
+                    lockup_term: LockupTerm {
+                        value: 10,
+                        percent: Uint128::new(100u128),
+                        max_nfts: None,
+                        max_reward: None,
+                    },
+                    is_end_reward: false,
+                    unlock_time,
+                    time_until_unlock: 6,
+                    reward_per_second: Uint128::new(5_000u128),
+                    pending_reward: Uint128::new(20_000u128),
+                    projected_reward: Uint128::new(50_000u128),
+                }
+            );
+            assert_eq!(dashboard.reward_per_second, Uint128::new(10_000u128));
+            assert_eq!(dashboard.claimable_reward, Uint128::new(40_000u128));
+            assert_eq!(dashboard.projected_reward, Uint128::new(100_000u128));
+
+            // projected reward is paid after unlock
+            increase_time(&mut app, 6);
+            let staked_info: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract,
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_1),
+                    },
+                )
+                .unwrap();
+            assert_eq!(staked_info.reward_debt, dashboard.projected_reward);
+        }
+
+        // - USER_1 stakes token_id 1 with lockup_term = 10s, percent = 100%
+        // - after 2s simulate staking token_id 2 until its end time:
+        // 	- token_id 1 = 2(s) * 10.000 + 8(s) * 10.000 / 2 (nft_count) = 60.000
+        // 	- token_id 2 = 8(s) * 10.000 / 2 (nft_count) + 2(s) * 10.000 = 60.000
+        // - simulated results match the real execution
+        #[test]
+        fn simulate_queries() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_active_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+                1_000_000,
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1, 2],
+            );
+
+            let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                nfts: vec![NftStake {
+                    token_id: "1".to_string(),
+                    lockup_term: 10,
+                    weight_proof: None,
+                }],
+                bonus: None,
+            };
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &stake_nft_msg,
+                &[],
+            )
+            .unwrap();
+
+            increase_time(&mut app, 2);
+            let current_time = app.block_info().time.seconds();
+
+            // simulation time in the past
+            let response: Result<Vec<SimulateStakeResult>, _> = app.wrap().query_wasm_smart(
+                campaign_contract.clone(),
+                &CampaignQueryMsg::SimulateStake {
+                    nfts: vec![],
+                    at_time: Some(current_time - 1),
+                },
+            );
+            assert!(response.is_err());
+
+            // invalid lockup term
+            let response: Result<Vec<SimulateStakeResult>, _> = app.wrap().query_wasm_smart(
+                campaign_contract.clone(),
+                &CampaignQueryMsg::SimulateStake {
+                    nfts: vec![NftStake {
+                        token_id: "2".to_string(),
+                        lockup_term: 20,
+                        weight_proof: None,
+                    }],
+                    at_time: None,
+                },
+            );
+            assert!(response.is_err());
+
+            let simulate_stake: Vec<SimulateStakeResult> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::SimulateStake {
+                        nfts: vec![NftStake {
+                            token_id: "2".to_string(),
+                            lockup_term: 10,
+                            weight_proof: None,
+                        }],
+                        at_time: Some(current_time + 10),
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                simulate_stake,
+                vec![SimulateStakeResult {
+                    token_id: "2".to_string(),
+                    lockup_term: LockupTerm {
+                        value: 10,
+                        percent: Uint128::new(100u128),
+                        max_nfts: None,
+                        max_reward: None,
+                    },
+                    start_time: current_time,
+                    end_time: current_time + 10,
+                    pending_reward: Uint128::new(60_000u128),
+                }]
+            );
+
+            // token_id 1 is still locked
+            let simulate_unstake: SimulateUnstakeResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::SimulateUnstake {
+                        token_id: "1".to_string(),
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+            assert!(!simulate_unstake.can_unstake);
+            assert_eq!(simulate_unstake.reward, Uint128::new(20_000u128));
+
+            // stake token_id 2 for real
+            let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                nfts: vec![NftStake {
+                    token_id: "2".to_string(),
+                    lockup_term: 10,
+                    weight_proof: None,
+                }],
+                bonus: None,
+            };
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &stake_nft_msg,
+                &[],
+            )
+            .unwrap();
+
+            increase_time(&mut app, 10);
+
+            let nft_info: NftInfo = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::NftInfo {
+                        token_id: "2".to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(nft_info.pending_reward, simulate_stake[0].pending_reward);
+
+            let simulate_unstake: SimulateUnstakeResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::SimulateUnstake {
+                        token_id: "1".to_string(),
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                simulate_unstake,
+                SimulateUnstakeResult {
+                    token_id: "1".to_string(),
+                    owner: Addr::unchecked(USER_1),
+                    can_unstake: true,
+                    reward: Uint128::new(60_000u128),
+                    reward_debt: Uint128::new(60_000u128),
+                }
+            );
+
+            let simulate_claim: SimulateClaimResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::SimulateClaim {
+                        owner: Addr::unchecked(USER_1),
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(simulate_claim.claimable_reward, Uint128::new(120_000u128));
+
+            // claim more than simulated amount
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: simulate_claim.claimable_reward + Uint128::one(),
+                    owner: None,
+                },
+                &[],
+            );
+            assert!(response.is_err());
+
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: simulate_claim.claimable_reward,
+                    owner: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+            let staked_info: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract,
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_1),
+                    },
+                )
+                .unwrap();
+            assert_eq!(staked_info.reward_debt, Uint128::zero());
+            assert_eq!(staked_info.reward_claimed, simulate_claim.claimable_reward);
+        }
+
+        // - add reward: reward_per_second = 10.000, no nft staked
+        // - USER_1 stakes token_id 1, after 3s stakes token_id 2
+        // 	- total accrued = 3(s) * 10.000 * 100 / 100 / 1 (nft_count) = 30.000
+        #[test]
+        fn reward_history() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_active_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+                1_000_000,
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1, 2],
+            );
+
+            for token_id in ["1", "2"] {
+                let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: token_id.to_string(),
+                        lockup_term: 10,
+                        weight_proof: None,
+                    }],
+                    bonus: None,
+                };
+                app.execute_contract(
+                    Addr::unchecked(USER_1),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &stake_nft_msg,
+                    &[],
+                )
+                .unwrap();
+                increase_time(&mut app, 3);
+            }
+
+            let history: Vec<RewardSnapshot> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::RewardHistory {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                history
+                    .iter()
+                    .map(|snapshot| (
+                        snapshot.reward_per_second.u128(),
+                        snapshot.nft_counts[0].nft_count,
+                        snapshot.total_accrued.u128()
+                    ))
+                    .collect::<Vec<_>>(),
+                vec![(10_000, 0, 0), (10_000, 1, 0), (10_000, 2, 30_000)]
+            );
+
+            // next page
+            let history_page: Vec<RewardSnapshot> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract,
+                    &CampaignQueryMsg::RewardHistory {
+                        start_after: Some(history[1].time),
+                        limit: Some(1),
+                    },
+                )
+                .unwrap();
+            assert_eq!(history_page, vec![history[2].clone()]);
+        }
+
+        // - USER_1 stakes token_id 1, 2 in one message, then claims after 5s
+        // 	- every staked nft emits its own wasm-stakify-stake event
+        // 	- claim emits the claimer and the claimed amount
+        #[test]
+        fn stakify_events() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_active_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+                1_000_000,
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1, 2],
+            );
+
+            let attribute = |event: &Event, key: &str| {
+                event
+                    .attributes
+                    .iter()
+                    .find(|attr| attr.key == key)
+                    .unwrap()
+                    .value
+                    .clone()
+            };
+
+            // staking no nft emits no event with empty token_ids
+            let err = app
+                .execute_contract(
+                    Addr::unchecked(USER_1),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::StakeNfts {
+                        nfts: vec![],
+                        bonus: None,
+                    },
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(err.root_cause().to_string(), "## No nft to stake ##");
+
+            let response = app
+                .execute_contract(
+                    Addr::unchecked(USER_1),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::StakeNfts {
+                        nfts: vec![
+                            NftStake {
+                                token_id: "1".to_string(),
+                                lockup_term: 10,
+                                weight_proof: None,
+                            },
+                            NftStake {
+                                token_id: "2".to_string(),
+                                lockup_term: 10,
+                                weight_proof: None,
+                            },
+                        ],
+                        bonus: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            let stake_events: Vec<&Event> = response
+                .events
+                .iter()
+                .filter(|event| event.ty == "wasm-stakify-stake")
+                .collect();
+            assert_eq!(
+                stake_events
+                    .iter()
+                    .map(|event| (
+                        attribute(event, "schema_version"),
+                        attribute(event, "staker"),
+                        attribute(event, "token_id"),
+                        attribute(event, "term")
+                    ))
+                    .collect::<Vec<_>>(),
+                vec![
+                    (
+                        "1".to_string(),
+                        USER_1.to_string(),
+                        "1".to_string(),
+                        "10".to_string()
+                    ),
+                    (
+                        "1".to_string(),
+                        USER_1.to_string(),
+                        "2".to_string(),
+                        "10".to_string()
+                    ),
+                ]
+            );
+            assert!(stake_events
+                .iter()
+                .all(|event| attribute(event, "weight") == "100"));
+
+            increase_time(&mut app, 5);
+
+            // 5(s) * 10.000 = 50.000 reward shared by 2 nfts
+            let response = app
+                .execute_contract(
+                    Addr::unchecked(USER_1),
+                    Addr::unchecked(campaign_contract),
+                    &CampaignExecuteMsg::ClaimReward {
+                        amount: Uint128::new(50_000u128),
+                        owner: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            let claim_event = response
+                .events
+                .iter()
+                .find(|event| event.ty == "wasm-stakify-claim")
+                .unwrap();
+            assert_eq!(attribute(claim_event, "staker"), USER_1.to_string());
+            assert_eq!(
+                attribute(claim_event, "reward_token"),
+                contracts[1].contract_addr
+            );
+            assert_eq!(attribute(claim_event, "amount"), "50000".to_string());
+        }
+
+        // - USER_1 stakes token_id 1 in 10s term, unstakes after 12s and claims 40.000
+        // 	- settlement = 10(s) * 10.000 = 100.000
+        #[test]
+        fn staker_ledger() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_active_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+                1_000_000,
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1],
+            );
+
+            let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                nfts: vec![NftStake {
+                    token_id: "1".to_string(),
+                    lockup_term: 10,
+                    weight_proof: None,
+                }],
+                bonus: None,
+            };
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &stake_nft_msg,
+                &[],
+            )
+            .unwrap();
+
+            increase_time(&mut app, 12);
+            let unstake_time = app.block_info().time.seconds();
+            let unstake_height = app.block_info().height;
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+
+            increase_time(&mut app, 1);
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: Uint128::new(40_000u128),
+                    owner: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+            let ledger: Vec<LedgerEntry> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::StakerLedger {
+                        owner: Addr::unchecked(USER_1),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                ledger,
+                vec![
+                    LedgerEntry {
+                        id: 0,
+                        action: LedgerAction::Settlement,
+                        token_id: Some("1".to_string()),
+                        amount: Uint128::new(100_000u128),
+                        time: unstake_time,
+                        height: unstake_height,
+                    },
+                    LedgerEntry {
+                        id: 1,
+                        action: LedgerAction::Claim,
+                        token_id: None,
+                        amount: Uint128::new(40_000u128),
+                        time: unstake_time + 1,
+                        height: unstake_height + 1,
+                    },
+                ]
+            );
+
+            // next page and empty ledger of other staker
+            let ledger_page: Vec<LedgerEntry> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::StakerLedger {
+                        owner: Addr::unchecked(USER_1),
+                        start_after: Some(0),
+                        limit: Some(1),
+                    },
+                )
+                .unwrap();
+            assert_eq!(ledger_page, vec![ledger[1].clone()]);
+            let empty_ledger: Vec<LedgerEntry> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract,
+                    &CampaignQueryMsg::StakerLedger {
+                        owner: Addr::unchecked(USER_2),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert!(empty_ledger.is_empty());
+        }
+
+        // - USER_1 stakes token_id 1 in 10s term, sends reward to a cold wallet and approves USER_2
+        // - USER_2 claims and unstakes on behalf of USER_1
+        // 	- reward goes to the cold wallet, nft goes back to USER_1
+        // - after revoke USER_2 cannot claim anymore
+        #[test]
+        fn operator_and_reward_recipient() {
+            const COLD_WALLET: &str = "aura10000000000000000000000000000000coldwallet";
+            let (mut app, contracts) = instantiate_contracts();
+            let token_contract = contracts[1].contract_addr.clone();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_active_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+                1_000_000,
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1],
+            );
+
+            let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                nfts: vec![NftStake {
+                    token_id: "1".to_string(),
+                    lockup_term: 10,
+                    weight_proof: None,
+                }],
+                bonus: None,
+            };
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &stake_nft_msg,
+                &[],
+            )
+            .unwrap();
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::SetRewardRecipient {
+                    recipient: Some(COLD_WALLET.to_string()),
+                },
+                &[],
+            )
+            .unwrap();
+
+            increase_time(&mut app, 3);
+
+            // USER_2 is not an operator of USER_1 yet
+            let claim_for_msg = CampaignExecuteMsg::ClaimReward {
+                amount: Uint128::new(30_000u128),
+                owner: Some(USER_1.to_string()),
+            };
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2),
+                Addr::unchecked(campaign_contract.clone()),
+                &claim_for_msg,
+                &[],
+            );
+            assert!(response.is_err());
+
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::ApproveAll {
+                    operator: USER_2.to_string(),
+                    expires: None,
+                },
+                &[],
+            )
+            .unwrap();
+            let operators: OperatorsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::Operators {
+                        owner: Addr::unchecked(USER_1),
+                        include_expired: None,
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(operators.operators.len(), 1);
+            assert_eq!(operators.operators[0].spender, USER_2.to_string());
+
+            // 3(s) * 10.000 = 30.000 is sent to the cold wallet
+            app.execute_contract(
+                Addr::unchecked(USER_2),
+                Addr::unchecked(campaign_contract.clone()),
+                &claim_for_msg,
+                &[],
+            )
+            .unwrap();
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract,
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: COLD_WALLET.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(balance.balance, Uint128::new(30_000u128));
+
+            // nft is returned to USER_1
+            increase_time(&mut app, 10);
+            app.execute_contract(
+                Addr::unchecked(USER_2),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            let owner: OwnerOfResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    collection_contract,
+                    &Cw721QueryMsg::OwnerOf {
+                        token_id: "1".to_string(),
+                        include_expired: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(owner.owner, USER_1.to_string());
+
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::RevokeAll {
+                    operator: USER_2.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2),
+                Addr::unchecked(campaign_contract),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: Uint128::new(10_000u128),
+                    owner: Some(USER_1.to_string()),
+                },
+                &[],
+            );
+            assert!(response.is_err());
+        }
+
+        // - USER_1 stakes token_id 1, 2, 3 in 10s term one second apart, a keeper settles after 20s
+        // 	- Settle { limit: 2 } stops at the end_time of token_id 2
+        // 	- Settle { limit: None } settles until now
+        // - ClaimFor needs a registered reward recipient of USER_1
+        #[test]
+        fn settle_and_claim_for() {
+            const COLD_WALLET: &str = "aura10000000000000000000000000000000coldwallet";
+            let (mut app, contracts) = instantiate_contracts();
+            let token_contract = contracts[1].contract_addr.clone();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_active_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+                1_000_000,
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1, 2, 3],
+            );
+
+            let first_stake_time = app.block_info().time.seconds();
+            for token_id in ["1", "2", "3"] {
+                let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: token_id.to_string(),
+                        lockup_term: 10,
+                        weight_proof: None,
+                    }],
+                    bonus: None,
+                };
+                app.execute_contract(
+                    Addr::unchecked(USER_1),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &stake_nft_msg,
+                    &[],
+                )
+                .unwrap();
+                increase_time(&mut app, 1);
+            }
+            increase_time(&mut app, 20);
+
+            let attribute = |response: &AppResponse, key: &str| {
+                response
+                    .events
+                    .iter()
+                    .find(|event| event.ty == "wasm-stakify-settle")
+                    .and_then(|event| event.attributes.iter().find(|attr| attr.key == key))
+                    .unwrap()
+                    .value
+                    .clone()
+            };
+
+            // anyone can settle
+            let response = app
+                .execute_contract(
+                    Addr::unchecked(USER_2),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::Settle { limit: Some(2) },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                attribute(&response, "checkpoint"),
+                (first_stake_time + 11).to_string()
+            );
+            assert_eq!(attribute(&response, "remaining"), "1".to_string());
+
+            let response = app
+                .execute_contract(
+                    Addr::unchecked(USER_2),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::Settle { limit: None },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                attribute(&response, "checkpoint"),
+                app.block_info().time.seconds().to_string()
+            );
+            assert_eq!(attribute(&response, "remaining"), "0".to_string());
+
+            // USER_1 has not registered a reward recipient
+            let claim_for_msg = CampaignExecuteMsg::ClaimFor {
+                owner: USER_1.to_string(),
+            };
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2),
+                Addr::unchecked(campaign_contract.clone()),
+                &claim_for_msg,
+                &[],
+            );
+            assert!(response.is_err());
+
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::SetRewardRecipient {
+                    recipient: Some(COLD_WALLET.to_string()),
+                },
+                &[],
+            )
+            .unwrap();
+            let claimable: SimulateClaimResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::SimulateClaim {
+                        owner: Addr::unchecked(USER_1),
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+            app.execute_contract(
+                Addr::unchecked(USER_2),
+                Addr::unchecked(campaign_contract),
+                &claim_for_msg,
+                &[],
+            )
+            .unwrap();
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract,
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: COLD_WALLET.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(balance.balance, claimable.claimable_reward);
+            assert!(!balance.balance.is_zero());
+        }
+
+        // - n nfts of 10s term are staked and expire together
+        // - Settle reads the same storage entries for 20 and 120 expired nfts
+        #[test]
+        fn settle_work_is_bounded() {
+            let settle_reads = |nft_count: u64| {
+                let (mut app, contracts) = instantiate_contracts();
+                let collection_contract = contracts[2].contract_addr.clone();
+                let campaign_contract = create_active_campaign(
+                    &mut app,
+                    &contracts,
+                    vec![LockupTerm {
+                        value: 10,
+                        percent: Uint128::new(100u128),
+                        max_nfts: None,
+                        max_reward: None,
+                    }],
+                    1_000_000,
+                );
+                let token_ids = (1..=nft_count).collect::<Vec<_>>();
+                mint_nfts(
+                    &mut app,
+                    &collection_contract,
+                    USER_1,
+                    &campaign_contract,
+                    &token_ids,
+                );
+                app.execute_contract(
+                    Addr::unchecked(USER_1),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::StakeNfts {
+                        nfts: token_ids
+                            .iter()
+                            .map(|token_id| NftStake {
+                                token_id: token_id.to_string(),
+                                lockup_term: 10,
+                                weight_proof: None,
+                            })
+                            .collect(),
+                        bonus: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+                increase_time(&mut app, 20);
+
+                // limit must be greater than 0
+                let response = app.execute_contract(
+                    Addr::unchecked(USER_2),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::Settle { limit: Some(0) },
+                    &[],
+                );
+                assert_eq!(
+                    response.unwrap_err().root_cause().to_string(),
+                    "## Limit must be greater than 0 ##"
+                );
+
+                let reads_before = storage_reads();
+                let response = app
+                    .execute_contract(
+                        Addr::unchecked(USER_2),
+                        Addr::unchecked(campaign_contract.clone()),
+                        &CampaignExecuteMsg::Settle { limit: Some(5) },
+                        &[],
+                    )
+                    .unwrap();
+                let remaining = response
+                    .events
+                    .iter()
+                    .find(|event| event.ty == "wasm-stakify-settle")
+                    .and_then(|event| event.attributes.iter().find(|attr| attr.key == "remaining"))
+                    .unwrap()
+                    .value
+                    .clone();
+                assert_eq!(remaining, "5".to_string());
+
+                storage_reads() - reads_before
+            };
+
+            assert_eq!(settle_reads(20), settle_reads(120));
+        }
+
+        // - staking rules: min hold 15s, re-stake cooldown 5s, unbonding 3s, lockup term 10s
+        // - USER_1 stakes token_id 1
+        // 	- cannot unstake after 11s, can unstake after 15s
+        // 	- nft is withdrawn after 3s of unbonding
+        // 	- token_id 1 can be staked again 5s after unstake
+        #[test]
+        fn staking_rules() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+            );
+
+            let update_rules_msg = CampaignExecuteMsg::UpdateStakingRules {
+                staking_rules: StakingRules {
+                    min_hold_time: 15,
+                    restake_cooldown: 5,
+                    unbonding_period: 3,
+                },
+            };
+            // only campaign owner can update staking rules
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &update_rules_msg,
+                &[],
+            );
+            assert!(response.is_err());
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(campaign_contract.clone()),
+                &update_rules_msg,
+                &[],
+            )
+            .unwrap();
+
+            fund_campaign(&mut app, &contracts, &campaign_contract, 1_000_000);
+
+            // staking rules cannot change after reward is added
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(campaign_contract.clone()),
+                &update_rules_msg,
+                &[],
+            );
+            assert!(response.is_err());
+
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1],
+            );
+            let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                nfts: vec![NftStake {
+                    token_id: "1".to_string(),
+                    lockup_term: 10,
+                    weight_proof: None,
+                }],
+                bonus: None,
+            };
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &stake_nft_msg,
+                &[],
+            )
+            .unwrap();
+
+            // lockup term is over but min hold time is not
+            increase_time(&mut app, 11);
+            let unstake_msg = CampaignExecuteMsg::UnStakeNft {
+                token_id: "1".to_string(),
+            };
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &unstake_msg,
+                &[],
+            );
+            assert!(response.is_err());
+
+            increase_time(&mut app, 4);
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &unstake_msg,
+                &[],
+            )
+            .unwrap();
+            let unbonding_nfts: Vec<UnbondingNft> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::UnbondingNfts {
+                        owner: Addr::unchecked(USER_1),
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                unbonding_nfts,
+                vec![UnbondingNft {
+                    token_id: "1".to_string(),
+                    owner: Addr::unchecked(USER_1),
+                    release_time: app.block_info().time.seconds() + 3,
+                }]
+            );
+
+            // nft is still unbonding
+            let withdraw_nft_msg = CampaignExecuteMsg::WithdrawNft {
+                token_id: "1".to_string(),
+            };
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &withdraw_nft_msg,
+                &[],
+            );
+            assert!(response.is_err());
+
+            increase_time(&mut app, 3);
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &withdraw_nft_msg,
+                &[],
+            )
+            .unwrap();
+            let owner: OwnerOfResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    collection_contract.clone(),
+                    &Cw721QueryMsg::OwnerOf {
+                        token_id: "1".to_string(),
+                        include_expired: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(owner.owner, USER_1.to_string());
+
+            // re-stake cooldown is not over
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(collection_contract),
+                &cw721_base::ExecuteMsg::<Empty, Empty>::Approve {
+                    spender: campaign_contract.clone(),
+                    token_id: "1".to_string(),
+                    expires: None,
+                },
+                &[],
+            )
+            .unwrap();
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &stake_nft_msg,
+                &[],
+            );
+            assert!(response.is_err());
+
+            increase_time(&mut app, 2);
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract),
+                &stake_nft_msg,
+                &[],
+            )
+            .unwrap();
+        }
+
+        // - only token_id 1-500 are allowed, token_id 3 and USER_2 are denied
+        // 	- USER_1 can stake token_id 1 but not 3 and 501
+        // 	- USER_2 cannot stake
+        // - staker allowlist enabled with USER_1
+        #[test]
+        fn eligibility() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_active_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+                1_000_000,
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1, 3, 501],
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_2,
+                &campaign_contract,
+                &[2],
+            );
+
+            let update_eligibility_msg = CampaignExecuteMsg::UpdateEligibility {
+                token_allowlist_enabled: Some(true),
+                staker_allowlist_enabled: None,
+                allowed_token_ranges: Some(vec![TokenIdRange { min: 1, max: 500 }]),
+                denied_token_ranges: None,
+            };
+            // only campaign owner can update lists
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &update_eligibility_msg,
+                &[],
+            );
+            assert!(response.is_err());
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(campaign_contract.clone()),
+                &update_eligibility_msg,
+                &[],
+            )
+            .unwrap();
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UpdateTokenIdList {
+                    list: ListKind::Deny,
+                    add: vec!["3".to_string()],
+                    remove: vec![],
+                },
+                &[],
+            )
+            .unwrap();
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UpdateStakerList {
+                    list: ListKind::Deny,
+                    add: vec![USER_2.to_string()],
+                    remove: vec![],
+                },
+                &[],
+            )
+            .unwrap();
+
+            let eligibility: EligibilityResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::Eligibility {
+                        staker: Addr::unchecked(USER_1),
+                        token_ids: vec!["1".to_string(), "3".to_string(), "501".to_string()],
+                    },
+                )
+                .unwrap();
+            assert!(eligibility.staker_eligible);
+            assert_eq!(
+                eligibility
+                    .token_ids
+                    .iter()
+                    .map(|token| token.eligible)
+                    .collect::<Vec<_>>(),
+                vec![true, false, false]
+            );
+
+            let stake = |app: &mut App, staker: &str, token_id: &str| {
+                app.execute_contract(
+                    Addr::unchecked(staker),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::StakeNfts {
+                        nfts: vec![NftStake {
+                            token_id: token_id.to_string(),
+                            lockup_term: 10,
+                            weight_proof: None,
+                        }],
+                        bonus: None,
+                    },
+                    &[],
+                )
+            };
+            assert!(stake(&mut app, USER_1, "3").is_err());
+            assert!(stake(&mut app, USER_1, "501").is_err());
+            assert!(stake(&mut app, USER_2, "2").is_err());
+            stake(&mut app, USER_1, "1").unwrap();
+
+            // only USER_1 is allowed
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UpdateEligibility {
+                    token_allowlist_enabled: None,
+                    staker_allowlist_enabled: Some(true),
+                    allowed_token_ranges: None,
+                    denied_token_ranges: None,
+                },
+                &[],
+            )
+            .unwrap();
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UpdateStakerList {
+                    list: ListKind::Allow,
+                    add: vec![USER_1.to_string()],
+                    remove: vec![],
+                },
+                &[],
+            )
+            .unwrap();
+            for (staker, staker_eligible) in [(USER_1, true), (ADMIN, false)] {
+                let eligibility: EligibilityResult = app
+                    .wrap()
+                    .query_wasm_smart(
+                        campaign_contract.clone(),
+                        &CampaignQueryMsg::Eligibility {
+                            staker: Addr::unchecked(staker),
+                            token_ids: vec![],
+                        },
+                    )
+                    .unwrap();
+                assert_eq!(eligibility.staker_eligible, staker_eligible);
+            }
+        }
+
+        // sha256 of merkle leaf
+        fn leaf_hash(leaf: &str) -> [u8; 32] {
+            sha2::Sha256::digest(leaf.as_bytes()).into()
+        }
+
+        // merkle root of two leaves and the proof of each leaf
+        fn merkle_tree(leaves: [&str; 2]) -> (String, [Vec<String>; 2]) {
+            let mut hashes = [leaf_hash(leaves[0]), leaf_hash(leaves[1])];
+            let proofs = [vec![hex::encode(hashes[1])], vec![hex::encode(hashes[0])]];
+            hashes.sort_unstable();
+            let root: [u8; 32] = sha2::Sha256::digest(hashes.concat()).into();
+            (hex::encode(root), proofs)
+        }
+
+        // - token_id 1 weighs 300 and token_id 2 weighs 100
+        // - USER_1 has no bonus and USER_2 has 2x bonus
+        // 	- USER_1 stakes token_id 1 with weight 300, USER_2 stakes token_id 2 with weight 200
+        // 	- after 5s, 50.000 reward is shared 30.000 / 20.000
+        #[test]
+        fn merkle_roots() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1],
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_2,
+                &campaign_contract,
+                &[2],
+            );
+
+            let (token_weight_root, token_proofs) = merkle_tree(["1:300", "2:100"]);
+            let (staker_bonus_root, bonus_proofs) =
+                merkle_tree([&format!("{}:100", USER_1), &format!("{}:200", USER_2)]);
+            let register_msg = CampaignExecuteMsg::RegisterMerkleRoots {
+                staker_bonus_root: Some(staker_bonus_root.clone()),
+                token_weight_root: Some(token_weight_root.clone()),
+            };
+
+            // only campaign owner can register valid merkle roots
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &register_msg,
+                &[],
+            );
+            assert!(response.is_err());
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::RegisterMerkleRoots {
+                    staker_bonus_root: Some("abcd".to_string()),
+                    token_weight_root: None,
+                },
+                &[],
+            );
+            assert!(response.is_err());
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(campaign_contract.clone()),
+                &register_msg,
+                &[],
+            )
+            .unwrap();
+            let merkle_roots: MerkleRoots = app
+                .wrap()
+                .query_wasm_smart(campaign_contract.clone(), &CampaignQueryMsg::MerkleRoots {})
+                .unwrap();
+            assert_eq!(
+                merkle_roots,
+                MerkleRoots {
+                    staker_bonus_root: Some(staker_bonus_root),
+                    token_weight_root: Some(token_weight_root),
+                }
+            );
+
+            fund_campaign(&mut app, &contracts, &campaign_contract, 1_000_000);
+
+            // merkle roots cannot change once reward is added
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(campaign_contract.clone()),
+                &register_msg,
+                &[],
+            );
+            assert!(response.is_err());
+
+            let stake = |app: &mut App,
+                         staker: &str,
+                         token_id: &str,
+                         weight_proof: Option<TokenWeightProof>,
+                         bonus: Option<StakerBonusProof>| {
+                app.execute_contract(
+                    Addr::unchecked(staker),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::StakeNfts {
+                        nfts: vec![NftStake {
+                            token_id: token_id.to_string(),
+                            lockup_term: 10,
+                            weight_proof,
+                        }],
+                        bonus,
+                    },
+                    &[],
+                )
+            };
+            let weight_proof = |weight: u64, proof: &Vec<String>| {
+                Some(TokenWeightProof {
+                    weight,
+                    proof: proof.clone(),
+                })
+            };
+            let bonus = |bonus_multiplier: u64, proof: &Vec<String>| {
+                Some(StakerBonusProof {
+                    bonus_multiplier,
+                    proof: proof.clone(),
+                })
+            };
+
+            // missing or wrong proofs
+            let user_1_bonus = bonus(100, &bonus_proofs[0]);
+            assert!(stake(&mut app, USER_1, "1", None, user_1_bonus.clone()).is_err());
+            assert!(stake(
+                &mut app,
+                USER_1,
+                "1",
+                weight_proof(300, &token_proofs[0]),
+                None
+            )
+            .is_err());
+            assert!(stake(
+                &mut app,
+                USER_1,
+                "1",
+                weight_proof(500, &token_proofs[0]),
+                user_1_bonus.clone()
+            )
+            .is_err());
+            assert!(stake(
+                &mut app,
+                USER_1,
+                "1",
+                weight_proof(300, &token_proofs[0]),
+                bonus(200, &bonus_proofs[0])
+            )
+            .is_err());
+
+            let response = stake(
+                &mut app,
+                USER_1,
+                "1",
+                weight_proof(300, &token_proofs[0]),
+                user_1_bonus,
+            )
+            .unwrap();
+            let stake_event = response
+                .events
+                .iter()
+                .find(|event| event.ty == "wasm-stakify-stake")
+                .unwrap();
+            assert_eq!(
+                stake_event
+                    .attributes
+                    .iter()
+                    .find(|attr| attr.key == "weight")
+                    .unwrap()
+                    .value,
+                "300"
+            );
+            stake(
+                &mut app,
+                USER_2,
+                "2",
+                weight_proof(100, &token_proofs[1]),
+                bonus(200, &bonus_proofs[1]),
+            )
+            .unwrap();
+
+            increase_time(&mut app, 5);
+
+            // 5(s) * 10.000 = 50.000 reward shared by weight 300 and 200
+            for (staker, reward_debt) in [(USER_1, 30_000u128), (USER_2, 20_000u128)] {
+                let staked_info: StakedInfoResult = app
+                    .wrap()
+                    .query_wasm_smart(
+                        campaign_contract.clone(),
+                        &CampaignQueryMsg::NftStaked {
+                            owner: Addr::unchecked(staker),
+                        },
+                    )
+                    .unwrap();
+                assert_eq!(staked_info.reward_debt, Uint128::new(reward_debt));
+            }
+        }
+
+        // - 10s term pays at most 30.000 to at most 2 nfts
+        // 	- USER_1 stakes token_id 1, 2 and cannot stake token_id 3
+        // 	- after 5s, 50.000 reward is capped at 30.000
+        // 	- token_id 3 cannot be staked after unstaking token_id 1, term reward is used up
+        #[test]
+        fn term_caps() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_active_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: Some(2),
+                    max_reward: Some(Uint128::new(30_000u128)),
+                }],
+                1_000_000,
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1, 2, 3],
+            );
+
+            let stake = |app: &mut App, token_id: &str| {
+                app.execute_contract(
+                    Addr::unchecked(USER_1),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::StakeNfts {
+                        nfts: vec![NftStake {
+                            token_id: token_id.to_string(),
+                            lockup_term: 10,
+                            weight_proof: None,
+                        }],
+                        bonus: None,
+                    },
+                    &[],
+                )
+            };
+            stake(&mut app, "1").unwrap();
+            stake(&mut app, "2").unwrap();
+            assert!(stake(&mut app, "3").is_err());
+
+            let term_fill = |app: &App| {
+                let campaign_info: CampaignInfoResult = app
+                    .wrap()
+                    .query_wasm_smart(
+                        campaign_contract.clone(),
+                        &CampaignQueryMsg::CampaignInfo {},
+                    )
+                    .unwrap();
+                campaign_info.term_fills[0].clone()
+            };
+            assert_eq!(term_fill(&app).nft_count, 2);
+
+            increase_time(&mut app, 5);
+
+            // 5(s) * 10.000 = 50.000 reward capped at 30.000
+            let staked_info: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_1),
+                    },
+                )
+                .unwrap();
+            assert_eq!(staked_info.reward_debt, Uint128::new(30_000u128));
+            app.execute_contract(
+                Addr::unchecked(USER_2),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::Settle { limit: None },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(term_fill(&app).accrued_reward, Uint128::new(30_000u128));
+
+            increase_time(&mut app, 5);
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            let fill = term_fill(&app);
+            assert_eq!(fill.nft_count, 1);
+            assert_eq!(fill.accrued_reward, Uint128::new(30_000u128));
+            assert!(stake(&mut app, "3").is_err());
+        }
+
+        // - factory allows 10s and 30s terms
+        // 	- campaigns with 20s, flexible, duplicate or over 100 percent terms are rejected
+        // 	- campaign cannot update lockup terms outside of policy
+        #[test]
+        fn term_policy() {
+            let (mut app, contracts) = instantiate_contracts();
+            let factory_contract = contracts[0].contract_addr.clone();
+            let term = |value: u64, percent: u128| LockupTerm {
+                value,
+                percent: Uint128::new(percent),
+                max_nfts: None,
+                max_reward: None,
+            };
+            let term_policy = TermPolicy {
+                min_term: 10,
+                max_term: 100,
+                allowed_terms: vec![10, 30],
+                allow_flexible: false,
+            };
+
+            // only factory owner can update a valid term policy
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(factory_contract.clone()),
+                &FactoryExecuteMsg::UpdateConfig {
+                    owner: None,
+                    campaign_code_id: None,
+                    term_policy: Some(term_policy.clone()),
+                    receipt_code_id: None,
+                    max_active_campaigns: None,
+                },
+                &[],
+            );
+            assert!(response.is_err());
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(factory_contract.clone()),
+                &FactoryExecuteMsg::UpdateConfig {
+                    owner: None,
+                    campaign_code_id: None,
+                    term_policy: Some(TermPolicy {
+                        min_term: 10,
+                        max_term: 100,
+                        allowed_terms: vec![5],
+                        allow_flexible: false,
+                    }),
+                    receipt_code_id: None,
+                    max_active_campaigns: None,
+                },
+                &[],
+            );
+            assert!(response.is_err());
+            let response = app
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    Addr::unchecked(factory_contract.clone()),
+                    &FactoryExecuteMsg::UpdateConfig {
+                        owner: None,
+                        campaign_code_id: None,
+                        term_policy: Some(term_policy.clone()),
+                        receipt_code_id: None,
+                        max_active_campaigns: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            let update_config_event = response
+                .events
+                .iter()
+                .find(|event| event.ty == "wasm-stakify-factory-update-config")
+                .unwrap();
+            assert_eq!(
+                update_config_event
+                    .attributes
+                    .iter()
+                    .skip(2)
+                    .map(|attr| (attr.key.as_str(), attr.value.as_str()))
+                    .collect::<Vec<_>>(),
+                vec![
+                    ("owner", ADMIN),
+                    ("campaign_code_id", "4"),
+                    ("min_term", "10"),
+                    ("max_term", "100"),
+                    ("allowed_terms", "10,30"),
+                    ("allow_flexible", "false"),
+                    ("receipt_code_id", "3"),
+                    ("max_active_campaigns", "0"),
+                ]
+            );
+
+            assert!(
+                try_create_campaign(&mut app, &contracts, vec![term(20, 100)])
+                    .unwrap_err()
+                    .contains("Invalid LockupTerm")
+            );
+            assert!(
+                try_create_campaign(&mut app, &contracts, vec![term(0, 100)])
+                    .unwrap_err()
+                    .contains("Invalid LockupTerm")
+            );
+            assert!(
+                try_create_campaign(&mut app, &contracts, vec![term(10, 30), term(10, 70)])
+                    .unwrap_err()
+                    .contains("Duplicate lockup term 10")
+            );
+            assert!(
+                try_create_campaign(&mut app, &contracts, vec![term(10, 60), term(30, 50)])
+                    .unwrap_err()
+                    .contains("Total percent")
+            );
+            let campaign_contract =
+                create_campaign(&mut app, &contracts, vec![term(10, 30), term(30, 70)]);
+
+            let campaign_term_policy: TermPolicy = app
+                .wrap()
+                .query_wasm_smart(campaign_contract.clone(), &CampaignQueryMsg::TermPolicy {})
+                .unwrap();
+            assert_eq!(campaign_term_policy, term_policy);
+
+            let update_campaign = |app: &mut App, lockup_term: Vec<LockupTerm>| {
+                app.execute_contract(
+                    Addr::unchecked(ADMIN),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::UpdateCampaign {
+                        campaign_info_update: CampaignInfoUpdate {
+                            campaign_name: None,
+                            campaign_image: None,
+                            campaign_description: None,
+                            limit_per_staker: None,
+                            lockup_term: Some(lockup_term),
+                            start_time: None,
+                            end_time: None,
+                        },
+                    },
+                    &[],
+                )
+            };
+            assert!(update_campaign(&mut app, vec![term(20, 100)]).is_err());
+            update_campaign(&mut app, vec![term(30, 100)]).unwrap();
+        }
+
+        // - USER_1 and USER_2 stake token_id 1, 2 in flexible term
+        // 	- after 5s, USER_1 unstakes with 25.000 reward
+        // 	- after 5s more, USER_2 has 25.000 + 50.000 = 75.000 reward
+        #[test]
+        fn flexible_term() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_active_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 0,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+                1_000_000,
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1],
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_2,
+                &campaign_contract,
+                &[2],
+            );
+            for (staker, token_id) in [(USER_1, "1"), (USER_2, "2")] {
+                app.execute_contract(
+                    Addr::unchecked(staker),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::StakeNfts {
+                        nfts: vec![NftStake {
+                            token_id: token_id.to_string(),
+                            lockup_term: 0,
+                            weight_proof: None,
+                        }],
+                        bonus: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            }
+
+            // nft of flexible term earns until the end of campaign
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::CampaignInfo {},
+                )
+                .unwrap();
+            let nft_info: NftInfo = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::NftInfo {
+                        token_id: "1".to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(nft_info.end_time, campaign_info.end_time);
+
+            increase_time(&mut app, 5);
+
+            let simulate_unstake: SimulateUnstakeResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::SimulateUnstake {
+                        token_id: "1".to_string(),
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+            assert!(simulate_unstake.can_unstake);
+            assert_eq!(simulate_unstake.reward, Uint128::new(25_000u128));
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+
+            increase_time(&mut app, 5);
+
+            for (staker, reward_debt) in [(USER_1, 25_000u128), (USER_2, 75_000u128)] {
+                let staked_info: StakedInfoResult = app
+                    .wrap()
+                    .query_wasm_smart(
+                        campaign_contract.clone(),
+                        &CampaignQueryMsg::NftStaked {
+                            owner: Addr::unchecked(staker),
+                        },
+                    )
+                    .unwrap();
+                assert_eq!(staked_info.reward_debt, Uint128::new(reward_debt));
+            }
+        }
+
+        // - 2 nfts give 1.5x, a red nft and a cap nft give 2x
+        // 	- USER_2 stakes token_id 10, USER_1 stakes red token_id 1 and cap token_id 2
+        // 	- after 5s, 50.000 reward is shared 40.000 / 10.000 by weight 200 + 200 and 100
+        // 	- USER_1 loses set bonus after unstaking token_id 1
+        #[test]
+        fn set_bonuses() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = create_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+            );
+            let trait_metadata = |trait_type: &str, value: &str| Metadata {
+                attributes: Some(vec![Trait {
+                    display_type: None,
+                    trait_type: trait_type.to_string(),
+                    value: value.to_string(),
+                }]),
+                ..Metadata::default()
+            };
+            mint_nft(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                1,
+                trait_metadata("color", "red"),
+            );
+            mint_nft(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                2,
+                trait_metadata("hat", "cap"),
+            );
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_2,
+                &campaign_contract,
+                &[10],
+            );
+
+            let set_bonuses = vec![
+                SetBonus {
+                    rule: SetRule::Count { count: 2 },
+                    multiplier: 150,
+                },
+                SetBonus {
+                    rule: SetRule::Traits {
+                        groups: vec![
+                            TraitGroup {
+                                trait_type: "color".to_string(),
+                                values: vec!["red".to_string(), "blue".to_string()],
+                            },
+                            TraitGroup {
+                                trait_type: "hat".to_string(),
+                                values: vec!["cap".to_string()],
+                            },
+                        ],
+                    },
+                    multiplier: 200,
+                },
+            ];
+
+            // only campaign owner can update set bonuses, a bonus cannot lower weight
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UpdateSetBonuses {
+                    set_bonuses: set_bonuses.clone(),
+                },
+                &[],
+            );
+            assert!(response.is_err());
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UpdateSetBonuses {
+                    set_bonuses: vec![SetBonus {
+                        rule: SetRule::Count { count: 2 },
+                        multiplier: 50,
+                    }],
+                },
+                &[],
+            );
+            assert!(response.is_err());
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UpdateSetBonuses {
+                    set_bonuses: set_bonuses.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+            let campaign_set_bonuses: Vec<SetBonus> = app
+                .wrap()
+                .query_wasm_smart(campaign_contract.clone(), &CampaignQueryMsg::SetBonuses {})
+                .unwrap();
+            assert_eq!(campaign_set_bonuses, set_bonuses);
+
+            fund_campaign(&mut app, &contracts, &campaign_contract, 1_000_000);
+
+            let stake = |app: &mut App, staker: &str, token_id: &str| {
+                app.execute_contract(
+                    Addr::unchecked(staker),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::StakeNfts {
+                        nfts: vec![NftStake {
+                            token_id: token_id.to_string(),
+                            lockup_term: 10,
+                            weight_proof: None,
+                        }],
+                        bonus: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            };
+            let staked_info = |app: &App, staker: &str| -> StakedInfoResult {
+                app.wrap()
+                    .query_wasm_smart(
+                        campaign_contract.clone(),
+                        &CampaignQueryMsg::NftStaked {
+                            owner: Addr::unchecked(staker),
+                        },
+                    )
+                    .unwrap()
+            };
+            stake(&mut app, USER_2, "10");
+            stake(&mut app, USER_1, "1");
+            assert_eq!(staked_info(&app, USER_1).set_multiplier, 100);
+            stake(&mut app, USER_1, "2");
+            let user_1_info = staked_info(&app, USER_1);
+            assert_eq!(user_1_info.set_multiplier, 200);
+            assert!(user_1_info.nfts.iter().all(|nft| nft.weight == 200));
+
+            increase_time(&mut app, 5);
+
+            // 5(s) * 10.000 = 50.000 reward shared by weight 400 and 100
+            assert_eq!(
+                staked_info(&app, USER_1).reward_debt,
+                Uint128::new(40_000u128)
+            );
+            assert_eq!(
+                staked_info(&app, USER_2).reward_debt,
+                Uint128::new(10_000u128)
+            );
+
+            increase_time(&mut app, 5);
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(staked_info(&app, USER_1).set_multiplier, 100);
+        }
+
+        // - 1.000 tokens weigh as much as one nft
+        // 	- USER_1 stakes token_id 1, USER_2 stakes 1.500 tokens of the same term
+        // 	- token_id 1 and the token_id of the deposit cannot be staked again
+        // 	- after 10s, 100.000 reward is shared 40.000 / 60.000 by weight 100 and 150
+        // 	- USER_2 gets its tokens back after unstaking the deposit
+        #[test]
+        fn token_staking() {
+            let (mut app, contracts) = instantiate_contracts();
+            let token_contract = contracts[1].contract_addr.clone();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let lockup_term = vec![LockupTerm {
+                value: 10,
+                percent: Uint128::new(100u128),
+                max_nfts: None,
+                max_reward: None,
+            }];
+
+            // tokens cannot be staked if token staking is not enabled
+            let nft_campaign = create_campaign(&mut app, &contracts, lockup_term.clone());
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: USER_2.to_string(),
+                    amount: Uint128::new(1_505u128),
+                },
+                &[],
+            )
+            .unwrap();
+            let stake_tokens = |app: &mut App, campaign: &str, amount: u128| {
+                app.execute_contract(
+                    Addr::unchecked(USER_2),
+                    Addr::unchecked(token_contract.clone()),
+                    &Cw20ExecuteMsg::Send {
+                        contract: campaign.to_string(),
+                        amount: Uint128::new(amount),
+                        msg: to_binary(&Cw20HookMsg::StakeTokens {
+                            lockup_term: 10,
+                            bonus: None,
+                        })
+                        .unwrap(),
+                    },
+                    &[],
+                )
+            };
+            let err = stake_tokens(&mut app, &nft_campaign, 1_500).unwrap_err();
+            assert_eq!(
+                err.root_cause().to_string(),
+                "## Token staking is not enabled in this campaign ##"
+            );
+
+            let campaign_contract = try_create_custom_campaign(
+                &mut app,
+                &contracts,
+                lockup_term,
+                Some(TokenStaking {
+                    contract_addr: Addr::unchecked(token_contract.clone()),
+                    amount_per_nft: Uint128::new(1_000u128),
+                }),
+                None,
+                false,
+                false,
+            )
+            .unwrap();
+            fund_campaign(&mut app, &contracts, &campaign_contract, 1_000_000);
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1],
+            );
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 10,
+                        weight_proof: None,
+                    }],
+                    bonus: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+            // deposit must weigh at least 1
+            let err = stake_tokens(&mut app, &campaign_contract, 5).unwrap_err();
+            assert_eq!(
+                err.root_cause().to_string(),
+                "## Weight and bonus multiplier must be greater than 0 ##"
+            );
+            stake_tokens(&mut app, &campaign_contract, 1_500).unwrap();
+
+            let deposits: Vec<TokenDeposit> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::TokenDeposits {
+                        owner: Addr::unchecked(USER_2),
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                deposits,
+                vec![TokenDeposit {
+                    token_id: "cw20:1".to_string(),
+                    amount: Uint128::new(1_500u128),
+                }]
+            );
+            let nft_info: NftInfo = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::NftInfo {
+                        token_id: "cw20:1".to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(nft_info.weight, 150);
+
+            // a token_id is staked once and token_ids of deposits are reserved
+            for (token_ids, error) in [
+                (vec!["2", "2"], "## Duplicate token_id: \"2\" ##"),
+                (vec!["1"], "## Duplicate token_id: \"1\" ##"),
+                (
+                    vec!["cw20:1"],
+                    "## Token_id \"cw20:1\" is reserved for token deposits ##",
+                ),
+            ] {
+                let err = app
+                    .execute_contract(
+                        Addr::unchecked(USER_1),
+                        Addr::unchecked(campaign_contract.clone()),
+                        &CampaignExecuteMsg::StakeNfts {
+                            nfts: token_ids
+                                .iter()
+                                .map(|token_id| NftStake {
+                                    token_id: token_id.to_string(),
+                                    lockup_term: 10,
+                                    weight_proof: None,
+                                })
+                                .collect(),
+                            bonus: None,
+                        },
+                        &[],
+                    )
+                    .unwrap_err();
+                assert_eq!(err.root_cause().to_string(), error);
+            }
+
+            increase_time(&mut app, 10);
+
+            app.execute_contract(
+                Addr::unchecked(USER_2),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "cw20:1".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: USER_2.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(balance.balance, Uint128::new(1_505u128));
+            let deposits: Vec<TokenDeposit> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::TokenDeposits {
+                        owner: Addr::unchecked(USER_2),
+                    },
+                )
+                .unwrap();
+            assert!(deposits.is_empty());
+
+            for (staker, reward) in [(USER_1, 40_000u128), (USER_2, 60_000u128)] {
+                let simulate_claim: SimulateClaimResult = app
+                    .wrap()
+                    .query_wasm_smart(
+                        campaign_contract.clone(),
+                        &CampaignQueryMsg::SimulateClaim {
+                            owner: Addr::unchecked(staker),
+                            at_time: None,
+                        },
+                    )
+                    .unwrap();
+                assert_eq!(simulate_claim.claimable_reward, Uint128::new(reward));
+            }
+        }
+
+        // - USER_1 has no bonus and USER_2 has 2x bonus in staker bonus root
+        // 	- USER_2 cannot stake tokens without its bonus proof
+        // 	- USER_2 stakes 1.500 tokens with 2x bonus, the deposit weighs 300
+        #[test]
+        fn token_staking_staker_bonus() {
+            let (mut app, contracts) = instantiate_contracts();
+            let token_contract = contracts[1].contract_addr.clone();
+            let campaign_contract = try_create_custom_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+                Some(TokenStaking {
+                    contract_addr: Addr::unchecked(token_contract.clone()),
+                    amount_per_nft: Uint128::new(1_000u128),
+                }),
+                None,
+                false,
+                false,
+            )
+            .unwrap();
+            let (staker_bonus_root, bonus_proofs) =
+                merkle_tree([&format!("{}:100", USER_1), &format!("{}:200", USER_2)]);
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::RegisterMerkleRoots {
+                    staker_bonus_root: Some(staker_bonus_root),
+                    token_weight_root: None,
+                },
+                &[],
+            )
+            .unwrap();
+            fund_campaign(&mut app, &contracts, &campaign_contract, 1_000_000);
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: USER_2.to_string(),
+                    amount: Uint128::new(1_500u128),
+                },
+                &[],
+            )
+            .unwrap();
+            let stake_tokens = |app: &mut App, bonus: Option<StakerBonusProof>| {
+                app.execute_contract(
+                    Addr::unchecked(USER_2),
+                    Addr::unchecked(token_contract.clone()),
+                    &Cw20ExecuteMsg::Send {
+                        contract: campaign_contract.clone(),
+                        amount: Uint128::new(1_500u128),
+                        msg: to_binary(&Cw20HookMsg::StakeTokens {
+                            lockup_term: 10,
+                            bonus,
+                        })
+                        .unwrap(),
+                    },
+                    &[],
+                )
+            };
+
+            let err = stake_tokens(&mut app, None).unwrap_err();
+            assert_eq!(
+                err.root_cause().to_string(),
+                "## Staker is not eligible for this campaign ##"
+            );
+            let err = stake_tokens(
+                &mut app,
+                Some(StakerBonusProof {
+                    bonus_multiplier: 100,
+                    proof: bonus_proofs[1].clone(),
+                }),
+            )
+            .unwrap_err();
+            assert_eq!(
+                err.root_cause().to_string(),
+                "## Merkle proof verification failed ##"
+            );
+
+            stake_tokens(
+                &mut app,
+                Some(StakerBonusProof {
+                    bonus_multiplier: 200,
+                    proof: bonus_proofs[1].clone(),
+                }),
+            )
+            .unwrap();
+            let nft_info: NftInfo = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::NftInfo {
+                        token_id: "cw20:1".to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(nft_info.weight, 300);
+        }
+
+        // - campaign 2 is exclusive with a switch cooldown of 30s, campaign 1 and 3 are not
+        // 	- USER_1 stakes token_id 1 in campaign 1 and unstakes it after 10s
+        // 	- token_id 1 joins campaign 3 at once and leaves it after 1s
+        // 	- token_id 1 can join campaign 2 only 30s after leaving campaign 3
+        #[test]
+        fn exclusive_campaigns() {
+            let (mut app, contracts) = instantiate_contracts();
+            let factory_contract = contracts[0].contract_addr.clone();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let lockup_term = vec![LockupTerm {
+                value: 10,
+                percent: Uint128::new(100u128),
+                max_nfts: None,
+                max_reward: None,
+            }];
+            let campaign_1 = create_campaign(&mut app, &contracts, lockup_term.clone());
+            let campaign_3 = create_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 1,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+            );
+            let campaign_2 = try_create_custom_campaign(
+                &mut app,
+                &contracts,
+                lockup_term,
+                None,
+                Some(ExclusivityRules {
+                    switch_cooldown: 30,
+                }),
+                false,
+                false,
+            )
+            .unwrap();
+            fund_campaign(&mut app, &contracts, &campaign_1, 1_000_000);
+            fund_campaign(&mut app, &contracts, &campaign_2, 1_000_000);
+            fund_campaign(&mut app, &contracts, &campaign_3, 1_000_000);
+
+            let exclusivity: Option<ExclusivityRules> = app
+                .wrap()
+                .query_wasm_smart(campaign_2.clone(), &CampaignQueryMsg::ExclusivityRules {})
+                .unwrap();
+            assert_eq!(
+                exclusivity,
+                Some(ExclusivityRules {
+                    switch_cooldown: 30
+                })
+            );
+
+            // only campaigns created by factory update the registry
+            let err = app
+                .execute_contract(
+                    Addr::unchecked(USER_1),
+                    Addr::unchecked(factory_contract.clone()),
+                    &FactoryExecuteMsg::RegisterNfts {
+                        token_ids: vec!["1".to_string()],
+                    },
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(err.root_cause().to_string(), "## Unauthorized ##");
+
+            mint_nfts(&mut app, &collection_contract, USER_1, &campaign_1, &[1]);
+            let stake_msg = CampaignExecuteMsg::StakeNfts {
+                nfts: vec![NftStake {
+                    token_id: "1".to_string(),
+                    lockup_term: 10,
+                    weight_proof: None,
+                }],
+                bonus: None,
+            };
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_1.clone()),
+                &stake_msg,
+                &[],
+            )
+            .unwrap();
+
+            let query_registration = |app: &App| -> Option<NftRegistration> {
+                app.wrap()
+                    .query_wasm_smart(
+                        factory_contract.clone(),
+                        &FactoryQueryMsg::NftRegistration {
+                            collection: collection_contract.clone(),
+                            token_id: "1".to_string(),
+                        },
+                    )
+                    .unwrap()
+            };
+            let staked_time = app.block_info().time.seconds();
+            assert_eq!(
+                query_registration(&app),
+                Some(NftRegistration {
+                    campaign_addr: Addr::unchecked(campaign_1.clone()),
+                    staked: true,
+                    updated_at: staked_time,
+                })
+            );
+
+            increase_time(&mut app, 10);
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_1.clone()),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            let unstaked_time = app.block_info().time.seconds();
+            assert_eq!(
+                query_registration(&app),
+                Some(NftRegistration {
+                    campaign_addr: Addr::unchecked(campaign_1.clone()),
+                    staked: false,
+                    updated_at: unstaked_time,
+                })
+            );
+
+            // nft switches freely between campaigns without exclusivity rules
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(collection_contract.clone()),
+                &Cw721ExecuteMsg::Approve {
+                    spender: campaign_3.clone(),
+                    token_id: "1".to_string(),
+                    expires: None,
+                },
+                &[],
+            )
+            .unwrap();
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_3.clone()),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 1,
+                        weight_proof: None,
+                    }],
+                    bonus: None,
+                },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(
+                query_registration(&app).unwrap().campaign_addr,
+                Addr::unchecked(campaign_3.clone())
+            );
+            increase_time(&mut app, 1);
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_3.clone()),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            let unstaked_time = app.block_info().time.seconds();
+
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(collection_contract.clone()),
+                &Cw721ExecuteMsg::Approve {
+                    spender: campaign_2.clone(),
+                    token_id: "1".to_string(),
+                    expires: None,
+                },
+                &[],
+            )
+            .unwrap();
+            let err = app
+                .execute_contract(
+                    Addr::unchecked(USER_1),
+                    Addr::unchecked(campaign_2.clone()),
+                    &stake_msg,
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.root_cause().to_string(),
+                format!(
+                    "## Nft \"1\" cannot switch campaign until {} ##",
+                    unstaked_time + 30
+                )
+            );
+
+            increase_time(&mut app, 30);
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_2.clone()),
+                &stake_msg,
+                &[],
+            )
+            .unwrap();
+            assert_eq!(
+                query_registration(&app).unwrap().campaign_addr,
+                Addr::unchecked(campaign_2)
+            );
+        }
+
+        // - nfts stay in wallet of USER_1 who soft stakes token_id 1, 2, 3
+        // 	- after 4s, USER_1 transfers token_id 2 and USER_2 slashes it
+        // 	- after 2s, USER_1 transfers token_id 3 which is slashed when USER_1 claims
+        // 	- USER_1 unstakes token_id 1 which is kept in its wallet
+        #[test]
+        fn soft_staking() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = try_create_custom_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+                None,
+                None,
+                true,
+                false,
+            )
+            .unwrap();
+            fund_campaign(&mut app, &contracts, &campaign_contract, 1_000_000);
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1, 2, 3],
+            );
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: ["1", "2", "3"]
+                        .iter()
+                        .map(|token_id| NftStake {
+                            token_id: token_id.to_string(),
+                            lockup_term: 10,
+                            weight_proof: None,
+                        })
+                        .collect(),
+                    bonus: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+            let query_owner = |app: &App, token_id: &str| -> String {
+                let owner: OwnerOfResponse = app
+                    .wrap()
+                    .query_wasm_smart(
+                        collection_contract.clone(),
+                        &Cw721QueryMsg::OwnerOf {
+                            token_id: token_id.to_string(),
+                            include_expired: None,
+                        },
+                    )
+                    .unwrap();
+                owner.owner
+            };
+            assert_eq!(query_owner(&app, "1"), USER_1);
+            let soft_staking: bool = app
+                .wrap()
+                .query_wasm_smart(campaign_contract.clone(), &CampaignQueryMsg::SoftStaking {})
+                .unwrap();
+            assert!(soft_staking);
+
+            // nft still owned by staker cannot be slashed
+            let slash = |app: &mut App, token_id: &str| {
+                app.execute_contract(
+                    Addr::unchecked(USER_2),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::Slash {
+                        token_id: token_id.to_string(),
+                    },
+                    &[],
+                )
+            };
+            let err = slash(&mut app, "1").unwrap_err();
+            assert_eq!(
+                err.root_cause().to_string(),
+                "## Nft \"1\" is still owned by its staker ##"
+            );
+
+            let transfer = |app: &mut App, token_id: &str| {
+                app.execute_contract(
+                    Addr::unchecked(USER_1),
+                    Addr::unchecked(collection_contract.clone()),
+                    &Cw721ExecuteMsg::TransferNft {
+                        recipient: USER_2.to_string(),
+                        token_id: token_id.to_string(),
+                    },
+                    &[],
+                )
+                .unwrap();
+            };
+            increase_time(&mut app, 4);
+            transfer(&mut app, "2");
+            slash(&mut app, "2").unwrap();
+
+            increase_time(&mut app, 2);
+            transfer(&mut app, "3");
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: Uint128::new(23_333u128),
+                    owner: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+            let ledger: Vec<LedgerEntry> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::StakerLedger {
+                        owner: Addr::unchecked(USER_1),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                ledger
+                    .iter()
+                    .map(|entry| (entry.action.clone(), entry.token_id.clone(), entry.amount))
+                    .collect::<Vec<_>>(),
+                vec![
+                    (
+                        LedgerAction::Penalty,
+                        Some("2".to_string()),
+                        Uint128::new(13_333u128)
+                    ),
+                    (
+                        LedgerAction::Penalty,
+                        Some("3".to_string()),
+                        Uint128::new(23_333u128)
+                    ),
+                    (LedgerAction::Claim, None, Uint128::new(23_333u128)),
+                ]
+            );
+
+            // soft staked nft is only unregistered
+            increase_time(&mut app, 4);
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(query_owner(&app, "1"), USER_1);
+            let staked_info: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_1),
+                    },
+                )
+                .unwrap();
+            assert!(staked_info.nfts.is_empty());
+            assert_eq!(staked_info.reward_debt, Uint128::new(40_000u128));
+        }
+
+        // - USER_1 soft stakes token_id 1
+        // 	- after term, USER_1 transfers token_id 1 to USER_2 and unstakes it
+        // 	- token_id 1 is slashed, its reward is not settled to USER_1
+        #[test]
+        fn soft_unstake_transferred_nft() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = try_create_custom_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+                None,
+                None,
+                true,
+                false,
+            )
+            .unwrap();
+            fund_campaign(&mut app, &contracts, &campaign_contract, 1_000_000);
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1],
+            );
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 10,
+                        weight_proof: None,
+                    }],
+                    bonus: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+            increase_time(&mut app, 10);
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(collection_contract.clone()),
+                &Cw721ExecuteMsg::TransferNft {
+                    recipient: USER_2.to_string(),
+                    token_id: "1".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+
+            let staked_info: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_1),
+                    },
+                )
+                .unwrap();
+            assert!(staked_info.nfts.is_empty());
+            assert_eq!(staked_info.reward_debt, Uint128::zero());
+
+            let ledger: Vec<LedgerEntry> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::StakerLedger {
+                        owner: Addr::unchecked(USER_1),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                ledger
+                    .iter()
+                    .map(|entry| (entry.action.clone(), entry.token_id.clone(), entry.amount))
+                    .collect::<Vec<_>>(),
+                vec![(
+                    LedgerAction::Penalty,
+                    Some("1".to_string()),
+                    Uint128::new(100_000u128)
+                )]
+            );
+
+            // nothing is left to claim
+            let err = app
+                .execute_contract(
+                    Addr::unchecked(USER_1),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::ClaimReward {
+                        amount: Uint128::new(1u128),
+                        owner: None,
+                    },
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(err.root_cause().to_string(), "## Insufficient balance ##");
+        }
+
+        // - 10s term of soft staking campaign pays at most 30.000
+        // 	- USER_1 soft stakes token_id 1, 2
+        // 	- after 3s, 30.000 reward is used up and token_id 3 cannot be staked
+        // 	- token_id 2 is transferred and slashed, its 15.000 reward is released to the term
+        // 	- USER_1 stakes token_id 3
+        #[test]
+        fn slash_releases_term_reward() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = try_create_custom_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: Some(Uint128::new(30_000u128)),
+                }],
+                None,
+                None,
+                true,
+                false,
+            )
+            .unwrap();
+            fund_campaign(&mut app, &contracts, &campaign_contract, 1_000_000);
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1, 2, 3],
+            );
+            let stake = |app: &mut App, token_ids: &[&str]| {
+                app.execute_contract(
+                    Addr::unchecked(USER_1),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::StakeNfts {
+                        nfts: token_ids
+                            .iter()
+                            .map(|token_id| NftStake {
+                                token_id: token_id.to_string(),
+                                lockup_term: 10,
+                                weight_proof: None,
+                            })
+                            .collect(),
+                        bonus: None,
+                    },
+                    &[],
+                )
+            };
+            let term_fill = |app: &App| {
+                let campaign_info: CampaignInfoResult = app
+                    .wrap()
+                    .query_wasm_smart(
+                        campaign_contract.clone(),
+                        &CampaignQueryMsg::CampaignInfo {},
+                    )
+                    .unwrap();
+                campaign_info.term_fills[0].clone()
+            };
+            stake(&mut app, &["1", "2"]).unwrap();
+
+            increase_time(&mut app, 3);
+            assert!(stake(&mut app, &["3"]).is_err());
+            app.execute_contract(
+                Addr::unchecked(USER_2),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::Settle { limit: None },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(term_fill(&app).accrued_reward, Uint128::new(30_000u128));
+
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(collection_contract.clone()),
+                &Cw721ExecuteMsg::TransferNft {
+                    recipient: USER_2.to_string(),
+                    token_id: "2".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            app.execute_contract(
+                Addr::unchecked(USER_2),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::Slash {
+                    token_id: "2".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(term_fill(&app).accrued_reward, Uint128::new(15_000u128));
+
+            stake(&mut app, &["3"]).unwrap();
+        }
+
+        // - factory instantiates receipt collection of campaign, USER_1 stakes token_id 1
+        // 	- after 6s, USER_1 transfers its receipt to USER_2 and cannot unstake anymore
+        // 	- USER_2 sends the receipt to take the position and claim its reward at once
+        // 	- USER_2 sends the receipt to transfer the position back to USER_1
+        // 	- after term, USER_1 sends the receipt to unstake and receives token_id 1
+        #[test]
+        fn receipt_positions() {
+            let (mut app, contracts) = instantiate_contracts();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let campaign_contract = try_create_custom_campaign(
+                &mut app,
+                &contracts,
+                vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                    max_nfts: None,
+                    max_reward: None,
+                }],
+                None,
+                None,
+                false,
+                true,
+            )
+            .unwrap();
+            let receipt_collection: Option<Addr> = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::ReceiptCollection {},
+                )
+                .unwrap();
+            let receipt_collection = receipt_collection.unwrap();
+
+            fund_campaign(&mut app, &contracts, &campaign_contract, 1_000_000);
+            mint_nfts(
+                &mut app,
+                &collection_contract,
+                USER_1,
+                &campaign_contract,
+                &[1],
+            );
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_contract.clone()),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 10,
+                        weight_proof: None,
+                    }],
+                    bonus: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+            let query_owner = |app: &App, collection: &str, token_id: &str| {
+                app.wrap().query_wasm_smart::<OwnerOfResponse>(
+                    collection,
+                    &Cw721QueryMsg::OwnerOf {
+                        token_id: token_id.to_string(),
+                        include_expired: None,
+                    },
+                )
+            };
+            assert_eq!(
+                query_owner(&app, receipt_collection.as_str(), "1")
+                    .unwrap()
+                    .owner,
+                USER_1
+            );
+
+            increase_time(&mut app, 6);
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                receipt_collection.clone(),
+                &Cw721ExecuteMsg::TransferNft {
+                    recipient: USER_2.to_string(),
+                    token_id: "1".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+
+            // staker without receipt cannot unstake
+            let err = app
+                .execute_contract(
+                    Addr::unchecked(USER_1),
+                    Addr::unchecked(campaign_contract.clone()),
+                    &CampaignExecuteMsg::UnStakeNft {
+                        token_id: "1".to_string(),
+                    },
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.root_cause().to_string(),
+                "## Send receipt of \"1\" to unstake ##"
+            );
+
+            // holder takes the position and claims its pending reward, the receipt is sent back
+            app.execute_contract(
+                Addr::unchecked(USER_2),
+                receipt_collection.clone(),
+                &Cw721ExecuteMsg::SendNft {
+                    contract: campaign_contract.clone(),
+                    token_id: "1".to_string(),
+                    msg: to_binary(&ReceiptHookMsg::Claim {}).unwrap(),
+                },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(
+                query_owner(&app, receipt_collection.as_str(), "1")
+                    .unwrap()
+                    .owner,
+                USER_2
+            );
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    contracts[1].contract_addr.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: USER_2.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(balance.balance, Uint128::new(60_000u128));
+            let staked_info: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_contract.clone(),
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_2),
+                    },
+                )
+                .unwrap();
+            assert_eq!(staked_info.nfts.len(), 1);
+            assert_eq!(staked_info.reward_claimed, Uint128::new(60_000u128));
+
+            // holder moves the position with the receipt to USER_1
+            app.execute_contract(
+                Addr::unchecked(USER_2),
+                receipt_collection.clone(),
+                &Cw721ExecuteMsg::SendNft {
+                    contract: campaign_contract.clone(),
+                    token_id: "1".to_string(),
+                    msg: to_binary(&ReceiptHookMsg::Transfer {
+                        recipient: USER_1.to_string(),
+                    })
+                    .unwrap(),
+                },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(
+                query_owner(&app, receipt_collection.as_str(), "1")
+                    .unwrap()
+                    .owner,
+                USER_1
+            );
+
+            increase_time(&mut app, 4);
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                receipt_collection.clone(),
+                &Cw721ExecuteMsg::SendNft {
+                    contract: campaign_contract.clone(),
+                    token_id: "1".to_string(),
+                    msg: to_binary(&ReceiptHookMsg::Unstake {}).unwrap(),
+                },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(
+                query_owner(&app, &collection_contract, "1").unwrap().owner,
+                USER_1
+            );
+            assert!(query_owner(&app, receipt_collection.as_str(), "1").is_err());
+        }
+
+        // - ADMIN hands the factory to a cw4 group of USER_1 (weight 2) and USER_2 (weight 1)
+        // 	- USER_2 proposes a new campaign_code_id which passes when USER_1 votes
+        // 	- after the timelock of 50s, anyone executes the proposal
+        #[test]
+        fn governance() {
+            let (mut app, contracts) = instantiate_contracts();
+            let factory_contract = contracts[0].contract_addr.clone();
+            let group_contract = instantiate_group(&mut app, &[(USER_1, 2), (USER_2, 1)]);
+
+            let execute = |app: &mut App, sender: &str, msg: &FactoryExecuteMsg| {
+                app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(factory_contract.clone()),
+                    msg,
+                    &[],
+                )
+                .map_err(|err| err.root_cause().to_string())
+            };
+            let set_governance = FactoryExecuteMsg::SetGovernance {
+                group_addr: group_contract.clone(),
+                quorum: Decimal::percent(60),
+                voting_period: 100,
+                timelock: 50,
+            };
+            assert_eq!(
+                execute(&mut app, USER_1, &set_governance).unwrap_err(),
+                "## Unauthorized ##"
+            );
+            execute(&mut app, ADMIN, &set_governance).unwrap();
+
+            // owner can no longer update config
+            let update_config = FactoryExecuteMsg::UpdateConfig {
+                owner: None,
+                campaign_code_id: Some(99),
+                term_policy: None,
+                receipt_code_id: None,
+                max_active_campaigns: None,
+            };
+            assert_eq!(
+                execute(&mut app, ADMIN, &update_config).unwrap_err(),
+                "## Privileged actions are governed by proposals ##"
+            );
+            let propose = FactoryExecuteMsg::Propose {
+                action: ProposalAction::UpdateConfig {
+                    owner: None,
+                    campaign_code_id: Some(99),
+                    term_policy: None,
+                    receipt_code_id: None,
+                    max_active_campaigns: None,
+                },
+            };
+            assert_eq!(
+                execute(&mut app, ADMIN, &propose).unwrap_err(),
+                "## Not a member of the governance group ##"
+            );
+
+            // 1/3 of total weight is below quorum
+            execute(&mut app, USER_2, &propose).unwrap();
+            let execute_proposal = FactoryExecuteMsg::ExecuteProposal { proposal_id: 1 };
+            assert_eq!(
+                execute(&mut app, ADMIN, &execute_proposal).unwrap_err(),
+                "## Proposal 1 has not passed ##"
+            );
+            let vote = FactoryExecuteMsg::Vote { proposal_id: 1 };
+            assert_eq!(
+                execute(&mut app, USER_2, &vote).unwrap_err(),
+                "## Already voted on proposal 1 ##"
+            );
+
+            increase_time(&mut app, 10);
+            execute(&mut app, USER_1, &vote).unwrap();
+            let proposal: Proposal = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &FactoryQueryMsg::Proposal { proposal_id: 1 },
+                )
+                .unwrap();
+            assert_eq!(proposal.yes_weight, 3);
+            assert_eq!(proposal.total_weight, 3);
+            let passed_at = proposal.passed_at.unwrap();
+            assert_eq!(
+                execute(&mut app, ADMIN, &execute_proposal).unwrap_err(),
+                format!("## Proposal 1 is timelocked until {} ##", passed_at + 50)
+            );
+
+            increase_time(&mut app, 50);
+            execute(&mut app, ADMIN, &execute_proposal).unwrap();
+            let config: ConfigResponse = app
+                .wrap()
+                .query_wasm_smart(factory_contract.clone(), &FactoryQueryMsg::Config {})
+                .unwrap();
+            assert_eq!(config.campaign_code_id, 99);
+            assert_eq!(
+                execute(&mut app, ADMIN, &execute_proposal).unwrap_err(),
+                "## Proposal 1 is already executed ##"
+            );
+
+            // voting period of proposal 2 is over
+            execute(&mut app, USER_2, &propose).unwrap();
+            increase_time(&mut app, 100);
+            assert_eq!(
+                execute(
+                    &mut app,
+                    USER_1,
+                    &FactoryExecuteMsg::Vote { proposal_id: 2 }
+                )
+                .unwrap_err(),
+                "## Voting period of proposal 2 is over ##"
+            );
+
+            // USER_1 alone passes the change of governance
+            let governance = Governance {
+                group_addr: Addr::unchecked(group_contract),
+                quorum: Decimal::percent(100),
+                voting_period: 200,
+                timelock: 0,
+            };
+            execute(
+                &mut app,
+                USER_1,
+                &FactoryExecuteMsg::Propose {
+                    action: ProposalAction::UpdateGovernance(governance.clone()),
+                },
+            )
+            .unwrap();
+            increase_time(&mut app, 50);
+            execute(
+                &mut app,
+                USER_2,
+                &FactoryExecuteMsg::ExecuteProposal { proposal_id: 3 },
+            )
+            .unwrap();
+            let current_governance: Option<Governance> = app
+                .wrap()
+                .query_wasm_smart(factory_contract, &FactoryQueryMsg::Governance {})
+                .unwrap();
+            assert_eq!(current_governance, Some(governance));
+        }
+
+        // - campaigns with unverified reward token or collection are flagged in event
+        // 	- ADMIN verifies the reward token and requires verified assets
+        // 	- campaigns are rejected until the collection is verified too
+        #[test]
+        fn verified_assets() {
+            let (mut app, contracts) = instantiate_contracts();
+            let factory_contract = contracts[0].contract_addr.clone();
+            let token_contract = contracts[1].contract_addr.clone();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let reward_token = TokenInfo::Token {
+                contract_addr: token_contract.clone(),
+            };
+
+            let create_campaign = |app: &mut App| {
+                let current_block_time = app.block_info().time.seconds();
+                app.execute_contract(
+                    Addr::unchecked(ADMIN),
+                    Addr::unchecked(factory_contract.clone()),
+                    &FactoryExecuteMsg::CreateCampaign {
+                        owner: ADMIN.to_string(),
+                        campaign_name: "campaign name".to_string(),
+                        campaign_image: "campaign image".to_string(),
+                        campaign_description: "campaign description".to_string(),
+                        start_time: current_block_time + 10,
+                        end_time: current_block_time + 110,
+                        limit_per_staker: 0,
+                        reward_token_info: AssetToken {
+                            info: TokenInfo::Token {
+                                contract_addr: token_contract.clone(),
+                            },
+                            amount: Uint128::zero(),
+                        },
+                        allowed_collection: collection_contract.clone(),
+                        lockup_term: vec![LockupTerm {
+                            value: 10,
+                            percent: Uint128::new(100u128),
+                            max_nfts: None,
+                            max_reward: None,
+                        }],
+                        token_staking: None,
+                        exclusivity: None,
+                        soft_staking: None,
+                        receipt: None,
+                    },
+                    &[],
+                )
+                .map_err(|err| err.root_cause().to_string())
+            };
+            let verified_flags = |response: &AppResponse| {
+                let event = response
+                    .events
+                    .iter()
+                    .find(|event| event.ty == "wasm-stakify-factory-create-campaign")
+                    .unwrap();
+                ["verified_reward_token", "verified_collection"].map(|key| {
+                    event
+                        .attributes
+                        .iter()
+                        .find(|attr| attr.key == key)
+                        .unwrap()
+                        .value
+                        .clone()
+                })
+            };
+            let update_verified_assets = |app: &mut App, sender: &str, update| {
+                app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(factory_contract.clone()),
+                    &FactoryExecuteMsg::UpdateVerifiedAssets(update),
+                    &[],
+                )
+                .map_err(|err| err.root_cause().to_string())
+            };
+
+            let response = create_campaign(&mut app).unwrap();
+            assert_eq!(verified_flags(&response), ["false", "false"]);
+
+            let verify_token = VerifiedAssetsUpdate {
+                require_verified: Some(true),
+                add: vec![AssetLabel {
+                    asset: VerifiedAsset::Token(reward_token.clone()),
+                    label: "Reward".to_string(),
+                }],
+                remove: vec![],
+            };
+            assert_eq!(
+                update_verified_assets(&mut app, USER_1, verify_token.clone()).unwrap_err(),
+                "## Unauthorized ##"
+            );
+            update_verified_assets(&mut app, ADMIN, verify_token).unwrap();
+            assert_eq!(
+                create_campaign(&mut app).unwrap_err(),
+                format!("## Asset \"{}\" is not verified ##", collection_contract)
+            );
+
+            let verify_collection = |asset: VerifiedAsset| VerifiedAssetsUpdate {
+                require_verified: None,
+                add: vec![AssetLabel {
+                    asset,
+                    label: "Collection".to_string(),
+                }],
+                remove: vec![],
+            };
+            assert_eq!(
+                update_verified_assets(
+                    &mut app,
+                    ADMIN,
+                    verify_collection(VerifiedAsset::Collection("".to_string()))
+                )
+                .unwrap_err(),
+                "## Invalid verified asset \"collection:\" ##"
+            );
+            update_verified_assets(
+                &mut app,
+                ADMIN,
+                verify_collection(VerifiedAsset::Collection(collection_contract.clone())),
+            )
+            .unwrap();
+            let response = create_campaign(&mut app).unwrap();
+            assert_eq!(verified_flags(&response), ["true", "true"]);
+
+            let verification: Option<Verification> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &FactoryQueryMsg::Verification {
+                        asset: VerifiedAsset::Token(reward_token.clone()),
+                    },
+                )
+                .unwrap();
+            assert_eq!(verification.unwrap().label, "Reward");
+            let verifications: Vec<Verification> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &FactoryQueryMsg::Verifications {
+                        start_after: Some(VerifiedAsset::Collection(collection_contract.clone())),
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                verifications
+                    .iter()
+                    .map(|verification| verification.asset.clone())
+                    .collect::<Vec<_>>(),
+                vec![VerifiedAsset::Token(reward_token)]
+            );
+
+            // removed collection is no longer verified
+            update_verified_assets(
+                &mut app,
+                ADMIN,
+                VerifiedAssetsUpdate {
+                    require_verified: None,
+                    add: vec![],
+                    remove: vec![VerifiedAsset::Collection(collection_contract.clone())],
+                },
+            )
+            .unwrap();
+            assert!(create_campaign(&mut app).is_err());
+        }
+
+        // - ADMIN limits creators to 2 active campaigns and creates 2 campaigns
+        // 	- third campaign is rejected until a campaign ends
+        // 	- campaign 2 is extended, so only campaign 1 ends and a fourth campaign is rejected
+        // 	- USER_1 sets its display name and ADMIN verifies USER_1
+        #[test]
+        fn creator_campaigns() {
+            let (mut app, contracts) = instantiate_contracts();
+            let factory_contract = contracts[0].contract_addr.clone();
+            let lockup_term = vec![LockupTerm {
+                value: 10,
+                percent: Uint128::new(100u128),
+                max_nfts: None,
+                max_reward: None,
+            }];
+
+            let execute = |app: &mut App, sender: &str, msg: &FactoryExecuteMsg| {
+                app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(factory_contract.clone()),
+                    msg,
+                    &[],
+                )
+                .map_err(|err| err.root_cause().to_string())
+            };
+            execute(
+                &mut app,
+                ADMIN,
+                &FactoryExecuteMsg::UpdateConfig {
+                    owner: None,
+                    campaign_code_id: None,
+                    term_policy: None,
+                    receipt_code_id: None,
+                    max_active_campaigns: Some(2),
+                },
+            )
+            .unwrap();
+
+            let campaign_1 = create_campaign(&mut app, &contracts, lockup_term.clone());
+            let campaign_2 = create_campaign(&mut app, &contracts, lockup_term.clone());
+            assert_eq!(
+                try_create_campaign(&mut app, &contracts, lockup_term.clone()).unwrap_err(),
+                "## Creator has 2 active campaigns ##"
+            );
+
+            // campaign 2 is extended, it still counts after campaign 1 ends
+            let end_time = app.block_info().time.seconds() + 1_000;
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(campaign_2.clone()),
+                &CampaignExecuteMsg::UpdateCampaign {
+                    campaign_info_update: CampaignInfoUpdate {
+                        campaign_name: None,
+                        campaign_image: None,
+                        campaign_description: None,
+                        limit_per_staker: None,
+                        lockup_term: None,
+                        start_time: None,
+                        end_time: Some(end_time),
+                    },
+                },
+                &[],
+            )
+            .unwrap();
+            increase_time(&mut app, 111);
+            let campaign_3 = create_campaign(&mut app, &contracts, lockup_term.clone());
+            assert_eq!(
+                try_create_campaign(&mut app, &contracts, lockup_term).unwrap_err(),
+                "## Creator has 2 active campaigns ##"
+            );
+
+            let campaigns_by_creator = |app: &App, creator: &str, start_after| {
+                app.wrap()
+                    .query_wasm_smart::<Vec<FactoryCampaign>>(
+                        factory_contract.clone(),
+                        &FactoryQueryMsg::CampaignsByCreator {
+                            creator: creator.to_string(),
+                            start_after,
+                            limit: None,
+                        },
+                    )
+                    .unwrap()
+                    .iter()
+                    .map(|campaign| campaign.campaign_addr.to_string())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                campaigns_by_creator(&app, ADMIN, None),
+                vec![campaign_1, campaign_2.clone(), campaign_3.clone()]
+            );
+            assert_eq!(
+                campaigns_by_creator(&app, ADMIN, Some(1)),
+                vec![campaign_2, campaign_3]
+            );
+            assert!(campaigns_by_creator(&app, USER_1, None).is_empty());
+
+            assert_eq!(
+                execute(
+                    &mut app,
+                    USER_1,
+                    &FactoryExecuteMsg::SetCreatorProfile {
+                        display_name: " ".to_string(),
+                    },
+                )
+                .unwrap_err(),
+                "## Invalid creator profile ##"
+            );
+            execute(
+                &mut app,
+                USER_1,
+                &FactoryExecuteMsg::SetCreatorProfile {
+                    display_name: "Stakify".to_string(),
+                },
+            )
+            .unwrap();
+            let verify_creator = FactoryExecuteMsg::VerifyCreator {
+                creator: USER_1.to_string(),
+                verified: true,
+            };
+            assert_eq!(
+                execute(&mut app, USER_1, &verify_creator).unwrap_err(),
+                "## Unauthorized ##"
+            );
+            execute(&mut app, ADMIN, &verify_creator).unwrap();
+
+            // creator without profile is verified without display_name
+            let response = app
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    Addr::unchecked(factory_contract.clone()),
+                    &FactoryExecuteMsg::VerifyCreator {
+                        creator: USER_2.to_string(),
+                        verified: true,
+                    },
+                    &[],
+                )
+                .unwrap();
+            let event = response
+                .events
+                .iter()
+                .find(|event| event.ty == "wasm-stakify-factory-set-creator-profile")
+                .unwrap();
+            assert_eq!(
+                event
+                    .attributes
+                    .iter()
+                    .map(|attr| (attr.key.as_str(), attr.value.as_str()))
+                    .collect::<Vec<_>>(),
+                vec![
+                    ("_contract_addr", factory_contract.as_str()),
+                    ("schema_version", "1"),
+                    ("creator", USER_2),
+                    ("display_name", "none"),
+                    ("verified", "true"),
+                ]
+            );
+
+            let profile: Option<CreatorProfile> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract,
+                    &FactoryQueryMsg::CreatorProfile {
+                        creator: USER_1.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                profile,
+                Some(CreatorProfile {
+                    display_name: "Stakify".to_string(),
+                    verified: true,
+                })
+            );
+        }
+
+        // - ADMIN creates 3 campaigns, USER_1 stakes token_id 1 in campaign 1 and 2 in campaign 3
+        // 	- positions of USER_1 are listed 2 campaigns per page
+        // 	- after USER_1 claims in campaign 1, global stats sum the claimed reward
+        // 	- after USER_1 unstakes token_id 2, global stats count 1 staked nft
+        #[test]
+        fn aggregated_queries() {
+            let (mut app, contracts) = instantiate_contracts();
+            let factory_contract = contracts[0].contract_addr.clone();
+            let token_contract = contracts[1].contract_addr.clone();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let lockup_term = vec![LockupTerm {
+                value: 10,
+                percent: Uint128::new(100u128),
+                max_nfts: None,
+                max_reward: None,
+            }];
+            let campaigns = (0..3)
+                .map(|_| create_campaign(&mut app, &contracts, lockup_term.clone()))
+                .collect::<Vec<_>>();
+            for campaign in &campaigns {
+                fund_campaign(&mut app, &contracts, campaign, 1_000_000);
+            }
+            for (token_id, campaign) in [("1", &campaigns[0]), ("2", &campaigns[2])] {
+                mint_nfts(
+                    &mut app,
+                    &collection_contract,
+                    USER_1,
+                    campaign,
+                    &[token_id.parse().unwrap()],
+                );
+                app.execute_contract(
+                    Addr::unchecked(USER_1),
+                    Addr::unchecked(campaign.clone()),
+                    &CampaignExecuteMsg::StakeNfts {
+                        nfts: vec![NftStake {
+                            token_id: token_id.to_string(),
+                            lockup_term: 10,
+                            weight_proof: None,
+                        }],
+                        bonus: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            }
+            increase_time(&mut app, 5);
+
+            let user_positions = |app: &App, start_after| -> UserPositionsResponse {
+                app.wrap()
+                    .query_wasm_smart(
+                        factory_contract.clone(),
+                        &FactoryQueryMsg::UserPositions {
+                            user: USER_1.to_string(),
+                            start_after,
+                            limit: Some(2),
+                        },
+                    )
+                    .unwrap()
+            };
+            let page = user_positions(&app, None);
+            assert_eq!(page.next_start_after, Some(2));
+            assert_eq!(page.positions.len(), 1);
+            assert_eq!(page.positions[0].campaign_key, 1);
+            assert_eq!(page.positions[0].nfts[0].token_id, "1");
+            assert_eq!(page.positions[0].nft_count, 1);
+            let claimable_reward = page.positions[0].claimable_reward;
+            assert!(!claimable_reward.is_zero());
+
+            let page = user_positions(&app, Some(2));
+            assert_eq!(page.next_start_after, None);
+            assert_eq!(
+                page.positions
+                    .iter()
+                    .map(|position| (position.campaign_key, position.campaign_addr.to_string()))
+                    .collect::<Vec<_>>(),
+                vec![(3, campaigns[2].clone())]
+            );
+
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaigns[0].clone()),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: claimable_reward,
+                    owner: None,
+                },
+                &[],
+            )
+            .unwrap();
+            let global_stats = |app: &App| -> GlobalStats {
+                app.wrap()
+                    .query_wasm_smart(
+                        factory_contract.clone(),
+                        &FactoryQueryMsg::GlobalStats {
+                            start_after: None,
+                            limit: None,
+                        },
+                    )
+                    .unwrap()
+            };
+            let rewards_distributed = vec![AssetToken {
+                info: TokenInfo::Token {
+                    contract_addr: token_contract,
+                },
+                amount: claimable_reward,
+            }];
+            assert_eq!(
+                global_stats(&app),
+                GlobalStats {
+                    total_campaigns: 3,
+                    total_nft_staked: 2,
+                    rewards_distributed: rewards_distributed.clone(),
+                }
+            );
+
+            // unstaked nft is no longer counted
+            increase_time(&mut app, 5);
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaigns[2].clone()),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "2".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(
+                global_stats(&app),
+                GlobalStats {
+                    total_campaigns: 3,
+                    total_nft_staked: 1,
+                    rewards_distributed,
+                }
+            );
+        }
+
+        // - ADMIN creates 2 campaigns and USER_1 stakes token_id 1 in campaign 1
+        // 	- after 5s, USER_1 claims from both campaigns through the factory
+        // 	- claim from campaign 1 succeeds and claim from campaign 2 fails alone
+        #[test]
+        fn claim_many() {
+            let (mut app, contracts) = instantiate_contracts();
+            let factory_contract = contracts[0].contract_addr.clone();
+            let token_contract = contracts[1].contract_addr.clone();
+            let collection_contract = contracts[2].contract_addr.clone();
+            let lockup_term = vec![LockupTerm {
+                value: 10,
+                percent: Uint128::new(100u128),
+                max_nfts: None,
+                max_reward: None,
+            }];
+            let campaign_1 = create_campaign(&mut app, &contracts, lockup_term.clone());
+            create_active_campaign(&mut app, &contracts, lockup_term, 1_000_000);
+            fund_campaign(&mut app, &contracts, &campaign_1, 1_000_000);
+            mint_nfts(&mut app, &collection_contract, USER_1, &campaign_1, &[1]);
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(campaign_1.clone()),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 10,
+                        weight_proof: None,
+                    }],
+                    bonus: None,
+                },
+                &[],
+            )
+            .unwrap();
+            increase_time(&mut app, 5);
+
+            let claim_many = |app: &mut App, campaign_ids: Vec<u64>| {
+                app.execute_contract(
+                    Addr::unchecked(USER_1),
+                    Addr::unchecked(factory_contract.clone()),
+                    &FactoryExecuteMsg::ClaimMany { campaign_ids },
+                    &[],
+                )
+            };
+            assert_eq!(
+                claim_many(&mut app, vec![1, 1])
+                    .unwrap_err()
+                    .root_cause()
+                    .to_string(),
+                "## Claim from 1 to 30 distinct campaigns ##"
+            );
+
+            let claimable: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    campaign_1.clone(),
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_1),
+                    },
+                )
+                .unwrap();
+            let response = claim_many(&mut app, vec![1, 2]).unwrap();
+            let results = response
+                .events
+                .iter()
+                .filter(|event| event.ty == "wasm-stakify-factory-claim-result")
+                .map(|event| {
+                    event
+                        .attributes
+                        .iter()
+                        .filter(|attr| attr.key == "campaign_id" || attr.key == "success")
+                        .map(|attr| attr.value.clone())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(results, vec![vec!["1", "true"], vec!["2", "false"]]);
+
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract,
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: USER_1.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(balance.balance, claimable.reward_debt);
+        }
+    }
 }
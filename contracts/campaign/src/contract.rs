@@ -1,29 +1,62 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
+use std::cmp::min;
+use std::collections::BTreeMap;
+
 use cosmwasm_std::{
-    has_coins, to_binary, Addr, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order,
-    QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
+    from_binary, has_coins, to_binary, Addr, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env,
+    Event, MessageInfo, Order, QueryRequest, Response, StdError, StdResult, Storage, Uint128,
+    Uint256, WasmMsg, WasmQuery,
 };
 use cw2::set_contract_version;
+use cw_storage_plus::Bound;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::events::{
+    add_reward_event, approve_all_event, claim_event, instantiate_event,
+    register_merkle_roots_event, revoke_all_event, set_receipt_collection_event,
+    set_reward_recipient_event, settle_event, slash_event, stake_event, stake_tokens_event,
+    transfer_position_event, unstake_event, update_campaign_event, update_eligibility_event,
+    update_list_event, update_set_bonuses_event, update_staking_rules_event, withdraw_nft_event,
+    withdraw_reward_event,
+};
+use crate::msg::{
+    Cw20HookMsg, ExecuteMsg, FactoryCallbackMsg, InstantiateMsg, QueryMsg, ReceiptHookMsg,
+};
 use crate::state::{
-    AssetToken, CampaignInfo, CampaignInfoResult, CampaignInfoUpdate, NftInfo, NftStake,
-    StakedInfoResult, StakerRewardAssetInfo, TokenInfo, CAMPAIGN_INFO, NFTS, STAKERS_INFO,
-    TOKEN_IDS,
+    staked_nfts, unbonding_nfts, AssetToken, CampaignInfo, CampaignInfoResult, CampaignInfoUpdate,
+    EligibilityConfig, EligibilityResult, LedgerAction, LedgerEntry, ListKind, LockupTerm,
+    MerkleRoots, Metadata, NftDashboard, NftInfo, NftStake, RewardSnapshot, SetBonus, SetRule,
+    SimulateClaimResult, SimulateStakeResult, SimulateUnstakeResult, StakedInfoResult,
+    StakerBonusProof, StakerDashboardResult, StakerInfoResult, StakerRewardAssetInfo, StakingRules,
+    TermFill, TermNftCount, TermPolicy, TermState, TokenDeposit, TokenEligibility, TokenIdRange,
+    TokenInfo, Trait, UnbondingNft, ALLOWED_STAKERS, ALLOWED_TOKEN_IDS, CAMPAIGN_INFO,
+    DENIED_STAKERS, DENIED_TOKEN_IDS, ELIGIBILITY_CONFIG, EXCLUSIVITY_RULES, FACTORY,
+    LAST_UNSTAKE_TIME, MAX_TIME_VALID, MERKLE_ROOTS, NFT_BASE_WEIGHTS, NFT_EXPIRATIONS,
+    NFT_REWARD_INDEXES, NFT_TRAITS, OPERATORS, RECEIPT_COLLECTION, REWARD_HISTORY,
+    REWARD_RECIPIENTS, SET_BONUSES, SET_MULTIPLIERS, SOFT_STAKING, STAKERS_INFO, STAKER_LEDGER,
+    STAKER_LEDGER_COUNT, STAKING_RULES, TERM_NFT_COUNTS, TERM_POLICY, TERM_REWARDS, TERM_STATES,
+    TOKEN_DEPOSITS, TOKEN_DEPOSIT_COUNT, TOKEN_STAKING, TOTAL_NFT_STAKED,
+};
+use crate::utils::{
+    add_reward, calc_index_reward, calc_set_multiplier, calc_weighted_reward_in_time, sub_reward,
+    verify_merkle_proof, BASE_WEIGHT,
+};
+use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw721::{
+    Approval, Cw721ExecuteMsg, Cw721QueryMsg, Cw721ReceiveMsg, Expiration, NftInfoResponse,
+    OperatorsResponse,
 };
-use crate::utils::{add_reward, calc_reward_in_time, sub_reward};
-use cw20::{Cw20ExecuteMsg, Cw20QueryMsg};
-use cw721::{Cw721ExecuteMsg, Cw721QueryMsg};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:campaign";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
-const MAX_TIME_VALID: u64 = 94608000; // 3 years
 const MAX_LENGTH_NAME: usize = 100;
 const MAX_LENGTH_IMAGE: usize = 500;
 const MAX_LENGTH_DESCRIPTION: usize = 500;
+// pagination of list queries
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -76,13 +109,32 @@ pub fn instantiate(
         });
     }
 
-    // // TODO: lockup_term must be 15days, 30days, 60days
-    // let lockup_term = &msg.lockup_term;
-    // for term in lockup_term {
-    //     if Term::from_value(&term.value).is_none() {
-    //         return Err(ContractError::InvalidLockupTerm {});
-    //     }
-    // }
+    // lockup terms must follow term policy of factory
+    let term_policy = msg.term_policy.unwrap_or_default();
+    if !term_policy.is_valid() {
+        return Err(ContractError::InvalidTermPolicy {});
+    }
+    validate_lockup_terms(&term_policy, &msg.lockup_term)?;
+
+    // staked token must be a valid cw20 address and deposits must have a weight
+    if let Some(token_staking) = &msg.token_staking {
+        deps.api
+            .addr_validate(token_staking.contract_addr.as_str())?;
+        if token_staking.amount_per_nft.is_zero() {
+            return Err(ContractError::InvalidTokenStaking {});
+        }
+        TOKEN_STAKING.save(deps.storage, token_staking)?;
+    }
+
+    // staked nfts are registered in factory, exclusivity rules are checked there
+    if let Some(factory) = &msg.factory {
+        FACTORY.save(deps.storage, &deps.api.addr_validate(factory)?)?;
+    }
+    if let Some(exclusivity) = &msg.exclusivity {
+        EXCLUSIVITY_RULES.save(deps.storage, exclusivity)?;
+    }
+
+    SOFT_STAKING.save(deps.storage, &msg.soft_staking.unwrap_or_default())?;
 
     // campaign info
     let campaign = CampaignInfo {
@@ -108,29 +160,15 @@ pub fn instantiate(
     // save campaign info
     CAMPAIGN_INFO.save(deps.storage, &campaign)?;
 
-    // init TOKEN_IDS to vec![]
-    TOKEN_IDS.save(deps.storage, &vec![])?;
+    // init TOTAL_NFT_STAKED to 0
+    TOTAL_NFT_STAKED.save(deps.storage, &0u64)?;
 
-    // we need emit the information of reward token to response
-    let reward_token_info_str = match msg.reward_token_info.info {
-        TokenInfo::Token { contract_addr } => contract_addr,
-        TokenInfo::NativeToken { denom } => denom,
-    };
+    TERM_POLICY.save(deps.storage, &term_policy)?;
 
     // emit the information of instantiated campaign
-    Ok(Response::new().add_attributes([
-        ("action", "instantiate"),
-        ("owner", &msg.owner),
-        ("campaign_name", &msg.campaign_name),
-        ("campaign_image", &msg.campaign_image),
-        ("campaign_description", &msg.campaign_description),
-        ("limit_per_staker", &msg.limit_per_staker.to_string()),
-        ("reward_token_info", &reward_token_info_str),
-        ("allowed_collection", &msg.allowed_collection),
-        ("lockup_term", &format!("{:?}", &msg.lockup_term)),
-        ("start_time", &msg.start_time.to_string()),
-        ("end_time", &msg.end_time.to_string()),
-    ]))
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_event(instantiate_event(&campaign)))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -142,13 +180,60 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::AddRewardToken { amount } => execute_add_reward_token(deps, env, info, amount),
-        ExecuteMsg::StakeNfts { nfts } => execute_stake_nft(deps, env, info, nfts),
+        ExecuteMsg::StakeNfts { nfts, bonus } => execute_stake_nft(deps, env, info, nfts, bonus),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
         ExecuteMsg::UnStakeNft { token_id } => execute_unstake_nft(deps, env, info, token_id),
-        ExecuteMsg::ClaimReward { amount } => execute_claim_reward(deps, env, info, amount),
+        ExecuteMsg::ClaimReward { amount, owner } => {
+            execute_claim_reward(deps, env, info, amount, owner)
+        }
         ExecuteMsg::WithdrawReward {} => execute_withdraw_reward(deps, env, info),
         ExecuteMsg::UpdateCampaign {
             campaign_info_update,
         } => execute_update_campaign(deps, env, info, campaign_info_update),
+        ExecuteMsg::SetRewardRecipient { recipient } => {
+            execute_set_reward_recipient(deps, info, recipient)
+        }
+        ExecuteMsg::ApproveAll { operator, expires } => {
+            execute_approve_all(deps, env, info, operator, expires)
+        }
+        ExecuteMsg::RevokeAll { operator } => execute_revoke_all(deps, info, operator),
+        ExecuteMsg::UpdateSetBonuses { set_bonuses } => {
+            execute_update_set_bonuses(deps, info, set_bonuses)
+        }
+        ExecuteMsg::UpdateStakingRules { staking_rules } => {
+            execute_update_staking_rules(deps, info, staking_rules)
+        }
+        ExecuteMsg::WithdrawNft { token_id } => execute_withdraw_nft(deps, env, info, token_id),
+        ExecuteMsg::UpdateEligibility {
+            token_allowlist_enabled,
+            staker_allowlist_enabled,
+            allowed_token_ranges,
+            denied_token_ranges,
+        } => execute_update_eligibility(
+            deps,
+            info,
+            token_allowlist_enabled,
+            staker_allowlist_enabled,
+            allowed_token_ranges,
+            denied_token_ranges,
+        ),
+        ExecuteMsg::UpdateTokenIdList { list, add, remove } => {
+            execute_update_token_id_list(deps, info, list, add, remove)
+        }
+        ExecuteMsg::UpdateStakerList { list, add, remove } => {
+            execute_update_staker_list(deps, info, list, add, remove)
+        }
+        ExecuteMsg::RegisterMerkleRoots {
+            staker_bonus_root,
+            token_weight_root,
+        } => execute_register_merkle_roots(deps, info, staker_bonus_root, token_weight_root),
+        ExecuteMsg::Settle { limit } => execute_settle(deps, env, limit),
+        ExecuteMsg::ClaimFor { owner } => execute_claim_for(deps, env, info, owner),
+        ExecuteMsg::Slash { token_id } => execute_slash(deps, env, info, token_id),
+        ExecuteMsg::SetReceiptCollection { receipt_collection } => {
+            execute_set_receipt_collection(deps, info, receipt_collection)
+        }
+        ExecuteMsg::ReceiveNft(msg) => execute_receive_nft(deps, env, info, msg),
     }
 }
 
@@ -201,9 +286,6 @@ pub fn execute_add_reward_token(
                 funds: vec![],
             }));
 
-            // add token info to response
-            res = res.add_attribute("reward_token_info", contract_addr);
-
             // update amount, reward_per_second token in campaign
             campaign_info.reward_token.amount = campaign_info
                 .reward_token
@@ -221,6 +303,7 @@ pub fn execute_add_reward_token(
 
             // save campaign
             CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
+            save_reward_snapshot(deps.storage, &campaign_info, current_time, Uint128::zero())?;
         }
         TokenInfo::NativeToken { denom } => {
             // check the amount of native token in funds
@@ -233,16 +316,11 @@ pub fn execute_add_reward_token(
             ) {
                 return Err(ContractError::InvalidFunds {});
             }
-
-            // add token info to response
-            res = res.add_attribute("reward_token_info", &denom);
         }
     }
-    Ok(res.add_attributes([
-        ("action", "add_reward_token"),
-        ("owner", campaign_info.owner.as_ref()),
-        ("reward_token_amount", &amount.to_string()),
-    ]))
+    Ok(res
+        .add_attribute("action", "add_reward_token")
+        .add_event(add_reward_event(&campaign_info, amount)))
 }
 
 pub fn execute_stake_nft(
@@ -250,6 +328,7 @@ pub fn execute_stake_nft(
     env: Env,
     info: MessageInfo,
     nfts: Vec<NftStake>,
+    bonus: Option<StakerBonusProof>,
 ) -> Result<Response, ContractError> {
     // load campaign info
     let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
@@ -266,20 +345,56 @@ pub fn execute_stake_nft(
         return Err(ContractError::InvalidTimeToStakeNft {});
     }
 
+    // at least one nft must be staked
+    if nfts.is_empty() {
+        return Err(ContractError::EmptyNfts {});
+    }
+
+    // a token_id is staked once, soft staked nft of previous owner must be slashed before
+    for (i, nft) in nfts.iter().enumerate() {
+        // position of a token deposit cannot be staked as nft
+        if TOKEN_DEPOSITS.has(deps.storage, nft.token_id.clone()) {
+            return Err(ContractError::ReservedTokenId {
+                token_id: nft.token_id.clone(),
+            });
+        }
+        if nfts[..i].iter().any(|n| n.token_id == nft.token_id)
+            || staked_nfts().has(deps.storage, nft.token_id.clone())
+        {
+            return Err(ContractError::DuplicateTokenId {
+                token_id: nft.token_id.clone(),
+            });
+        }
+    }
+
+    let staking_rules = STAKING_RULES.may_load(deps.storage)?.unwrap_or_default();
+
+    // staker must not be denied and must be allowed if staker allowlist is enabled
+    let eligibility_config = ELIGIBILITY_CONFIG
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if !is_eligible_staker(deps.storage, &eligibility_config, &info.sender)? {
+        return Err(ContractError::IneligibleStaker {});
+    }
+
+    // bonus multiplier of staker is proven by merkle proof if staker bonus root is registered
+    let merkle_roots = MERKLE_ROOTS.may_load(deps.storage)?.unwrap_or_default();
+    let bonus_multiplier = staker_bonus_multiplier(&merkle_roots, &info.sender, bonus)?;
+
     // load staker_info or default if staker has not staked nft
-    let mut staker_info = STAKERS_INFO
+    let staker_info = STAKERS_INFO
         .may_load(deps.storage, info.sender.clone())?
         .unwrap_or(StakerRewardAssetInfo {
-            token_ids: vec![],
             reward_debt: Uint128::zero(),
             reward_claimed: Uint128::zero(),
         });
 
     // if limit per staker > 0 then check amount nft staked
     // if limit_per_staker = 0, then no limit nft stake
+    let mut set_token_ids = load_staker_token_ids(deps.storage, &info.sender)?;
     if campaign_info.limit_per_staker > 0 {
-        // the length of token_ids + length nft staked should be smaller than limit per staker
-        if nfts.len() + staker_info.token_ids.len() > campaign_info.limit_per_staker as usize {
+        // the number of nft staked + length nft staked should be smaller than limit per staker
+        if nfts.len() + set_token_ids.len() > campaign_info.limit_per_staker as usize {
             return Err(ContractError::LimitPerStake {});
         }
     }
@@ -287,83 +402,26 @@ pub fn execute_stake_nft(
     // prepare response
     let mut res = Response::new();
 
-    // list token_ids
-    let mut token_ids = TOKEN_IDS.load(deps.storage)?;
-
     // if nft is first stake then skip calc reward
+    let mut accrued_reward = Uint128::zero();
     if campaign_info.time_calc_nft != 0 {
         // update pending reward for previous staking nft
-        let terms = campaign_info.clone().lockup_term;
-        let time_calc_nft = campaign_info.time_calc_nft;
-        let reward_per_second = campaign_info.reward_per_second;
-
-        // load nfts
-        let mut nfts_load = Vec::new();
-        let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
-        for item in nfts_storage {
-            let (_, nft_info) = item?;
-
-            // skip nft is end reward
-            if !nft_info.is_end_reward {
-                nfts_load.push(nft_info);
-            }
-        }
+        (_, accrued_reward) = save_pending_reward(deps.storage, &campaign_info, current_time)?;
+    }
 
-        // filter nft by term
-        for term in terms {
-            let mut nft_list = nfts_load
-                .clone()
-                .into_iter()
-                .filter(|nft| nft.lockup_term.value == term.value)
-                .collect::<Vec<_>>();
-            nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
-
-            let mut time_calc: u64 = time_calc_nft;
-            let mut nft_count = nft_list.len() as u128;
-            let mut reward = Uint128::zero();
-            for nft in nft_list.iter_mut() {
-                if nft.end_time <= current_time {
-                    // calc in time_calc -> nft.end_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        nft.end_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // increase reward for next nft
-                    reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                    nft_count -= 1; // update count nft for next calc reward
-                    time_calc = nft.end_time; // update time_calc
-                    nft.is_end_reward = true; // nft stake timeout
-                } else {
-                    // calc in time_calc -> current_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        current_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // add reward previous and current reward
-                    let accumulate_reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, accumulate_reward).unwrap();
-                }
-                // save nfts
-                NFTS.save(deps.storage, nft.token_id.clone(), nft)?;
-            }
+    // set bonus of staker with the nfts being staked, their traits are saved once
+    if has_trait_set(deps.storage)? {
+        for nft in nfts.iter() {
+            let traits = query_nft_traits(deps.as_ref(), &campaign_info, &nft.token_id);
+            NFT_TRAITS.save(deps.storage, nft.token_id.clone(), &traits)?;
         }
     }
+    set_token_ids.extend(nfts.iter().map(|nft| nft.token_id.clone()));
+    let set_multiplier = staker_set_multiplier(deps.storage, &set_token_ids)?;
+
+    // soft staked nfts stay in wallet of staker
+    let soft_staking = SOFT_STAKING.may_load(deps.storage)?.unwrap_or_default();
+    let receipt_collection = RECEIPT_COLLECTION.may_load(deps.storage)?;
 
     // check the owner of token_ids, all token_ids should be owned by info.sender
     for nft in &nfts {
@@ -378,6 +436,26 @@ pub fn execute_stake_nft(
             return Err(ContractError::InvalidLockupTerm {});
         }
 
+        if !is_eligible_token_id(deps.storage, &eligibility_config, &nft.token_id)? {
+            return Err(ContractError::IneligibleNft {
+                token_id: nft.token_id.clone(),
+            });
+        }
+        let weight = nft_weight(&merkle_roots, nft, bonus_multiplier)?;
+
+        // token_id cannot be staked again during re-stake cooldown
+        if let Some(unstake_time) =
+            LAST_UNSTAKE_TIME.may_load(deps.storage, nft.token_id.clone())?
+        {
+            let restake_time = unstake_time + staking_rules.restake_cooldown;
+            if current_time < restake_time {
+                return Err(ContractError::RestakeCooldown {
+                    token_id: nft.token_id.clone(),
+                    restake_time,
+                });
+            }
+        }
+
         // check owner of nft
         let query_owner_msg = Cw721QueryMsg::OwnerOf {
             token_id: nft.token_id.clone(),
@@ -421,6 +499,11 @@ pub fn execute_stake_nft(
             .find(|&term| term.value == nft.lockup_term)
             .cloned()
             .unwrap();
+        if is_term_full(deps.storage, &lockup_term)? {
+            return Err(ContractError::LockupTermFull {
+                lockup_term: lockup_term.value,
+            });
+        }
 
         let nft_info = NftInfo {
             token_id: nft.token_id.clone(),
@@ -429,200 +512,347 @@ pub fn execute_stake_nft(
             lockup_term: lockup_term.clone(),
             is_end_reward: false,
             start_time: current_time,
-            end_time: (current_time + lockup_term.value),
+            end_time: nft_end_time(&campaign_info, &lockup_term, current_time),
+            weight: set_bonus_weight(weight, set_multiplier)?,
         };
         // save info nft
-        NFTS.save(deps.storage, nft.token_id.clone(), &nft_info)?;
-
-        // save staker_info
-        staker_info.token_ids.push(nft.token_id.clone());
+        staked_nfts().save(deps.storage, nft.token_id.clone(), &nft_info)?;
+        update_term_nft_count(deps.storage, lockup_term.value, true)?;
+        NFT_BASE_WEIGHTS.save(deps.storage, nft.token_id.clone(), &weight)?;
+        add_earning_nft(deps.storage, &campaign_info, &nft_info)?;
 
-        token_ids.push(nft.token_id.clone());
-
-        res = res.add_message(transfer_nft_msg);
+        if !soft_staking {
+            res = res.add_message(transfer_nft_msg);
+        }
+        if let Some(receipt_collection) = &receipt_collection {
+            res = res.add_message(mint_receipt(receipt_collection, &nft_info)?);
+        }
+        res = res.add_event(stake_event(&nft_info));
     }
 
     STAKERS_INFO.save(deps.storage, info.sender.clone(), &staker_info)?;
+    save_set_multiplier(deps.storage, &info.sender, set_multiplier)?;
+
+    // register staked nfts in factory
+    if let Some(msg) = factory_callback(
+        deps.storage,
+        FactoryCallbackMsg::RegisterNfts {
+            token_ids: nfts.iter().map(|nft| nft.token_id.clone()).collect(),
+        },
+    )? {
+        res = res.add_message(msg);
+    }
 
     // update time calc pending reward for nft
     let mut update_campaign = campaign_info.clone();
     update_campaign.time_calc_nft = current_time;
     CAMPAIGN_INFO.save(deps.storage, &update_campaign)?;
+    save_reward_snapshot(deps.storage, &update_campaign, current_time, accrued_reward)?;
 
-    // save TOKEN_IDS
-    TOKEN_IDS.save(deps.storage, &token_ids)?;
+    // update number of nft staked
+    TOTAL_NFT_STAKED.update(deps.storage, |total| -> StdResult<_> {
+        Ok(total + nfts.len() as u64)
+    })?;
 
-    Ok(res.add_attributes([
-        ("action", "stake_nft"),
-        ("owner", info.sender.as_ref()),
-        (
-            "allowed_collection",
-            campaign_info.allowed_collection.as_ref(),
-        ),
-        ("nfts", &format!("{:?}", &nfts)),
-    ]))
+    Ok(res.add_attribute("action", "stake_nft"))
 }
 
-pub fn execute_unstake_nft(
-    deps: DepsMut,
+// anyone can slash a soft staked nft which is no longer owned by its staker
+pub fn execute_slash(
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     token_id: String,
 ) -> Result<Response, ContractError> {
     // load campaign info
     let mut campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
-    // prepare response
-    let mut res = Response::new();
 
-    if NFTS.may_load(deps.storage, token_id.clone())?.is_none() {
-        return Err(ContractError::EmptyNft { token_id });
+    let nft_info = match staked_nfts().may_load(deps.storage, token_id.clone())? {
+        Some(nft_info) => nft_info,
+        None => return Err(ContractError::EmptyNft { token_id }),
+    };
+
+    // nfts held by contract and token deposits cannot be slashed
+    if !SOFT_STAKING.may_load(deps.storage)?.unwrap_or_default()
+        || TOKEN_DEPOSITS.has(deps.storage, token_id.clone())
+    {
+        return Err(ContractError::NotSoftStaked {});
+    }
+    if is_nft_owner(deps.as_ref(), &campaign_info, &nft_info) {
+        return Err(ContractError::NotSlashable { token_id });
     }
 
     // max time calc pending reward is campaign_info.end_time
-    let mut current_time = env.block.time.seconds();
-    if campaign_info.end_time < env.block.time.seconds() {
-        current_time = campaign_info.end_time;
-    }
+    let current_time = min(env.block.time.seconds(), campaign_info.end_time);
 
     // update pending reward for previous staking nft
-    let terms = campaign_info.clone().lockup_term;
-    let time_calc_nft = campaign_info.time_calc_nft;
-    let reward_per_second = campaign_info.reward_per_second;
+    let (_, accrued_reward) =
+        save_pending_reward(deps.storage, &campaign_info, env.block.time.seconds())?;
 
-    // load nfts
-    let mut nfts_load = Vec::new();
-    let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
-    for item in nfts_storage {
-        let (_, nft_info) = item?;
-        if !nft_info.is_end_reward {
-            nfts_load.push(nft_info);
-        }
-    }
+    // update time calc pending reward for nft
+    campaign_info.time_calc_nft = current_time;
+    CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
+    save_reward_snapshot(
+        deps.storage,
+        &campaign_info,
+        env.block.time.seconds(),
+        accrued_reward,
+    )?;
 
-    for term in terms {
-        let mut nft_list = nfts_load
-            .clone()
-            .into_iter()
-            .filter(|nft| nft.lockup_term.value == term.value)
-            .collect::<Vec<_>>();
-        nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
-
-        let mut time_calc: u64 = time_calc_nft;
-        let mut nft_count = nft_list.len() as u128;
-        let mut reward = Uint128::zero();
-        for nft in nft_list.iter_mut() {
-            if nft.end_time <= current_time {
-                // calc in time_calc -> nft.end_time
-                let calc_reward = calc_reward_in_time(
-                    time_calc,
-                    nft.end_time,
-                    reward_per_second,
-                    term.percent,
-                    nft_count,
-                )
-                .unwrap();
+    let nft_info = staked_nfts().load(deps.storage, token_id)?;
+    let res = slash_nft(deps.branch(), &env, &campaign_info, &nft_info, &info.sender)?;
 
-                // increase reward for next nft
-                reward = add_reward(reward, calc_reward).unwrap();
-
-                // update reward for nft
-                nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                nft_count -= 1; // update count nft for next calc reward
-                time_calc = nft.end_time; // update time_calc
-                nft.is_end_reward = true; // nft stake timeout
-            } else {
-                // calc in time_calc -> current_time
-                let calc_reward = calc_reward_in_time(
-                    time_calc,
-                    current_time,
-                    reward_per_second,
-                    term.percent,
-                    nft_count,
-                )
-                .unwrap();
+    Ok(res.add_attribute("action", "slash"))
+}
+
+// remove soft staked nft, its pending reward is forfeited and stays in campaign.
+// reward state must be saved before
+fn slash_nft(
+    deps: DepsMut,
+    env: &Env,
+    campaign_info: &CampaignInfo,
+    nft_info: &NftInfo,
+    slasher: &Addr,
+) -> Result<Response, ContractError> {
+    let mut res = Response::new();
 
-                // add reward previous and current reward
-                let accumulate_reward = add_reward(reward, calc_reward).unwrap();
+    let mut nft_info = nft_info.clone();
+    remove_earning_nft(deps.storage, campaign_info, &mut nft_info)?;
+    staked_nfts().remove(deps.storage, nft_info.token_id.clone())?;
+    update_term_nft_count(deps.storage, nft_info.lockup_term.value, false)?;
+
+    // forfeited reward is released from reward of lockup term
+    TERM_REWARDS.update(
+        deps.storage,
+        nft_info.lockup_term.value,
+        |reward| -> StdResult<_> {
+            Ok(reward
+                .unwrap_or_default()
+                .saturating_sub(nft_info.pending_reward))
+        },
+    )?;
+    NFT_BASE_WEIGHTS.remove(deps.storage, nft_info.token_id.clone());
+    TOTAL_NFT_STAKED.update(deps.storage, |total| -> StdResult<_> { Ok(total - 1) })?;
+
+    // set bonus of owner without the slashed nft
+    NFT_TRAITS.remove(deps.storage, nft_info.token_id.clone());
+    let set_token_ids = load_staker_token_ids(deps.storage, &nft_info.owner)?;
+    let set_multiplier = staker_set_multiplier(deps.storage, &set_token_ids)?;
+    save_set_multiplier(deps.storage, &nft_info.owner, set_multiplier)?;
+
+    append_ledger_entry(
+        deps.storage,
+        env,
+        &nft_info.owner,
+        LedgerAction::Penalty,
+        Some(nft_info.token_id.clone()),
+        nft_info.pending_reward,
+    )?;
+
+    if let Some(msg) = factory_callback(
+        deps.storage,
+        FactoryCallbackMsg::UnregisterNfts {
+            token_ids: vec![nft_info.token_id.clone()],
+        },
+    )? {
+        res = res.add_message(msg);
+    }
 
-                // update reward for nft
-                nft.pending_reward = add_reward(nft.pending_reward, accumulate_reward).unwrap();
-            }
-            if env.block.time.seconds() >= campaign_info.end_time {
-                nft.is_end_reward = true;
-            }
-            // save nfts
-            NFTS.save(deps.storage, nft.token_id.clone(), nft)?;
+    Ok(res.add_event(slash_event(&nft_info, slasher)))
+}
+
+// staker still owns the soft staked nft, a failed query means it is burned
+fn is_nft_owner(deps: Deps, campaign_info: &CampaignInfo, nft_info: &NftInfo) -> bool {
+    let owner_response: StdResult<cw721::OwnerOfResponse> = deps.querier.query_wasm_smart(
+        campaign_info.allowed_collection.to_string(),
+        &Cw721QueryMsg::OwnerOf {
+            token_id: nft_info.token_id.clone(),
+            include_expired: Some(false),
+        },
+    );
+    matches!(owner_response, Ok(owner) if owner.owner == nft_info.owner)
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&msg.msg)? {
+        Cw20HookMsg::StakeTokens { lockup_term, bonus } => {
+            let staker = deps.api.addr_validate(&msg.sender)?;
+            execute_stake_tokens(deps, env, info, staker, msg.amount, lockup_term, bonus)
         }
     }
+}
 
-    // update time calc pending reward for nft
-    campaign_info.time_calc_nft = current_time;
-    CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
+// tokens are staked as one position with weight proportional to the amount,
+// it earns like an nft of the same lockup term
+pub fn execute_stake_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    staker: Addr,
+    amount: Uint128,
+    lockup_term: u64,
+    bonus: Option<StakerBonusProof>,
+) -> Result<Response, ContractError> {
+    // load campaign info
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
 
-    // load nft info
-    let nft_info = NFTS.load(deps.storage, token_id.clone())?;
+    let current_time = env.block.time.seconds();
 
-    // check time unstake and owner nft
-    if !nft_info.is_end_reward {
-        return Err(ContractError::InvalidTimeToUnStake {});
+    // only the staked token of campaign can be received
+    let token_staking = match TOKEN_STAKING.may_load(deps.storage)? {
+        Some(token_staking) => token_staking,
+        None => return Err(ContractError::TokenStakingDisabled {}),
+    };
+    if info.sender != token_staking.contract_addr {
+        return Err(ContractError::InvalidToken {});
     }
 
-    // prepare message to transfer nft back to the owner
-    let transfer_nft_msg = WasmMsg::Execute {
-        contract_addr: campaign_info.allowed_collection.to_string(),
-        msg: to_binary(&Cw721ExecuteMsg::TransferNft {
-            recipient: info.sender.to_string(),
-            token_id: token_id.clone(),
-        })?,
-        funds: vec![],
+    // the reward token must be added to campaign before staking tokens
+    if campaign_info.reward_token.amount == Uint128::zero() {
+        return Err(ContractError::EmptyReward {});
+    }
+
+    if campaign_info.start_time >= current_time || campaign_info.end_time <= current_time {
+        return Err(ContractError::InvalidTimeToStakeNft {});
+    }
+
+    let eligibility_config = ELIGIBILITY_CONFIG
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if !is_eligible_staker(deps.storage, &eligibility_config, &staker)? {
+        return Err(ContractError::IneligibleStaker {});
+    }
+
+    // bonus multiplier of staker is proven by merkle proof if staker bonus root is registered
+    let merkle_roots = MERKLE_ROOTS.may_load(deps.storage)?.unwrap_or_default();
+    let bonus_multiplier = staker_bonus_multiplier(&merkle_roots, &staker, bonus)?;
+
+    let lockup_term = match campaign_info
+        .lockup_term
+        .iter()
+        .find(|&term| term.value == lockup_term)
+    {
+        Some(term) => term.clone(),
+        None => return Err(ContractError::InvalidLockupTerm {}),
     };
+    if is_term_full(deps.storage, &lockup_term)? {
+        return Err(ContractError::LockupTermFull {
+            lockup_term: lockup_term.value,
+        });
+    }
 
-    // remove nft in NFTS
-    NFTS.remove(deps.storage, token_id.clone());
+    // amount_per_nft tokens weigh as much as one nft, multiplied by bonus of staker
+    let weight = amount.multiply_ratio(bonus_multiplier, token_staking.amount_per_nft);
+    if weight.is_zero() || weight > Uint128::from(u64::MAX) {
+        return Err(ContractError::InvalidWeight {});
+    }
 
-    // remove token_id in TOKEN_IDS
-    let mut token_ids = TOKEN_IDS.load(deps.storage)?;
-    token_ids.retain(|id| *id != token_id.clone());
-    TOKEN_IDS.save(deps.storage, &token_ids)?;
+    // update pending reward for previous staking nft
+    let mut accrued_reward = Uint128::zero();
+    if campaign_info.time_calc_nft != 0 {
+        (_, accrued_reward) = save_pending_reward(deps.storage, &campaign_info, current_time)?;
+    }
 
-    // update reward for staker
-    let mut staker = STAKERS_INFO.load(deps.storage, info.sender.clone())?;
-    staker.reward_debt = add_reward(staker.reward_debt, nft_info.pending_reward).unwrap();
-    staker.token_ids.retain(|key| *key != token_id.clone()); // remove nft for staker
-    STAKERS_INFO.save(deps.storage, info.sender.clone(), &staker)?;
+    let staker_info = STAKERS_INFO
+        .may_load(deps.storage, staker.clone())?
+        .unwrap_or(StakerRewardAssetInfo {
+            reward_debt: Uint128::zero(),
+            reward_claimed: Uint128::zero(),
+        });
+    STAKERS_INFO.save(deps.storage, staker.clone(), &staker_info)?;
+
+    // position of the deposit must not be taken by a staked nft
+    let mut deposit_count = TOKEN_DEPOSIT_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let token_id = loop {
+        deposit_count += 1;
+        let token_id = format!("cw20:{}", deposit_count);
+        if !staked_nfts().has(deps.storage, token_id.clone())
+            && !unbonding_nfts().has(deps.storage, token_id.clone())
+        {
+            break token_id;
+        }
+    };
+    TOKEN_DEPOSIT_COUNT.save(deps.storage, &deposit_count)?;
+
+    let nft_info = NftInfo {
+        token_id: token_id.clone(),
+        owner: staker,
+        pending_reward: Uint128::zero(),
+        lockup_term: lockup_term.clone(),
+        is_end_reward: false,
+        start_time: current_time,
+        end_time: nft_end_time(&campaign_info, &lockup_term, current_time),
+        weight: weight.u128() as u64,
+    };
+    staked_nfts().save(deps.storage, token_id.clone(), &nft_info)?;
+    update_term_nft_count(deps.storage, lockup_term.value, true)?;
+    TOKEN_DEPOSITS.save(deps.storage, token_id, &amount)?;
+    add_earning_nft(deps.storage, &campaign_info, &nft_info)?;
 
-    res = res.add_message(transfer_nft_msg);
-    Ok(res.add_attributes([
-        ("action", "unstake_nft"),
-        ("owner", info.sender.as_ref()),
-        (
-            "allowed_collection",
-            campaign_info.allowed_collection.as_ref(),
-        ),
-        ("token_id", &token_id),
-    ]))
+    // update time calc pending reward for nft
+    let mut update_campaign = campaign_info;
+    update_campaign.time_calc_nft = current_time;
+    CAMPAIGN_INFO.save(deps.storage, &update_campaign)?;
+    save_reward_snapshot(deps.storage, &update_campaign, current_time, accrued_reward)?;
+
+    let mut res = Response::new();
+    if let Some(receipt_collection) = RECEIPT_COLLECTION.may_load(deps.storage)? {
+        res = res.add_message(mint_receipt(&receipt_collection, &nft_info)?);
+    }
+
+    Ok(res
+        .add_attribute("action", "stake_tokens")
+        .add_event(stake_tokens_event(
+            &nft_info,
+            &token_staking.contract_addr,
+            amount,
+        )))
 }
 
-pub fn execute_claim_reward(
+pub fn execute_unstake_nft(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    amount: Uint128,
+    token_id: String,
 ) -> Result<Response, ContractError> {
-    // load campaign info
-    let mut campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+    // only owner of nft or its operator can unstake, nft is always returned to the owner
+    match staked_nfts().may_load(deps.storage, token_id.clone())? {
+        None => return Err(ContractError::EmptyNft { token_id }),
+        Some(nft_info) => {
+            if !can_act_for(deps.storage, &env, &nft_info.owner, &info.sender)? {
+                return Err(ContractError::NotOwner { token_id });
+            }
+        }
+    }
 
-    // Only stakers could claim rewards in this campaign
-    if STAKERS_INFO
-        .may_load(deps.storage, info.sender.clone())?
-        .is_none()
-    {
-        return Err(ContractError::InvalidClaim {});
+    // position with receipt is unstaked by sending the receipt to campaign
+    if RECEIPT_COLLECTION.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::ReceiptRequired { token_id });
     }
 
-    // load staker_info
-    let mut staker_info = STAKERS_INFO.load(deps.storage, info.sender.clone())?;
+    unstake_nft(deps, env, &info.sender, token_id)
+}
+
+// unstake nft of its owner
+fn unstake_nft(
+    deps: DepsMut,
+    env: Env,
+    sender: &Addr,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    // load campaign info
+    let mut campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+    // prepare response
+    let mut res = Response::new();
+
+    let owner = staked_nfts().load(deps.storage, token_id.clone())?.owner;
 
     // max time calc pending reward is campaign_info.end_time
     let mut current_time = env.block.time.seconds();
@@ -631,107 +861,479 @@ pub fn execute_claim_reward(
     }
 
     // update pending reward for previous staking nft
-    let terms = campaign_info.clone().lockup_term;
-    let time_calc_nft = campaign_info.time_calc_nft;
-    let reward_per_second = campaign_info.reward_per_second;
+    let (_, accrued_reward) =
+        save_pending_reward(deps.storage, &campaign_info, env.block.time.seconds())?;
 
-    // load nfts
-    let mut nfts_load = Vec::new();
-    let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
-    for item in nfts_storage {
-        let (_, nft_info) = item?;
-        if !nft_info.is_end_reward {
-            nfts_load.push(nft_info);
-        }
+    // update time calc pending reward for nft
+    campaign_info.time_calc_nft = current_time;
+    CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
+
+    // load nft info
+    let mut nft_info = staked_nfts().load(deps.storage, token_id.clone())?;
+    let is_deposit = TOKEN_DEPOSITS.has(deps.storage, token_id.clone());
+
+    // soft staked nft no longer owned by staker is slashed, its reward is not settled
+    let soft_staked = !is_deposit && SOFT_STAKING.may_load(deps.storage)?.unwrap_or_default();
+    if soft_staked && !is_nft_owner(deps.as_ref(), &campaign_info, &nft_info) {
+        save_reward_snapshot(
+            deps.storage,
+            &campaign_info,
+            env.block.time.seconds(),
+            accrued_reward,
+        )?;
+        let slash_res = slash_nft(deps, &env, &campaign_info, &nft_info, sender)?;
+        return Ok(slash_res.add_attribute("action", "slash"));
     }
 
-    for term in terms {
-        let mut nft_list = nfts_load
-            .clone()
-            .into_iter()
-            .filter(|nft| nft.lockup_term.value == term.value)
-            .collect::<Vec<_>>();
-        nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
-
-        let mut time_calc: u64 = time_calc_nft;
-        let mut nft_count = nft_list.len() as u128;
-        let mut reward = Uint128::zero();
-        for nft in nft_list.iter_mut() {
-            if nft.end_time <= current_time {
-                // calc in time_calc -> nft.end_time
-                let calc_reward = calc_reward_in_time(
-                    time_calc,
-                    nft.end_time,
-                    reward_per_second,
-                    term.percent,
-                    nft_count,
-                )
-                .unwrap();
+    // check time unstake, nft of flexible term can be unstaked at any time
+    if !nft_info.is_end_reward && !nft_info.lockup_term.is_flexible() {
+        return Err(ContractError::InvalidTimeToUnStake {});
+    }
 
-                // increase reward for next nft
-                reward = add_reward(reward, calc_reward).unwrap();
-
-                // update reward for nft
-                nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                nft_count -= 1; // update count nft for next calc reward
-                time_calc = nft.end_time; // update time_calc
-                nft.is_end_reward = true; // nft stake timeout
-            } else {
-                // calc in time_calc -> current_time
-                let calc_reward = calc_reward_in_time(
-                    time_calc,
-                    current_time,
-                    reward_per_second,
-                    term.percent,
-                    nft_count,
-                )
-                .unwrap();
+    // nft must be staked at least min_hold_time
+    let staking_rules = STAKING_RULES.may_load(deps.storage)?.unwrap_or_default();
+    let unlock_time = nft_info.start_time + staking_rules.min_hold_time;
+    if env.block.time.seconds() < unlock_time {
+        return Err(ContractError::MinHoldTime { unlock_time });
+    }
+    LAST_UNSTAKE_TIME.save(deps.storage, token_id.clone(), &env.block.time.seconds())?;
+
+    // nft is held by contract during unbonding period, otherwise it is returned to the owner.
+    // soft staked nft is in wallet of owner and only unregistered
+    let mut release_time = env.block.time.seconds();
+    if !soft_staked && staking_rules.unbonding_period > 0 {
+        release_time += staking_rules.unbonding_period;
+        unbonding_nfts().save(
+            deps.storage,
+            token_id.clone(),
+            &UnbondingNft {
+                token_id: token_id.clone(),
+                owner: owner.clone(),
+                release_time,
+            },
+        )?;
+    } else if !soft_staked {
+        res = res.add_message(transfer_staked(
+            deps.storage,
+            &campaign_info,
+            &owner,
+            &token_id,
+        )?);
+    }
 
-                // add reward previous and current reward
-                let accumulate_reward = add_reward(reward, calc_reward).unwrap();
+    // remove nft in staked nfts
+    remove_earning_nft(deps.storage, &campaign_info, &mut nft_info)?;
+    staked_nfts().remove(deps.storage, token_id.clone())?;
+    update_term_nft_count(deps.storage, nft_info.lockup_term.value, false)?;
+    NFT_BASE_WEIGHTS.remove(deps.storage, token_id.clone());
+
+    // set bonus of owner without the unstaked nft
+    NFT_TRAITS.remove(deps.storage, token_id.clone());
+    let set_token_ids = load_staker_token_ids(deps.storage, &owner)?;
+    let set_multiplier = staker_set_multiplier(deps.storage, &set_token_ids)?;
+    save_set_multiplier(deps.storage, &owner, set_multiplier)?;
+
+    // update number of nft staked and unregister nft in factory
+    if !is_deposit {
+        TOTAL_NFT_STAKED.update(deps.storage, |total| -> StdResult<_> { Ok(total - 1) })?;
+        if let Some(msg) = factory_callback(
+            deps.storage,
+            FactoryCallbackMsg::UnregisterNfts {
+                token_ids: vec![token_id.clone()],
+            },
+        )? {
+            res = res.add_message(msg);
+        }
+    }
 
-                // update reward for nft
-                nft.pending_reward = add_reward(nft.pending_reward, accumulate_reward).unwrap();
-            }
+    // update reward for staker
+    let mut staker = STAKERS_INFO.load(deps.storage, owner.clone())?;
+    staker.reward_debt = add_reward(staker.reward_debt, nft_info.pending_reward).unwrap();
+    STAKERS_INFO.save(deps.storage, owner.clone(), &staker)?;
+    append_ledger_entry(
+        deps.storage,
+        &env,
+        &owner,
+        LedgerAction::Settlement,
+        Some(token_id),
+        nft_info.pending_reward,
+    )?;
+
+    save_reward_snapshot(
+        deps.storage,
+        &campaign_info,
+        env.block.time.seconds(),
+        accrued_reward,
+    )?;
+
+    Ok(res
+        .add_attribute("action", "unstake_nft")
+        .add_event(unstake_event(&nft_info, release_time)))
+}
 
-            // if campaign is timeout -> nft timeout
-            if env.block.time.seconds() >= campaign_info.end_time {
-                nft.is_end_reward = true;
-            }
-            // save nfts
-            NFTS.save(deps.storage, nft.token_id.clone(), nft)?;
-        }
+// Only factory can execute it once, receipts are minted for positions staked afterwards
+pub fn execute_set_receipt_collection(
+    deps: DepsMut,
+    info: MessageInfo,
+    receipt_collection: String,
+) -> Result<Response, ContractError> {
+    if FACTORY.may_load(deps.storage)? != Some(info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if RECEIPT_COLLECTION.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::AlreadyExist {});
+    }
+    if SOFT_STAKING.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::InvalidReceipt {});
     }
 
-    // update time calc pending reward for nft
-    campaign_info.time_calc_nft = current_time;
+    let receipt_collection = deps.api.addr_validate(&receipt_collection)?;
+    RECEIPT_COLLECTION.save(deps.storage, &receipt_collection)?;
 
-    // transfer pending reward in nft to staker
-    for id in staker_info.token_ids.iter() {
-        let mut nft = NFTS.load(deps.storage, id.clone())?;
-        staker_info.reward_debt = add_reward(staker_info.reward_debt, nft.pending_reward).unwrap();
+    Ok(Response::new()
+        .add_attribute("action", "set_receipt_collection")
+        .add_event(set_receipt_collection_event(&receipt_collection)))
+}
 
-        //update pending reward for nft = 0 because pending reward in nft are transferred to staker
-        nft.pending_reward = Uint128::zero();
-        NFTS.save(deps.storage, id.clone(), &nft)?;
+// holder of receipt unstakes the position and the received receipt is burned,
+// or moves the position to recipient and the receipt is sent to recipient
+pub fn execute_receive_nft(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // only receipts of campaign can be received
+    let receipt_collection = match RECEIPT_COLLECTION.may_load(deps.storage)? {
+        Some(receipt_collection) if receipt_collection == info.sender => receipt_collection,
+        _ => return Err(ContractError::Unauthorized {}),
+    };
+
+    let holder = deps.api.addr_validate(&msg.sender)?;
+    let token_id = msg.token_id;
+    if !staked_nfts().has(deps.storage, token_id.clone()) {
+        return Err(ContractError::EmptyNft { token_id });
     }
+    let hook_msg: ReceiptHookMsg = from_binary(&msg.msg)?;
+    let new_owner = match &hook_msg {
+        ReceiptHookMsg::Unstake {} | ReceiptHookMsg::Claim {} => holder,
+        ReceiptHookMsg::Transfer { recipient } => deps.api.addr_validate(recipient)?,
+    };
 
-    // amount reward claim must be less than or equal reward in staker
-    if amount > staker_info.reward_debt {
-        return Err(ContractError::InsufficientBalance {});
+    // pending reward is saved before the position is moved to its new owner
+    let mut campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+    let (_, accrued_reward) =
+        save_pending_reward(deps.storage, &campaign_info, env.block.time.seconds())?;
+    campaign_info.time_calc_nft = min(env.block.time.seconds(), campaign_info.end_time);
+    CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
+    save_reward_snapshot(
+        deps.storage,
+        &campaign_info,
+        env.block.time.seconds(),
+        accrued_reward,
+    )?;
+
+    let nft_info = staked_nfts().load(deps.storage, token_id.clone())?;
+    let mut events = vec![];
+    if nft_info.owner != new_owner {
+        events.push(move_position(deps.branch(), nft_info, &new_owner)?);
     }
 
-    match campaign_info.reward_token.info.clone() {
-        TokenInfo::Token { contract_addr } => {
-            // check balance
-            let query_balance_msg = Cw20QueryMsg::Balance {
-                address: env.contract.address.to_string(),
+    match hook_msg {
+        ReceiptHookMsg::Unstake {} => {
+            let burn_receipt_msg = WasmMsg::Execute {
+                contract_addr: receipt_collection.to_string(),
+                msg: to_binary(&cw721_base::ExecuteMsg::<Option<Empty>, Empty>::Burn {
+                    token_id: token_id.clone(),
+                })?,
+                funds: vec![],
             };
-            let balance_response: StdResult<cw20::BalanceResponse> =
-                deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-                    contract_addr: contract_addr.to_string(),
-                    msg: to_binary(&query_balance_msg)?,
+            Ok(unstake_nft(deps, env, &new_owner, token_id)?
+                .add_message(burn_receipt_msg)
+                .add_events(events))
+        }
+        ReceiptHookMsg::Transfer { .. } => {
+            let transfer_receipt_msg = WasmMsg::Execute {
+                contract_addr: receipt_collection.to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                    recipient: new_owner.to_string(),
+                    token_id,
+                })?,
+                funds: vec![],
+            };
+            Ok(Response::new()
+                .add_message(transfer_receipt_msg)
+                .add_attribute("action", "transfer_position")
+                .add_events(events))
+        }
+        ReceiptHookMsg::Claim {} => {
+            let return_receipt_msg = WasmMsg::Execute {
+                contract_addr: receipt_collection.to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                    recipient: new_owner.to_string(),
+                    token_id,
+                })?,
+                funds: vec![],
+            };
+            Ok(claim_reward(deps, env, info, new_owner, None)?
+                .add_message(return_receipt_msg)
+                .add_events(events))
+        }
+    }
+}
+
+// message to mint receipt of position to its owner
+fn mint_receipt(receipt_collection: &Addr, nft_info: &NftInfo) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: receipt_collection.to_string(),
+        msg: to_binary(&cw721_base::ExecuteMsg::<Option<Empty>, Empty>::Mint(
+            cw721_base::MintMsg {
+                token_id: nft_info.token_id.clone(),
+                owner: nft_info.owner.to_string(),
+                token_uri: None,
+                extension: None,
+            },
+        ))?,
+        funds: vec![],
+    })
+}
+
+// move position with its pending reward to new owner, set bonuses of both owners are updated
+fn move_position(
+    deps: DepsMut,
+    mut nft_info: NftInfo,
+    new_owner: &Addr,
+) -> Result<Event, ContractError> {
+    let previous_owner = nft_info.owner.clone();
+    nft_info.owner = new_owner.clone();
+    staked_nfts().save(deps.storage, nft_info.token_id.clone(), &nft_info)?;
+
+    if !STAKERS_INFO.has(deps.storage, new_owner.clone()) {
+        STAKERS_INFO.save(
+            deps.storage,
+            new_owner.clone(),
+            &StakerRewardAssetInfo {
+                reward_debt: Uint128::zero(),
+                reward_claimed: Uint128::zero(),
+            },
+        )?;
+    }
+
+    for owner in [&previous_owner, new_owner] {
+        let set_token_ids = load_staker_token_ids(deps.storage, owner)?;
+        let set_multiplier = staker_set_multiplier(deps.storage, &set_token_ids)?;
+        save_set_multiplier(deps.storage, owner, set_multiplier)?;
+    }
+
+    Ok(transfer_position_event(
+        &nft_info.token_id,
+        &previous_owner,
+        new_owner,
+    ))
+}
+
+pub fn execute_claim_reward(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    owner: Option<String>,
+) -> Result<Response, ContractError> {
+    // operator can claim on behalf of owner
+    let staker = match owner {
+        Some(owner) => {
+            let owner = deps.api.addr_validate(&owner)?;
+            if !can_act_for(deps.storage, &env, &owner, &info.sender)? {
+                return Err(ContractError::Unauthorized {});
+            }
+            owner
+        }
+        None => info.sender.clone(),
+    };
+
+    claim_reward(deps, env, info, staker, Some(amount))
+}
+
+pub fn execute_claim_for(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+) -> Result<Response, ContractError> {
+    let owner = deps.api.addr_validate(&owner)?;
+
+    // anyone can claim for owner who registered a reward recipient, factory claims for any owner
+    if FACTORY.may_load(deps.storage)? != Some(info.sender.clone())
+        && REWARD_RECIPIENTS
+            .may_load(deps.storage, owner.clone())?
+            .is_none()
+    {
+        return Err(ContractError::EmptyRewardRecipient {});
+    }
+
+    claim_reward(deps, env, info, owner, None)
+}
+
+pub fn execute_settle(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    // load campaign info
+    let mut campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    if limit == 0 {
+        return Err(ContractError::InvalidLimit {});
+    }
+
+    // nothing is accrued before the first stake
+    if campaign_info.time_calc_nft == 0 {
+        return Ok(Response::new().add_attribute("action", "settle"));
+    }
+
+    // checkpoint moves to the time the limit-th nft stopped earning,
+    // remaining expirations are settled by the next call
+    let block_time = env.block.time.seconds();
+    let mut accrual = load_reward_accrual(deps.storage, &campaign_info)?;
+    accrue_reward(
+        deps.storage,
+        &campaign_info,
+        &mut accrual,
+        block_time,
+        Some(limit),
+    )?;
+    save_reward_accrual(deps.storage, &campaign_info, &accrual)?;
+
+    // update time calc pending reward for nft
+    campaign_info.time_calc_nft = accrual.time;
+    CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
+    save_reward_snapshot(
+        deps.storage,
+        &campaign_info,
+        block_time,
+        accrual.accrued_reward,
+    )?;
+
+    // expired nfts left for the next call, counted up to limit
+    let remaining = NFT_EXPIRATIONS
+        .keys_raw(
+            deps.storage,
+            None,
+            Some(expiration_bound(min(block_time, campaign_info.end_time))),
+            Order::Ascending,
+        )
+        .take(limit)
+        .count() as u64;
+
+    Ok(Response::new()
+        .add_attribute("action", "settle")
+        .add_event(settle_event(campaign_info.time_calc_nft, remaining)))
+}
+
+// transfer claimed reward of staker to its recipient, amount none claims all reward
+fn claim_reward(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    staker: Addr,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    // load campaign info
+    let mut campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+
+    // reward is paid to the recipient set by staker
+    let recipient = REWARD_RECIPIENTS
+        .may_load(deps.storage, staker.clone())?
+        .unwrap_or_else(|| staker.clone());
+
+    // max time calc pending reward is campaign_info.end_time
+    let mut current_time = env.block.time.seconds();
+    if campaign_info.end_time < env.block.time.seconds() {
+        current_time = campaign_info.end_time;
+    }
+
+    // update pending reward for previous staking nft
+    let (_, accrued_reward) =
+        save_pending_reward(deps.storage, &campaign_info, env.block.time.seconds())?;
+
+    // update time calc pending reward for nft
+    campaign_info.time_calc_nft = current_time;
+
+    let mut res = Response::new();
+
+    // Only stakers could claim rewards in this campaign
+    if STAKERS_INFO
+        .may_load(deps.storage, staker.clone())?
+        .is_none()
+    {
+        return Err(ContractError::InvalidClaim {});
+    }
+
+    // load staker_info
+    let mut staker_info = STAKERS_INFO.load(deps.storage, staker.clone())?;
+
+    // soft staked nfts no longer owned by staker are slashed, their reward is not claimed
+    let soft_staking = SOFT_STAKING.may_load(deps.storage)?.unwrap_or_default();
+
+    // transfer pending reward in nft to staker
+    for mut nft in load_staker_nfts(deps.storage, &staker)? {
+        if soft_staking
+            && !TOKEN_DEPOSITS.has(deps.storage, nft.token_id.clone())
+            && !is_nft_owner(deps.as_ref(), &campaign_info, &nft)
+        {
+            let slash_res = slash_nft(deps.branch(), &env, &campaign_info, &nft, &info.sender)?;
+            res = res
+                .add_submessages(slash_res.messages)
+                .add_events(slash_res.events);
+            continue;
+        }
+        save_nft_reward(deps.storage, &mut nft)?;
+        staker_info.reward_debt = add_reward(staker_info.reward_debt, nft.pending_reward).unwrap();
+
+        //update pending reward for nft = 0 because pending reward in nft are transferred to staker
+        nft.pending_reward = Uint128::zero();
+        staked_nfts().save(deps.storage, nft.token_id.clone(), &nft)?;
+    }
+
+    // amount reward claim must be less than or equal reward in staker,
+    // claiming all reward requires some reward
+    let amount = match amount {
+        Some(amount) => amount,
+        None if staker_info.reward_debt.is_zero() => {
+            return Err(ContractError::InsufficientBalance {})
+        }
+        None => staker_info.reward_debt,
+    };
+    if amount > staker_info.reward_debt {
+        return Err(ContractError::InsufficientBalance {});
+    }
+
+    append_ledger_entry(
+        deps.storage,
+        &env,
+        &staker,
+        LedgerAction::Claim,
+        None,
+        amount,
+    )?;
+
+    save_reward_snapshot(
+        deps.storage,
+        &campaign_info,
+        env.block.time.seconds(),
+        accrued_reward,
+    )?;
+
+    match campaign_info.reward_token.info.clone() {
+        TokenInfo::Token { contract_addr } => {
+            // check balance
+            let query_balance_msg = Cw20QueryMsg::Balance {
+                address: env.contract.address.to_string(),
+            };
+            let balance_response: StdResult<cw20::BalanceResponse> =
+                deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&query_balance_msg)?,
                 }));
             match balance_response {
                 Ok(balance) => {
@@ -748,7 +1350,7 @@ pub fn execute_claim_reward(
             let transfer_reward: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: contract_addr.to_string(),
                 msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                    recipient: info.sender.to_string(),
+                    recipient: recipient.to_string(),
                     amount,
                 })?,
                 funds: vec![],
@@ -757,7 +1359,7 @@ pub fn execute_claim_reward(
             // update staker info
             staker_info.reward_claimed = add_reward(staker_info.reward_claimed, amount).unwrap();
             staker_info.reward_debt = sub_reward(staker_info.reward_debt, amount).unwrap();
-            STAKERS_INFO.save(deps.storage, info.sender, &staker_info)?;
+            STAKERS_INFO.save(deps.storage, staker.clone(), &staker_info)?;
 
             // update reward total and reward claimed for campaign
             campaign_info.reward_token.amount =
@@ -768,14 +1370,22 @@ pub fn execute_claim_reward(
             // save campaign info
             CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
 
-            Ok(Response::new()
+            // factory sums reward distributed by its campaigns
+            if let Some(msg) =
+                factory_callback(deps.storage, FactoryCallbackMsg::RecordClaim { amount })?
+            {
+                res = res.add_message(msg);
+            }
+
+            Ok(res
                 .add_message(transfer_reward)
-                .add_attributes([
-                    ("action", "claim_reward"),
-                    ("owner", campaign_info.owner.as_ref()),
-                    ("reward_token_info", contract_addr.as_ref()),
-                    ("reward_claim_amount", &amount.to_string()),
-                ]))
+                .add_attribute("action", "claim_reward")
+                .add_event(claim_event(
+                    &staker,
+                    &recipient,
+                    &campaign_info.reward_token.info,
+                    amount,
+                )))
         }
         TokenInfo::NativeToken { denom } => {
             // check the amount of native token in funds
@@ -789,12 +1399,14 @@ pub fn execute_claim_reward(
                 return Err(ContractError::InvalidFunds {});
             }
 
-            Ok(Response::new().add_attributes([
-                ("action", "claim_reward"),
-                ("owner", campaign_info.owner.as_ref()),
-                ("denom", &denom),
-                ("reward_claim_amount", &amount.to_string()),
-            ]))
+            Ok(res
+                .add_attribute("action", "claim_reward")
+                .add_event(claim_event(
+                    &staker,
+                    &recipient,
+                    &campaign_info.reward_token.info,
+                    amount,
+                )))
         }
     }
 }
@@ -824,84 +1436,11 @@ pub fn execute_withdraw_reward(
     let current_time = campaign_info.end_time;
 
     // update pending reward for all nft
-    let terms = campaign_info.clone().lockup_term;
-    let time_calc_nft = campaign_info.time_calc_nft;
-    let reward_per_second = campaign_info.reward_per_second;
-
-    // load nfts
-    let mut nfts_load = Vec::new();
-    let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
-    for item in nfts_storage {
-        let (_, nft_info) = item?;
-        if !nft_info.is_end_reward {
-            nfts_load.push(nft_info);
-        }
-    }
-
-    for term in terms {
-        let mut nft_list = nfts_load
-            .clone()
-            .into_iter()
-            .filter(|nft| nft.lockup_term.value == term.value)
-            .collect::<Vec<_>>();
-        nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
-
-        let mut time_calc: u64 = time_calc_nft;
-        let mut nft_count = nft_list
-            .clone()
-            .into_iter()
-            .filter(|nft| !nft.is_end_reward)
-            .collect::<Vec<_>>()
-            .len() as u128;
-        let mut reward = Uint128::zero();
-        for nft in nft_list.iter_mut() {
-            if !nft.is_end_reward {
-                if nft.end_time <= current_time {
-                    // calc in time_calc -> nft.end_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        nft.end_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // increase reward for next nft
-                    reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                    nft_count -= 1; // update count nft for next calc reward
-                    time_calc = nft.end_time; // update time_calc
-                    nft.is_end_reward = true; // nft stake timeout
-                } else {
-                    // calc in time_calc -> current_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        current_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // add reward previous and current reward
-                    let accumulate_reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, accumulate_reward).unwrap()
-                }
-                nft.is_end_reward = true;
-            }
-
-            // pending reward in nft
-            total_pending_reward = add_reward(total_pending_reward, nft.pending_reward).unwrap();
-
-            // save nfts
-            NFTS.save(deps.storage, nft.token_id.clone(), nft)?;
-        }
+    let (nfts, accrued_reward) =
+        save_pending_reward(deps.storage, &campaign_info, env.block.time.seconds())?;
+    for nft in nfts {
+        // pending reward in nft
+        total_pending_reward = add_reward(total_pending_reward, nft.pending_reward)?;
     }
 
     // pending reward in staker
@@ -914,6 +1453,12 @@ pub fn execute_withdraw_reward(
     // update time calc pending reward for nft
     campaign_info.time_calc_nft = current_time;
     CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
+    save_reward_snapshot(
+        deps.storage,
+        &campaign_info,
+        env.block.time.seconds(),
+        accrued_reward,
+    )?;
 
     // reward remaining = reward in campaign - total pending reward
     let withdraw_reward = campaign_info
@@ -961,12 +1506,8 @@ pub fn execute_withdraw_reward(
 
             Ok(Response::new()
                 .add_message(transfer_reward)
-                .add_attributes([
-                    ("action", "withdraw_reward"),
-                    ("owner", campaign_info.owner.as_ref()),
-                    ("reward_token_info", contract_addr.as_ref()),
-                    ("withdraw_reward_amount", &withdraw_reward.to_string()),
-                ]))
+                .add_attribute("action", "withdraw_reward")
+                .add_event(withdraw_reward_event(&campaign_info, withdraw_reward)))
         }
         TokenInfo::NativeToken { denom } => {
             // check the amount of native token in funds
@@ -980,12 +1521,9 @@ pub fn execute_withdraw_reward(
                 return Err(ContractError::InvalidFunds {});
             }
 
-            Ok(Response::new().add_attributes([
-                ("action", "claim_reward"),
-                ("owner", campaign_info.owner.as_ref()),
-                ("denom", &denom),
-                ("reward_claim_amount", &withdraw_reward.to_string()),
-            ]))
+            Ok(Response::new()
+                .add_attribute("action", "withdraw_reward")
+                .add_event(withdraw_reward_event(&campaign_info, withdraw_reward)))
         }
     }
 }
@@ -1016,6 +1554,7 @@ pub fn execute_update_campaign(
     } else {
         campaign_info.start_time
     };
+    let previous_end_time = campaign_info.end_time;
     let update_end_time = if let Some(et) = campaign_info_update.end_time {
         et
     } else {
@@ -1050,6 +1589,10 @@ pub fn execute_update_campaign(
         campaign_info.lockup_term
     };
 
+    // lockup terms must follow term policy of factory
+    let term_policy = TERM_POLICY.may_load(deps.storage)?.unwrap_or_default();
+    validate_lockup_terms(&term_policy, &update_lockup_term)?;
+
     // campaign during max 3 years
     if (update_end_time - update_start_time) > MAX_TIME_VALID {
         return Err(ContractError::LimitStartDate {});
@@ -1098,366 +1641,1346 @@ pub fn execute_update_campaign(
         reward_per_second: campaign_info.reward_per_second,
     };
 
+    // factory counts active campaigns of creator by end time
+    let mut res = Response::new();
+    if campaign_info.end_time != previous_end_time {
+        if let Some(msg) = factory_callback(
+            deps.storage,
+            FactoryCallbackMsg::UpdateEndTime {
+                end_time: campaign_info.end_time,
+            },
+        )? {
+            res = res.add_message(msg);
+        }
+    }
+
     // save update campaign info
     CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
 
-    Ok(Response::new().add_attributes([
-        ("action", "update_campaign"),
-        ("owner", campaign_info.owner.as_ref()),
-        ("campaign_name", &campaign_info.campaign_name),
-        ("campaign_image", &campaign_info.campaign_image),
-        ("campaign_description", &campaign_info.campaign_description),
-        (
-            "limit_per_staker",
-            &campaign_info.limit_per_staker.to_string(),
-        ),
-        (
-            "reward_token_info",
-            &format!("{:?}", &campaign_info.reward_token),
-        ),
-        (
-            "allowed_collection",
-            campaign_info.allowed_collection.as_ref(),
-        ),
-        ("lockup_term", &format!("{:?}", &campaign_info.lockup_term)),
-        ("start_time", &update_start_time.to_string()),
-        ("end_time", &update_end_time.to_string()),
-    ]))
+    Ok(res
+        .add_attribute("action", "update_campaign")
+        .add_event(update_campaign_event(&campaign_info)))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
-    match msg {
-        QueryMsg::CampaignInfo {} => Ok(to_binary(&query_campaign_info(deps)?)?),
-        QueryMsg::NftInfo { token_id } => Ok(to_binary(&query_nft_info(deps, env, token_id)?)?),
-        QueryMsg::Nft { token_id } => Ok(to_binary(&query_nft(deps, env, token_id)?)?),
-        QueryMsg::NftStaked { owner } => Ok(to_binary(&query_staker_info(deps, env, owner)?)?),
-        QueryMsg::Nfts { limit } => Ok(to_binary(&query_nfts(deps, env, limit)?)?),
-        QueryMsg::TotalPendingReward {} => Ok(to_binary(&query_total_pending_reward(deps, env)?)?),
-        QueryMsg::TokenIds {} => Ok(to_binary(&query_token_ids(deps)?)?),
+pub fn execute_set_reward_recipient(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    // none resets the recipient to the staker
+    let recipient = match recipient {
+        Some(recipient) => {
+            let recipient = deps.api.addr_validate(&recipient)?;
+            REWARD_RECIPIENTS.save(deps.storage, info.sender.clone(), &recipient)?;
+            recipient
+        }
+        None => {
+            REWARD_RECIPIENTS.remove(deps.storage, info.sender.clone());
+            info.sender.clone()
+        }
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "set_reward_recipient")
+        .add_event(set_reward_recipient_event(&info.sender, &recipient)))
+}
+
+pub fn execute_approve_all(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operator: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    // reject expired operator
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidExpiration {});
     }
+
+    let operator = deps.api.addr_validate(&operator)?;
+    OPERATORS.save(
+        deps.storage,
+        (info.sender.clone(), operator.clone()),
+        &expires,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve_all")
+        .add_event(approve_all_event(&info.sender, &operator, &expires)))
 }
 
-fn query_campaign_info(deps: Deps) -> Result<CampaignInfoResult, ContractError> {
+pub fn execute_revoke_all(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+    OPERATORS.remove(deps.storage, (info.sender.clone(), operator.clone()));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_all")
+        .add_event(revoke_all_event(&info.sender, &operator)))
+}
+
+pub fn execute_update_staking_rules(
+    deps: DepsMut,
+    info: MessageInfo,
+    staking_rules: StakingRules,
+) -> Result<Response, ContractError> {
+    // load campaign info
     let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
 
-    let total_nft_staked = TOKEN_IDS.load(deps.storage)?.len() as u64;
+    // permission check
+    if info.sender != campaign_info.owner {
+        return Err(ContractError::Unauthorized {});
+    }
 
-    let campaign_result = CampaignInfoResult {
-        owner: campaign_info.owner,
-        campaign_name: campaign_info.campaign_name,
-        campaign_image: campaign_info.campaign_image,
-        campaign_description: campaign_info.campaign_description,
-        start_time: campaign_info.start_time,
-        end_time: campaign_info.end_time,
-        total_reward_claimed: campaign_info.total_reward_claimed,
-        total_reward: campaign_info.total_reward,
-        limit_per_staker: campaign_info.limit_per_staker,
-        reward_token_info: campaign_info.reward_token,
-        allowed_collection: campaign_info.allowed_collection,
-        lockup_term: campaign_info.lockup_term,
-        reward_per_second: campaign_info.reward_per_second,
-        time_calc_nft: campaign_info.time_calc_nft,
-        total_nft_staked,
-    };
-    Ok(campaign_result)
+    // like campaign info, rules cannot change once reward is added
+    if campaign_info.total_reward != Uint128::zero() {
+        return Err(ContractError::InvalidTimeToUpdate {});
+    }
+
+    STAKING_RULES.save(deps.storage, &staking_rules)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_staking_rules")
+        .add_event(update_staking_rules_event(&staking_rules)))
 }
 
-fn query_nft_info(deps: Deps, env: Env, token_id: String) -> Result<NftInfo, ContractError> {
-    let mut info: NftInfo = NFTS.load(deps.storage, token_id)?;
+pub fn execute_update_set_bonuses(
+    deps: DepsMut,
+    info: MessageInfo,
+    set_bonuses: Vec<SetBonus>,
+) -> Result<Response, ContractError> {
+    check_campaign_owner(deps.as_ref(), &info)?;
 
-    // if nft is active then calculate
-    if !info.is_end_reward {
-        // get time to calc pending reward
-        let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
-        let mut current_time = env.block.time.seconds();
-        if campaign_info.end_time < env.block.time.seconds() {
-            current_time = campaign_info.end_time;
-        }
-        // update pending reward for all nft
-        let terms = campaign_info.clone().lockup_term;
-        let time_calc_nft = campaign_info.time_calc_nft;
-        let reward_per_second = campaign_info.reward_per_second;
-
-        // load nfts
-        let mut nfts_load = Vec::new();
-        let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
-        for item in nfts_storage {
-            let (_, nft_info) = item?;
-            if !nft_info.is_end_reward {
-                nfts_load.push(nft_info);
-            }
-        }
+    // like staking rules, set bonuses cannot change once reward is added
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+    if campaign_info.total_reward != Uint128::zero() {
+        return Err(ContractError::InvalidTimeToUpdate {});
+    }
 
-        for term in terms {
-            let mut nft_list = nfts_load
-                .clone()
-                .into_iter()
-                .filter(|nft| nft.lockup_term.value == term.value)
-                .collect::<Vec<_>>();
-            nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
-
-            let mut time_calc: u64 = time_calc_nft;
-            let mut nft_count = nft_list
-                .clone()
-                .into_iter()
-                .filter(|nft| !nft.is_end_reward)
-                .collect::<Vec<_>>()
-                .len() as u128;
-            let mut reward = Uint128::zero();
-            for nft in nft_list.iter_mut() {
-                if !nft.is_end_reward {
-                    if nft.end_time <= current_time {
-                        // calc in time_calc -> nft.end_time
-                        let calc_reward = calc_reward_in_time(
-                            time_calc,
-                            nft.end_time,
-                            reward_per_second,
-                            term.percent,
-                            nft_count,
-                        )
-                        .unwrap();
-
-                        // increase reward for next nft
-                        reward = add_reward(reward, calc_reward).unwrap();
-
-                        // update reward for nft
-                        nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                        nft_count -= 1; // update count nft for next calc reward
-                        time_calc = nft.end_time; // update time_calc
-                        nft.is_end_reward = true; // nft stake timeout
-                    } else {
-                        // calc in time_calc -> current_time
-                        let calc_reward = calc_reward_in_time(
-                            time_calc,
-                            current_time,
-                            reward_per_second,
-                            term.percent,
-                            nft_count,
-                        )
-                        .unwrap();
-
-                        // add reward previous and current reward
-                        let accumulate_reward = add_reward(reward, calc_reward).unwrap();
-
-                        // update reward for nft
-                        nft.pending_reward =
-                            add_reward(nft.pending_reward, accumulate_reward).unwrap()
-                    }
-                    if env.block.time.seconds() >= campaign_info.end_time {
-                        nft.is_end_reward = true;
-                    }
-                }
-                if info.token_id == nft.token_id {
-                    info = nft.clone();
-                }
+    // a set bonus never lowers weight and its rule can be satisfied
+    for bonus in set_bonuses.iter() {
+        let valid_rule = match &bonus.rule {
+            SetRule::Count { count } => *count > 0,
+            SetRule::Traits { groups } => {
+                !groups.is_empty() && groups.iter().all(|group| !group.values.is_empty())
             }
+        };
+        if !valid_rule || bonus.multiplier < BASE_WEIGHT {
+            return Err(ContractError::InvalidSetBonus {});
         }
     }
 
-    Ok(info)
+    SET_BONUSES.save(deps.storage, &set_bonuses)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_set_bonuses")
+        .add_event(update_set_bonuses_event(&set_bonuses)))
 }
 
-fn query_nft(deps: Deps, _env: Env, token_id: String) -> Result<NftInfo, ContractError> {
-    let info: NftInfo = NFTS.load(deps.storage, token_id)?;
+// traits of nft in collection, no trait if collection has no metadata
+fn query_nft_traits(deps: Deps, campaign_info: &CampaignInfo, token_id: &str) -> Vec<Trait> {
+    let nft_info: StdResult<NftInfoResponse<Option<Metadata>>> = deps.querier.query_wasm_smart(
+        campaign_info.allowed_collection.to_string(),
+        &Cw721QueryMsg::NftInfo {
+            token_id: token_id.to_string(),
+        },
+    );
+    nft_info
+        .ok()
+        .and_then(|nft_info| nft_info.extension)
+        .and_then(|metadata| metadata.attributes)
+        .unwrap_or_default()
+}
 
-    Ok(info)
+// a trait set is configured, traits of staked nfts are saved
+fn has_trait_set(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(SET_BONUSES
+        .may_load(storage)?
+        .unwrap_or_default()
+        .iter()
+        .any(|bonus| matches!(bonus.rule, SetRule::Traits { .. })))
 }
 
-fn query_staker_info(deps: Deps, env: Env, owner: Addr) -> Result<StakedInfoResult, ContractError> {
-    let staker_asset: StakerRewardAssetInfo = STAKERS_INFO
-        .may_load(deps.storage, owner)?
-        .unwrap_or(StakerRewardAssetInfo {
-            token_ids: vec![],
-            reward_debt: Uint128::zero(),
-            reward_claimed: Uint128::zero(),
-        });
+// set multiplier of staker with token_ids from saved traits of its nfts
+fn staker_set_multiplier(storage: &dyn Storage, token_ids: &[String]) -> StdResult<u64> {
+    let set_bonuses = SET_BONUSES.may_load(storage)?.unwrap_or_default();
+    let nft_traits = token_ids
+        .iter()
+        .map(|token_id| {
+            Ok(NFT_TRAITS
+                .may_load(storage, token_id.clone())?
+                .unwrap_or_default())
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(calc_set_multiplier(
+        &set_bonuses,
+        token_ids.len() as u64,
+        &nft_traits,
+    ))
+}
 
-    let mut staked_info = StakedInfoResult {
-        nfts: vec![],
-        reward_debt: staker_asset.reward_debt,
-        reward_claimed: staker_asset.reward_claimed,
-    };
+fn set_bonus_weight(base_weight: u64, set_multiplier: u64) -> Result<u64, ContractError> {
+    u64::try_from(
+        Uint128::from(base_weight)
+            .multiply_ratio(set_multiplier, BASE_WEIGHT)
+            .u128(),
+    )
+    .map_err(|_| ContractError::WeightOverflow {})
+}
 
-    // get time to calc pending reward
-    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
-    let mut current_time = env.block.time.seconds();
-    if campaign_info.end_time < env.block.time.seconds() {
-        current_time = campaign_info.end_time;
+// save set multiplier of staker and apply it to its nfts still earning reward,
+// reward state must be saved before weights change
+fn save_set_multiplier(
+    storage: &mut dyn Storage,
+    staker: &Addr,
+    set_multiplier: u64,
+) -> Result<(), ContractError> {
+    // weights of nfts only change with the multiplier
+    let previous_multiplier = SET_MULTIPLIERS
+        .may_load(storage, staker.clone())?
+        .unwrap_or(BASE_WEIGHT);
+    SET_MULTIPLIERS.save(storage, staker.clone(), &set_multiplier)?;
+    if set_multiplier == previous_multiplier {
+        return Ok(());
     }
+    for mut nft in load_staker_nfts(storage, staker)? {
+        if nft.is_end_reward || TOKEN_DEPOSITS.has(storage, nft.token_id.clone()) {
+            continue;
+        }
+        let base_weight = NFT_BASE_WEIGHTS
+            .may_load(storage, nft.token_id.clone())?
+            .unwrap_or(nft.weight);
+        let weight = set_bonus_weight(base_weight, set_multiplier)?;
+        if weight != nft.weight {
+            // reward until now is earned with the previous weight
+            save_nft_reward(storage, &mut nft)?;
+            let mut state = TERM_STATES.load(storage, nft.lockup_term.value)?;
+            state.total_weight =
+                sub_reward(state.total_weight, Uint128::from(nft.weight))? + Uint128::from(weight);
+            TERM_STATES.save(storage, nft.lockup_term.value, &state)?;
+            nft.weight = weight;
+            staked_nfts().save(storage, nft.token_id.clone(), &nft)?;
+        }
+    }
+    Ok(())
+}
 
-    // update pending reward for all nft
-    let terms = campaign_info.clone().lockup_term;
-    let time_calc_nft = campaign_info.time_calc_nft;
-    let reward_per_second = campaign_info.reward_per_second;
+pub fn execute_withdraw_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
 
-    // load nfts
-    let mut nfts_load = Vec::new();
-    let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
-    for item in nfts_storage {
-        let (_, nft_info) = item?;
-        nfts_load.push(nft_info);
+    let unbonding_nft = match unbonding_nfts().may_load(deps.storage, token_id.clone())? {
+        Some(unbonding_nft) => unbonding_nft,
+        None => return Err(ContractError::EmptyNft { token_id }),
+    };
+
+    // only owner of nft or its operator can withdraw, nft is always returned to the owner
+    if !can_act_for(deps.storage, &env, &unbonding_nft.owner, &info.sender)? {
+        return Err(ContractError::NotOwner { token_id });
     }
 
-    for term in terms {
-        let mut nft_list = nfts_load
-            .clone()
-            .into_iter()
-            .filter(|nft| nft.lockup_term.value == term.value)
-            .collect::<Vec<_>>();
-        nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
+    if env.block.time.seconds() < unbonding_nft.release_time {
+        return Err(ContractError::Unbonding {
+            token_id,
+            release_time: unbonding_nft.release_time,
+        });
+    }
 
-        let mut time_calc: u64 = time_calc_nft;
-        let mut nft_count = nft_list
-            .clone()
-            .into_iter()
-            .filter(|nft| !nft.is_end_reward)
-            .collect::<Vec<_>>()
-            .len() as u128;
-        let mut reward = Uint128::zero();
-        for nft in nft_list.iter_mut() {
-            if !nft.is_end_reward {
-                if nft.end_time <= current_time {
-                    // calc in time_calc -> nft.end_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        nft.end_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // increase reward for next nft
-                    reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                    nft_count -= 1; // update count nft for next calc reward
-                    time_calc = nft.end_time; // update time_calc
-                    nft.is_end_reward = true; // nft stake timeout
-                } else {
-                    // calc in time_calc -> current_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        current_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // add reward previous and current reward
-                    let accumulate_reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, accumulate_reward).unwrap()
-                }
-                if env.block.time.seconds() >= campaign_info.end_time {
-                    nft.is_end_reward = true;
-                }
-            }
-            if staker_asset.token_ids.contains(&nft.token_id) {
-                staked_info.nfts.push(nft.clone());
-                staked_info.reward_debt =
-                    add_reward(staked_info.reward_debt, nft.pending_reward).unwrap();
-            }
+    unbonding_nfts().remove(deps.storage, token_id.clone())?;
+
+    Ok(Response::new()
+        .add_message(transfer_staked(
+            deps.storage,
+            &campaign_info,
+            &unbonding_nft.owner,
+            &token_id,
+        )?)
+        .add_attribute("action", "withdraw_nft")
+        .add_event(withdraw_nft_event(&unbonding_nft)))
+}
+
+pub fn execute_update_eligibility(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_allowlist_enabled: Option<bool>,
+    staker_allowlist_enabled: Option<bool>,
+    allowed_token_ranges: Option<Vec<TokenIdRange>>,
+    denied_token_ranges: Option<Vec<TokenIdRange>>,
+) -> Result<Response, ContractError> {
+    check_campaign_owner(deps.as_ref(), &info)?;
+
+    let mut config = ELIGIBILITY_CONFIG
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if let Some(enabled) = token_allowlist_enabled {
+        config.token_allowlist_enabled = enabled;
+    }
+    if let Some(enabled) = staker_allowlist_enabled {
+        config.staker_allowlist_enabled = enabled;
+    }
+    if let Some(ranges) = allowed_token_ranges {
+        config.allowed_token_ranges = ranges;
+    }
+    if let Some(ranges) = denied_token_ranges {
+        config.denied_token_ranges = ranges;
+    }
+
+    // validate ranges
+    if config
+        .allowed_token_ranges
+        .iter()
+        .chain(config.denied_token_ranges.iter())
+        .any(|range| range.min > range.max)
+    {
+        return Err(ContractError::Std(StdError::generic_err(
+            "## Min of token_id range is greater than max ##",
+        )));
+    }
+
+    ELIGIBILITY_CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_eligibility")
+        .add_event(update_eligibility_event(&config)))
+}
+
+pub fn execute_update_token_id_list(
+    deps: DepsMut,
+    info: MessageInfo,
+    list: ListKind,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    check_campaign_owner(deps.as_ref(), &info)?;
+
+    let token_ids = match list {
+        ListKind::Allow => ALLOWED_TOKEN_IDS,
+        ListKind::Deny => DENIED_TOKEN_IDS,
+    };
+    for token_id in add.iter() {
+        token_ids.save(deps.storage, token_id.clone(), &true)?;
+    }
+    for token_id in remove.iter() {
+        token_ids.remove(deps.storage, token_id.clone());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_token_id_list")
+        .add_event(update_list_event(
+            "update-token-id-list",
+            &list,
+            &add,
+            &remove,
+        )))
+}
+
+pub fn execute_update_staker_list(
+    deps: DepsMut,
+    info: MessageInfo,
+    list: ListKind,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    check_campaign_owner(deps.as_ref(), &info)?;
+
+    let stakers = match list {
+        ListKind::Allow => ALLOWED_STAKERS,
+        ListKind::Deny => DENIED_STAKERS,
+    };
+    for staker in add.iter() {
+        stakers.save(deps.storage, deps.api.addr_validate(staker)?, &true)?;
+    }
+    for staker in remove.iter() {
+        stakers.remove(deps.storage, deps.api.addr_validate(staker)?);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_staker_list")
+        .add_event(update_list_event(
+            "update-staker-list",
+            &list,
+            &add,
+            &remove,
+        )))
+}
+
+pub fn execute_register_merkle_roots(
+    deps: DepsMut,
+    info: MessageInfo,
+    staker_bonus_root: Option<String>,
+    token_weight_root: Option<String>,
+) -> Result<Response, ContractError> {
+    check_campaign_owner(deps.as_ref(), &info)?;
+
+    // like campaign info, weights cannot change once reward is added
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+    if campaign_info.total_reward != Uint128::zero() {
+        return Err(ContractError::InvalidTimeToUpdate {});
+    }
+
+    // check merkle roots are valid 32 bytes hex
+    for merkle_root in staker_bonus_root.iter().chain(token_weight_root.iter()) {
+        let mut root_buf: [u8; 32] = [0; 32];
+        hex::decode_to_slice(merkle_root, &mut root_buf)?;
+    }
+
+    let merkle_roots = MerkleRoots {
+        staker_bonus_root,
+        token_weight_root,
+    };
+    MERKLE_ROOTS.save(deps.storage, &merkle_roots)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_merkle_roots")
+        .add_event(register_merkle_roots_event(&merkle_roots)))
+}
+
+// bonus multiplier of staker, BASE_WEIGHT if staker bonus root is not registered
+fn staker_bonus_multiplier(
+    merkle_roots: &MerkleRoots,
+    staker: &Addr,
+    bonus: Option<StakerBonusProof>,
+) -> Result<u64, ContractError> {
+    match (&merkle_roots.staker_bonus_root, bonus) {
+        (None, _) => Ok(BASE_WEIGHT),
+        (Some(_), None) => Err(ContractError::IneligibleStaker {}),
+        (Some(merkle_root), Some(bonus)) => {
+            let leaf = format!("{}:{}", staker, bonus.bonus_multiplier);
+            verify_merkle_proof(merkle_root, &leaf, &bonus.proof)?;
+            Ok(bonus.bonus_multiplier)
         }
     }
+}
 
-    Ok(staked_info)
+// weight of nft = token weight * staker bonus multiplier / BASE_WEIGHT,
+// token weight is BASE_WEIGHT if token weight root is not registered
+fn nft_weight(
+    merkle_roots: &MerkleRoots,
+    nft: &NftStake,
+    bonus_multiplier: u64,
+) -> Result<u64, ContractError> {
+    let token_weight = match (&merkle_roots.token_weight_root, &nft.weight_proof) {
+        (None, _) => BASE_WEIGHT,
+        (Some(_), None) => {
+            return Err(ContractError::IneligibleNft {
+                token_id: nft.token_id.clone(),
+            })
+        }
+        (Some(merkle_root), Some(weight_proof)) => {
+            let leaf = format!("{}:{}", nft.token_id, weight_proof.weight);
+            verify_merkle_proof(merkle_root, &leaf, &weight_proof.proof)?;
+            weight_proof.weight
+        }
+    };
+
+    let weight = u64::try_from(
+        Uint128::from(token_weight)
+            .multiply_ratio(bonus_multiplier, BASE_WEIGHT)
+            .u128(),
+    )
+    .map_err(|_| ContractError::WeightOverflow {})?;
+    if weight == 0 {
+        return Err(ContractError::InvalidWeight {});
+    }
+    Ok(weight)
 }
 
-fn query_nfts(deps: Deps, env: Env, limit: Option<u32>) -> Result<Vec<NftInfo>, ContractError> {
-    let mut result_nfts = vec![];
+// lockup terms are unique, allowed by term policy and share at most 100 percent of reward
+fn validate_lockup_terms(
+    term_policy: &TermPolicy,
+    lockup_term: &[LockupTerm],
+) -> Result<(), ContractError> {
+    let mut total_percent = Uint128::zero();
+    for (i, term) in lockup_term.iter().enumerate() {
+        if !term_policy.allows(term.value) {
+            return Err(ContractError::InvalidLockupTerm {});
+        }
+        if lockup_term[..i].iter().any(|t| t.value == term.value) {
+            return Err(ContractError::DuplicateLockupTerm {
+                lockup_term: term.value,
+            });
+        }
+        total_percent = add_reward(total_percent, term.percent)?;
+    }
+    if total_percent > Uint128::new(100u128) {
+        return Err(ContractError::InvalidTermPercent {});
+    }
+    Ok(())
+}
 
-    let limit = limit.unwrap_or(30 as u32) as usize;
-    // get time to calc pending reward
+// only owner of campaign can execute
+fn check_campaign_owner(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
     let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
-    let mut current_time = env.block.time.seconds();
-    if campaign_info.end_time < env.block.time.seconds() {
-        current_time = campaign_info.end_time;
+    if info.sender != campaign_info.owner {
+        return Err(ContractError::Unauthorized {});
     }
-    // update pending reward for all nft
-    let terms = campaign_info.clone().lockup_term;
-    let time_calc_nft = campaign_info.time_calc_nft;
-    let reward_per_second = campaign_info.reward_per_second;
+    Ok(())
+}
 
-    // load nfts
-    let mut nfts_load = Vec::new();
-    let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
-    for item in nfts_storage {
-        let (_, nft_info) = item?;
-        nfts_load.push(nft_info);
+// staker is not denied and is allowed if staker allowlist is enabled
+fn is_eligible_staker(
+    storage: &dyn Storage,
+    config: &EligibilityConfig,
+    staker: &Addr,
+) -> StdResult<bool> {
+    if DENIED_STAKERS.has(storage, staker.clone()) {
+        return Ok(false);
+    }
+    Ok(!config.staker_allowlist_enabled || ALLOWED_STAKERS.has(storage, staker.clone()))
+}
+
+// token_id is not denied and is allowed if token allowlist is enabled
+fn is_eligible_token_id(
+    storage: &dyn Storage,
+    config: &EligibilityConfig,
+    token_id: &str,
+) -> StdResult<bool> {
+    if DENIED_TOKEN_IDS.has(storage, token_id.to_string())
+        || config
+            .denied_token_ranges
+            .iter()
+            .any(|range| range.contains(token_id))
+    {
+        return Ok(false);
     }
+    Ok(!config.token_allowlist_enabled
+        || ALLOWED_TOKEN_IDS.has(storage, token_id.to_string())
+        || config
+            .allowed_token_ranges
+            .iter()
+            .any(|range| range.contains(token_id)))
+}
 
-    for term in terms {
-        let mut nft_list = nfts_load
-            .clone()
-            .into_iter()
-            .filter(|nft| nft.lockup_term.value == term.value)
+// nft of flexible term has no lockup and earns until the end of campaign unless unstaked
+fn nft_end_time(campaign_info: &CampaignInfo, lockup_term: &LockupTerm, start_time: u64) -> u64 {
+    if lockup_term.is_flexible() {
+        campaign_info.end_time
+    } else {
+        start_time + lockup_term.value
+    }
+}
+
+// message to transfer a staked nft or token deposit back to recipient,
+// the deposit is removed once its tokens are returned
+fn transfer_staked(
+    storage: &mut dyn Storage,
+    campaign_info: &CampaignInfo,
+    recipient: &Addr,
+    token_id: &str,
+) -> StdResult<WasmMsg> {
+    if let Some(amount) = TOKEN_DEPOSITS.may_load(storage, token_id.to_string())? {
+        TOKEN_DEPOSITS.remove(storage, token_id.to_string());
+        let token_staking = TOKEN_STAKING.load(storage)?;
+        return Ok(WasmMsg::Execute {
+            contract_addr: token_staking.contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        });
+    }
+    Ok(WasmMsg::Execute {
+        contract_addr: campaign_info.allowed_collection.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: recipient.to_string(),
+            token_id: token_id.to_string(),
+        })?,
+        funds: vec![],
+    })
+}
+
+// message to the factory keeping registry of staked nfts if campaign is created by it
+fn factory_callback(storage: &dyn Storage, msg: FactoryCallbackMsg) -> StdResult<Option<WasmMsg>> {
+    match FACTORY.may_load(storage)? {
+        Some(factory) => Ok(Some(WasmMsg::Execute {
+            contract_addr: factory.to_string(),
+            msg: to_binary(&msg)?,
+            funds: vec![],
+        })),
+        None => Ok(None),
+    }
+}
+
+// sender is the owner or an operator approved by the owner and not expired
+fn can_act_for(storage: &dyn Storage, env: &Env, owner: &Addr, sender: &Addr) -> StdResult<bool> {
+    if owner == sender {
+        return Ok(true);
+    }
+    let expires = OPERATORS.may_load(storage, (owner.clone(), sender.clone()))?;
+    Ok(matches!(expires, Some(expires) if !expires.is_expired(&env.block)))
+}
+
+// load all nfts which are still earning reward
+fn load_active_nfts(
+    storage: &dyn Storage,
+    campaign_info: &CampaignInfo,
+) -> StdResult<Vec<NftInfo>> {
+    let mut nfts = vec![];
+    for term in campaign_info.lockup_term.iter() {
+        let term_nfts = staked_nfts().idx.lockup_term.prefix(term.value).range(
+            storage,
+            None,
+            None,
+            Order::Ascending,
+        );
+        for item in term_nfts {
+            let (_, nft_info) = item?;
+            if !nft_info.is_end_reward {
+                nfts.push(nft_info);
+            }
+        }
+    }
+    Ok(nfts)
+}
+
+// load all nfts staked by owner
+fn load_staker_nfts(storage: &dyn Storage, owner: &Addr) -> StdResult<Vec<NftInfo>> {
+    staked_nfts()
+        .idx
+        .owner
+        .prefix(owner.clone())
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, nft_info)| nft_info))
+        .collect()
+}
+
+// token_ids of nfts staked by owner, token deposits are not nfts
+fn load_staker_token_ids(storage: &dyn Storage, owner: &Addr) -> StdResult<Vec<String>> {
+    Ok(load_staker_nfts(storage, owner)?
+        .into_iter()
+        .map(|nft| nft.token_id)
+        .filter(|token_id| !TOKEN_DEPOSITS.has(storage, token_id.clone()))
+        .collect())
+}
+
+// reward of lockup terms accrued from campaign_info.time_calc_nft without saving.
+// reward of each lockup term is shared between its nfts by weight, and an nft stops earning
+// at its end_time, so reward is accrued between expirations of nfts in order.
+// nfts of flexible term end with the campaign and leave the calculation when unstaked.
+#[derive(Clone)]
+struct RewardAccrual {
+    time: u64,
+    term_states: BTreeMap<u64, TermState>,
+    term_rewards: BTreeMap<u64, Uint128>,
+    accrued_reward: Uint128,
+    // nfts which are not saved, with their reward index
+    scratch_nfts: BTreeMap<String, (NftInfo, Uint256)>,
+    // nfts which stopped earning, with their final pending reward
+    expired_nfts: BTreeMap<String, NftInfo>,
+    // last saved expiration which is accrued
+    last_expiration: Option<(u64, String)>,
+}
+
+// reward state of lockup terms at campaign_info.time_calc_nft
+fn load_reward_accrual(
+    storage: &dyn Storage,
+    campaign_info: &CampaignInfo,
+) -> StdResult<RewardAccrual> {
+    let mut term_states = BTreeMap::new();
+    for term in campaign_info.lockup_term.iter() {
+        let state = TERM_STATES
+            .may_load(storage, term.value)?
+            .unwrap_or_default();
+        term_states.insert(term.value, state);
+    }
+
+    Ok(RewardAccrual {
+        time: campaign_info.time_calc_nft,
+        term_states,
+        term_rewards: load_term_rewards(storage, campaign_info)?,
+        accrued_reward: Uint128::zero(),
+        scratch_nfts: BTreeMap::new(),
+        expired_nfts: BTreeMap::new(),
+        last_expiration: None,
+    })
+}
+
+// accrue reward of lockup terms until block_time, capped at the end of campaign.
+// with a limit, at most limit expirations are accrued and the accrual stops at the last one.
+// reward of a lockup term is capped at its max_reward, the reward exceeding term_rewards
+// is scaled down for every nft of the term.
+fn accrue_reward(
+    storage: &dyn Storage,
+    campaign_info: &CampaignInfo,
+    accrual: &mut RewardAccrual,
+    block_time: u64,
+    limit: Option<usize>,
+) -> Result<(), ContractError> {
+    // nothing is accrued before the first stake
+    let mut current_time = min(block_time, campaign_info.end_time);
+    if accrual.time == 0 || current_time < accrual.time {
+        return Ok(());
+    }
+
+    // expirations until current_time in order, saved ones after the last accrued
+    let start = accrual.last_expiration.clone().map(Bound::exclusive);
+    let mut expirations = NFT_EXPIRATIONS
+        .range(
+            storage,
+            start,
+            Some(expiration_bound(current_time)),
+            Order::Ascending,
+        )
+        .take(limit.map_or(usize::MAX, |limit| limit + 1))
+        .map(|item| item.map(|(expiration, _)| expiration))
+        .collect::<StdResult<Vec<_>>>()?;
+    for (token_id, (nft, _)) in accrual.scratch_nfts.iter() {
+        let time = expiration_time(campaign_info, nft);
+        if time <= current_time && !accrual.expired_nfts.contains_key(token_id) {
+            expirations.push((time, token_id.clone()));
+        }
+    }
+    expirations.sort();
+    if let Some(limit) = limit {
+        if expirations.len() > limit {
+            expirations.truncate(limit);
+            current_time = expirations.last().map_or(accrual.time, |(time, _)| *time);
+        }
+    }
+
+    // expiring nfts with their reward index
+    let mut expiring_nfts = vec![];
+    for (_, token_id) in expirations.iter() {
+        let expiring_nft = match accrual.scratch_nfts.get(token_id) {
+            Some(scratch) => scratch.clone(),
+            None => (
+                staked_nfts().load(storage, token_id.clone())?,
+                NFT_REWARD_INDEXES.load(storage, token_id.clone())?,
+            ),
+        };
+        expiring_nfts.push(expiring_nft);
+    }
+
+    for term in campaign_info.lockup_term.iter() {
+        let state = accrual.term_states.entry(term.value).or_default();
+        let term_nfts = expiring_nfts
+            .iter()
+            .filter(|(nft, _)| nft.lockup_term.value == term.value)
             .collect::<Vec<_>>();
-        nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
 
-        let mut time_calc: u64 = time_calc_nft;
-        let mut nft_count = nft_list
-            .clone()
-            .into_iter()
-            .filter(|nft| !nft.is_end_reward)
-            .collect::<Vec<_>>()
-            .len() as u128;
-        let mut reward = Uint128::zero();
-        for nft in nft_list.iter_mut() {
-            if !nft.is_end_reward {
-                if nft.end_time <= current_time {
-                    // calc in time_calc -> nft.end_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        nft.end_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // increase reward for next nft
-                    reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                    nft_count -= 1; // update count nft for next calc reward
-                    time_calc = nft.end_time; // update time_calc
-                    nft.is_end_reward = true; // nft stake timeout
-                } else {
-                    // calc in time_calc -> current_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        current_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // add reward previous and current reward
-                    let accumulate_reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, accumulate_reward).unwrap()
-                }
-                if env.block.time.seconds() >= campaign_info.end_time {
-                    nft.is_end_reward = true;
+        // reward of an nft with BASE_WEIGHT and total weight of term between expirations
+        let mut periods = vec![];
+        let mut time_calc = accrual.time;
+        let mut total_weight = state.total_weight.u128();
+        for (nft, _) in term_nfts.iter() {
+            let end_time = expiration_time(campaign_info, nft);
+            periods.push((
+                period_reward(campaign_info, term, time_calc, end_time, total_weight)?,
+                total_weight,
+            ));
+            total_weight -= nft.weight as u128;
+            time_calc = end_time;
+        }
+        periods.push((
+            period_reward(campaign_info, term, time_calc, current_time, total_weight)?,
+            total_weight,
+        ));
+
+        let mut new_reward = total_period_reward(&periods)?;
+        if let Some(max_reward) = term.max_reward {
+            let accrued_reward = accrual
+                .term_rewards
+                .get(&term.value)
+                .copied()
+                .unwrap_or_default();
+            let remaining_reward = max_reward.saturating_sub(accrued_reward);
+            if new_reward > remaining_reward {
+                for (reward, _) in periods.iter_mut() {
+                    *reward = reward.multiply_ratio(remaining_reward, new_reward);
                 }
+                new_reward = total_period_reward(&periods)?;
             }
-            result_nfts.push(nft.clone());
         }
+
+        // nfts stop earning at the end of their period
+        for ((reward, _), (nft, reward_index)) in periods.iter().zip(term_nfts) {
+            state.reward_index = state.reward_index.checked_add(*reward)?;
+            let mut nft = nft.clone();
+            nft.pending_reward = add_reward(
+                nft.pending_reward,
+                weighted_reward(state.reward_index.checked_sub(*reward_index)?, &nft)?,
+            )?;
+            nft.is_end_reward = true;
+            state.total_weight = sub_reward(state.total_weight, Uint128::from(nft.weight))?;
+            state.nft_count -= 1;
+            accrual.expired_nfts.insert(nft.token_id.clone(), nft);
+        }
+        if let Some((reward, _)) = periods.last() {
+            state.reward_index = state.reward_index.checked_add(*reward)?;
+        }
+
+        let term_reward = accrual.term_rewards.entry(term.value).or_default();
+        *term_reward = add_reward(*term_reward, new_reward)?;
+        accrual.accrued_reward = add_reward(accrual.accrued_reward, new_reward)?;
+    }
+
+    if let Some(expiration) = expirations
+        .into_iter()
+        .rev()
+        .find(|(_, token_id)| !accrual.scratch_nfts.contains_key(token_id))
+    {
+        accrual.last_expiration = Some(expiration);
+    }
+    accrual.time = current_time;
+
+    Ok(())
+}
+
+// scaled reward of an nft with BASE_WEIGHT from start_time to end_time
+fn period_reward(
+    campaign_info: &CampaignInfo,
+    term: &LockupTerm,
+    start_time: u64,
+    end_time: u64,
+    total_weight: u128,
+) -> Result<Uint256, ContractError> {
+    if total_weight == 0 {
+        return Ok(Uint256::zero());
+    }
+    calc_weighted_reward_in_time(
+        start_time,
+        end_time,
+        campaign_info.reward_per_second,
+        term.percent,
+        total_weight,
+    )
+}
+
+// reward of all nfts of term in periods
+fn total_period_reward(periods: &[(Uint256, u128)]) -> Result<Uint128, ContractError> {
+    periods
+        .iter()
+        .try_fold(Uint128::zero(), |total, (reward, total_weight)| {
+            Ok(add_reward(
+                total,
+                calc_index_reward(*reward, *total_weight)?,
+            )?)
+        })
+}
+
+// save reward state of lockup terms and nfts which stopped earning
+fn save_reward_accrual(
+    storage: &mut dyn Storage,
+    campaign_info: &CampaignInfo,
+    accrual: &RewardAccrual,
+) -> StdResult<()> {
+    for (lockup_term, state) in accrual.term_states.iter() {
+        TERM_STATES.save(storage, *lockup_term, state)?;
+    }
+    for (lockup_term, reward) in accrual.term_rewards.iter() {
+        TERM_REWARDS.save(storage, *lockup_term, reward)?;
+    }
+    for nft in accrual.expired_nfts.values() {
+        staked_nfts().save(storage, nft.token_id.clone(), nft)?;
+        NFT_REWARD_INDEXES.remove(storage, nft.token_id.clone());
+        NFT_EXPIRATIONS.remove(
+            storage,
+            (expiration_time(campaign_info, nft), nft.token_id.clone()),
+        );
+    }
+    Ok(())
+}
+
+// nft with its pending reward at the time of accrual
+fn accrued_nft(
+    storage: &dyn Storage,
+    accrual: &RewardAccrual,
+    mut nft: NftInfo,
+) -> Result<NftInfo, ContractError> {
+    if let Some(expired_nft) = accrual.expired_nfts.get(&nft.token_id) {
+        return Ok(expired_nft.clone());
+    }
+    if nft.is_end_reward {
+        return Ok(nft);
+    }
+
+    let reward_index = match accrual.scratch_nfts.get(&nft.token_id) {
+        Some((_, reward_index)) => *reward_index,
+        None => NFT_REWARD_INDEXES.load(storage, nft.token_id.clone())?,
+    };
+    let term_index = accrual
+        .term_states
+        .get(&nft.lockup_term.value)
+        .map_or(reward_index, |state| state.reward_index);
+    nft.pending_reward = add_reward(
+        nft.pending_reward,
+        weighted_reward(term_index.checked_sub(reward_index)?, &nft)?,
+    )?;
+    Ok(nft)
+}
+
+// time nft stops earning reward
+fn expiration_time(campaign_info: &CampaignInfo, nft: &NftInfo) -> u64 {
+    min(nft.end_time, campaign_info.end_time)
+}
+
+// bound of expirations until time
+fn expiration_bound(time: u64) -> Bound<'static, (u64, String)> {
+    Bound::exclusive((time + 1, String::new()))
+}
+
+// add staked nft to reward state of its lockup term
+fn add_earning_nft(
+    storage: &mut dyn Storage,
+    campaign_info: &CampaignInfo,
+    nft: &NftInfo,
+) -> StdResult<()> {
+    let mut state = TERM_STATES
+        .may_load(storage, nft.lockup_term.value)?
+        .unwrap_or_default();
+    state.total_weight += Uint128::from(nft.weight);
+    state.nft_count += 1;
+    TERM_STATES.save(storage, nft.lockup_term.value, &state)?;
+
+    NFT_REWARD_INDEXES.save(storage, nft.token_id.clone(), &state.reward_index)?;
+    NFT_EXPIRATIONS.save(
+        storage,
+        (expiration_time(campaign_info, nft), nft.token_id.clone()),
+        &nft.lockup_term.value,
+    )
+}
+
+// save pending reward of nft and remove it from reward state of its lockup term,
+// reward state must be saved at current time before
+fn remove_earning_nft(
+    storage: &mut dyn Storage,
+    campaign_info: &CampaignInfo,
+    nft: &mut NftInfo,
+) -> Result<(), ContractError> {
+    if nft.is_end_reward {
+        return Ok(());
+    }
+    save_nft_reward(storage, nft)?;
+
+    let mut state = TERM_STATES.load(storage, nft.lockup_term.value)?;
+    state.total_weight = sub_reward(state.total_weight, Uint128::from(nft.weight))?;
+    state.nft_count -= 1;
+    TERM_STATES.save(storage, nft.lockup_term.value, &state)?;
+
+    NFT_REWARD_INDEXES.remove(storage, nft.token_id.clone());
+    NFT_EXPIRATIONS.remove(
+        storage,
+        (expiration_time(campaign_info, nft), nft.token_id.clone()),
+    );
+    Ok(())
+}
+
+// add reward of nft earning reward since its reward index was saved to its pending reward,
+// reward state must be saved at current time before
+fn save_nft_reward(storage: &mut dyn Storage, nft: &mut NftInfo) -> Result<(), ContractError> {
+    if nft.is_end_reward {
+        return Ok(());
+    }
+    let term_index = TERM_STATES
+        .load(storage, nft.lockup_term.value)?
+        .reward_index;
+    let reward_index = NFT_REWARD_INDEXES.load(storage, nft.token_id.clone())?;
+    nft.pending_reward = add_reward(
+        nft.pending_reward,
+        weighted_reward(term_index.checked_sub(reward_index)?, nft)?,
+    )?;
+    NFT_REWARD_INDEXES.save(storage, nft.token_id.clone(), &term_index)?;
+    Ok(())
+}
+
+// reward accrued by nfts of each lockup term until campaign_info.time_calc_nft
+fn load_term_rewards(
+    storage: &dyn Storage,
+    campaign_info: &CampaignInfo,
+) -> StdResult<BTreeMap<u64, Uint128>> {
+    campaign_info
+        .lockup_term
+        .iter()
+        .map(|term| {
+            let reward = TERM_REWARDS
+                .may_load(storage, term.value)?
+                .unwrap_or_default();
+            Ok((term.value, reward))
+        })
+        .collect()
+}
+
+// number of nfts staked in lockup term
+fn term_nft_count(storage: &dyn Storage, lockup_term: u64) -> StdResult<u64> {
+    Ok(TERM_NFT_COUNTS
+        .may_load(storage, lockup_term)?
+        .unwrap_or_default())
+}
+
+// count nft staked in or removed from lockup term
+fn update_term_nft_count(
+    storage: &mut dyn Storage,
+    lockup_term: u64,
+    staked: bool,
+) -> StdResult<()> {
+    TERM_NFT_COUNTS.update(storage, lockup_term, |count| -> StdResult<_> {
+        let count = count.unwrap_or_default();
+        Ok(if staked { count + 1 } else { count - 1 })
+    })?;
+    Ok(())
+}
+
+// lockup term is full if it reached max_nfts or max_reward
+fn is_term_full(storage: &dyn Storage, lockup_term: &LockupTerm) -> StdResult<bool> {
+    if let Some(max_nfts) = lockup_term.max_nfts {
+        if term_nft_count(storage, lockup_term.value)? >= max_nfts {
+            return Ok(true);
+        }
+    }
+    if let Some(max_reward) = lockup_term.max_reward {
+        let reward = TERM_REWARDS
+            .may_load(storage, lockup_term.value)?
+            .unwrap_or_default();
+        if reward >= max_reward {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// reward of nft from scaled reward of an nft with BASE_WEIGHT
+fn weighted_reward(base_reward: Uint256, nft: &NftInfo) -> Result<Uint128, ContractError> {
+    calc_index_reward(base_reward, nft.weight as u128)
+}
+
+// update pending reward of active nfts at block_time,
+// returns the nfts which stopped earning and the reward accrued
+fn save_pending_reward(
+    storage: &mut dyn Storage,
+    campaign_info: &CampaignInfo,
+    block_time: u64,
+) -> Result<(Vec<NftInfo>, Uint128), ContractError> {
+    let mut accrual = load_reward_accrual(storage, campaign_info)?;
+    accrue_reward(storage, campaign_info, &mut accrual, block_time, None)?;
+    save_reward_accrual(storage, campaign_info, &accrual)?;
+
+    Ok((
+        accrual.expired_nfts.into_values().collect(),
+        accrual.accrued_reward,
+    ))
+}
+
+// save reward rate, nft count of each lockup term and total accrued reward at time
+fn save_reward_snapshot(
+    storage: &mut dyn Storage,
+    campaign_info: &CampaignInfo,
+    time: u64,
+    accrued_reward: Uint128,
+) -> Result<(), ContractError> {
+    let mut nft_counts = vec![];
+    for term in campaign_info.lockup_term.iter() {
+        let state = TERM_STATES
+            .may_load(storage, term.value)?
+            .unwrap_or_default();
+        nft_counts.push(TermNftCount {
+            lockup_term: term.value,
+            nft_count: state.nft_count,
+        });
     }
-    result_nfts = result_nfts.iter().take(limit).cloned().collect::<Vec<_>>();
 
-    Ok(result_nfts)
+    // total accrued reward of previous snapshot
+    let previous_accrued = REWARD_HISTORY
+        .range(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(Uint128::zero(), |(_, snapshot)| snapshot.total_accrued);
+
+    REWARD_HISTORY.save(
+        storage,
+        time,
+        &RewardSnapshot {
+            time,
+            reward_per_second: campaign_info.reward_per_second,
+            nft_counts,
+            total_accrued: add_reward(previous_accrued, accrued_reward)?,
+        },
+    )?;
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::CampaignInfo {} => Ok(to_binary(&query_campaign_info(deps)?)?),
+        QueryMsg::NftInfo { token_id } => Ok(to_binary(&query_nft_info(deps, env, token_id)?)?),
+        QueryMsg::Nft { token_id } => Ok(to_binary(&query_nft(deps, env, token_id)?)?),
+        QueryMsg::NftStaked { owner } => Ok(to_binary(&query_staker_info(deps, env, owner)?)?),
+        QueryMsg::Nfts { start_after, limit } => {
+            Ok(to_binary(&query_nfts(deps, env, start_after, limit)?)?)
+        }
+        QueryMsg::TotalPendingReward {} => Ok(to_binary(&query_total_pending_reward(deps, env)?)?),
+        QueryMsg::TokenIds { start_after, limit } => {
+            Ok(to_binary(&query_token_ids(deps, start_after, limit)?)?)
+        }
+        QueryMsg::Stakers { start_after, limit } => {
+            Ok(to_binary(&query_stakers(deps, env, start_after, limit)?)?)
+        }
+        QueryMsg::StakerDashboard { owner } => {
+            Ok(to_binary(&query_staker_dashboard(deps, env, owner)?)?)
+        }
+        QueryMsg::SimulateStake { nfts, at_time } => {
+            Ok(to_binary(&query_simulate_stake(deps, env, nfts, at_time)?)?)
+        }
+        QueryMsg::SimulateUnstake { token_id, at_time } => Ok(to_binary(&query_simulate_unstake(
+            deps, env, token_id, at_time,
+        )?)?),
+        QueryMsg::SimulateClaim { owner, at_time } => Ok(to_binary(&query_simulate_claim(
+            deps, env, owner, at_time,
+        )?)?),
+        QueryMsg::RewardHistory { start_after, limit } => {
+            Ok(to_binary(&query_reward_history(deps, start_after, limit)?)?)
+        }
+        QueryMsg::TermPolicy {} => Ok(to_binary(
+            &TERM_POLICY.may_load(deps.storage)?.unwrap_or_default(),
+        )?),
+        QueryMsg::SetBonuses {} => Ok(to_binary(
+            &SET_BONUSES.may_load(deps.storage)?.unwrap_or_default(),
+        )?),
+        QueryMsg::StakingRules {} => Ok(to_binary(
+            &STAKING_RULES.may_load(deps.storage)?.unwrap_or_default(),
+        )?),
+        QueryMsg::UnbondingNfts { owner } => Ok(to_binary(&query_unbonding_nfts(deps, owner)?)?),
+        QueryMsg::ExclusivityRules {} => Ok(to_binary(&EXCLUSIVITY_RULES.may_load(deps.storage)?)?),
+        QueryMsg::ReceiptCollection {} => {
+            Ok(to_binary(&RECEIPT_COLLECTION.may_load(deps.storage)?)?)
+        }
+        QueryMsg::SoftStaking {} => Ok(to_binary(
+            &SOFT_STAKING.may_load(deps.storage)?.unwrap_or_default(),
+        )?),
+        QueryMsg::TokenStaking {} => Ok(to_binary(&TOKEN_STAKING.may_load(deps.storage)?)?),
+        QueryMsg::TokenDeposits { owner } => Ok(to_binary(&query_token_deposits(deps, owner)?)?),
+        QueryMsg::MerkleRoots {} => Ok(to_binary(
+            &MERKLE_ROOTS.may_load(deps.storage)?.unwrap_or_default(),
+        )?),
+        QueryMsg::EligibilityConfig {} => Ok(to_binary(
+            &ELIGIBILITY_CONFIG
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        )?),
+        QueryMsg::Eligibility { staker, token_ids } => {
+            Ok(to_binary(&query_eligibility(deps, staker, token_ids)?)?)
+        }
+        QueryMsg::RewardRecipient { owner } => {
+            Ok(to_binary(&query_reward_recipient(deps, owner)?)?)
+        }
+        QueryMsg::Operators {
+            owner,
+            include_expired,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query_operators(
+            deps,
+            env,
+            owner,
+            include_expired.unwrap_or(false),
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::StakerLedger {
+            owner,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query_staker_ledger(
+            deps,
+            owner,
+            start_after,
+            limit,
+        )?)?),
+    }
+}
+
+// append an entry to the reward ledger of staker
+fn append_ledger_entry(
+    storage: &mut dyn Storage,
+    env: &Env,
+    staker: &Addr,
+    action: LedgerAction,
+    token_id: Option<String>,
+    amount: Uint128,
+) -> StdResult<()> {
+    let id = STAKER_LEDGER_COUNT
+        .may_load(storage, staker.clone())?
+        .unwrap_or(0);
+    STAKER_LEDGER.save(
+        storage,
+        (staker.clone(), id),
+        &LedgerEntry {
+            id,
+            action,
+            token_id,
+            amount,
+            time: env.block.time.seconds(),
+            height: env.block.height,
+        },
+    )?;
+    STAKER_LEDGER_COUNT.save(storage, staker.clone(), &(id + 1))
+}
+
+// reward state of lockup terms at time without saving
+fn query_reward_accrual(
+    deps: Deps,
+    campaign_info: &CampaignInfo,
+    time: u64,
+) -> Result<RewardAccrual, ContractError> {
+    let mut accrual = load_reward_accrual(deps.storage, campaign_info)?;
+    accrue_reward(deps.storage, campaign_info, &mut accrual, time, None)?;
+    Ok(accrual)
+}
+
+// calculate pending reward of active nfts at current block time without saving
+fn query_active_nfts(
+    deps: Deps,
+    env: &Env,
+    campaign_info: &CampaignInfo,
+) -> Result<Vec<NftInfo>, ContractError> {
+    let accrual = query_reward_accrual(deps, campaign_info, env.block.time.seconds())?;
+    load_active_nfts(deps.storage, campaign_info)?
+        .into_iter()
+        .map(|nft| accrued_nft(deps.storage, &accrual, nft))
+        .collect()
+}
+
+fn query_campaign_info(deps: Deps) -> Result<CampaignInfoResult, ContractError> {
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+
+    let total_nft_staked = TOTAL_NFT_STAKED.load(deps.storage)?;
+
+    // fill level of each lockup term
+    let term_rewards = load_term_rewards(deps.storage, &campaign_info)?;
+    let term_fills = campaign_info
+        .lockup_term
+        .iter()
+        .map(|term| {
+            Ok(TermFill {
+                lockup_term: term.value,
+                nft_count: term_nft_count(deps.storage, term.value)?,
+                max_nfts: term.max_nfts,
+                accrued_reward: term_rewards[&term.value],
+                max_reward: term.max_reward,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let campaign_result = CampaignInfoResult {
+        owner: campaign_info.owner,
+        campaign_name: campaign_info.campaign_name,
+        campaign_image: campaign_info.campaign_image,
+        campaign_description: campaign_info.campaign_description,
+        start_time: campaign_info.start_time,
+        end_time: campaign_info.end_time,
+        total_reward_claimed: campaign_info.total_reward_claimed,
+        total_reward: campaign_info.total_reward,
+        limit_per_staker: campaign_info.limit_per_staker,
+        reward_token_info: campaign_info.reward_token,
+        allowed_collection: campaign_info.allowed_collection,
+        lockup_term: campaign_info.lockup_term,
+        reward_per_second: campaign_info.reward_per_second,
+        time_calc_nft: campaign_info.time_calc_nft,
+        total_nft_staked,
+        term_fills,
+    };
+    Ok(campaign_result)
+}
+
+fn query_nft_info(deps: Deps, env: Env, token_id: String) -> Result<NftInfo, ContractError> {
+    let info: NftInfo = staked_nfts().load(deps.storage, token_id)?;
+
+    // if nft is active then calculate
+    if !info.is_end_reward {
+        let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+        let accrual = query_reward_accrual(deps, &campaign_info, env.block.time.seconds())?;
+        return accrued_nft(deps.storage, &accrual, info);
+    }
+
+    Ok(info)
+}
+
+fn query_nft(deps: Deps, _env: Env, token_id: String) -> Result<NftInfo, ContractError> {
+    let info: NftInfo = staked_nfts().load(deps.storage, token_id)?;
+
+    Ok(info)
+}
+
+fn query_staker_info(deps: Deps, env: Env, owner: Addr) -> Result<StakedInfoResult, ContractError> {
+    let staker_asset: StakerRewardAssetInfo = STAKERS_INFO
+        .may_load(deps.storage, owner.clone())?
+        .unwrap_or(StakerRewardAssetInfo {
+            reward_debt: Uint128::zero(),
+            reward_claimed: Uint128::zero(),
+        });
+
+    let mut staked_info = StakedInfoResult {
+        nfts: vec![],
+        reward_debt: staker_asset.reward_debt,
+        reward_claimed: staker_asset.reward_claimed,
+        set_multiplier: SET_MULTIPLIERS
+            .may_load(deps.storage, owner.clone())?
+            .unwrap_or(BASE_WEIGHT),
+    };
+
+    // update pending reward for all nft
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+    let accrual = query_reward_accrual(deps, &campaign_info, env.block.time.seconds())?;
+
+    for nft in load_staker_nfts(deps.storage, &owner)? {
+        let nft = accrued_nft(deps.storage, &accrual, nft)?;
+        staked_info.reward_debt = add_reward(staked_info.reward_debt, nft.pending_reward)?;
+        staked_info.nfts.push(nft);
+    }
+
+    // nfts are listed by lockup term then by end time
+    staked_info.nfts.sort_by_key(|nft| {
+        (
+            campaign_info
+                .lockup_term
+                .iter()
+                .position(|term| term.value == nft.lockup_term.value),
+            nft.end_time,
+            nft.token_id.clone(),
+        )
+    });
+
+    Ok(staked_info)
+}
+
+fn query_nfts(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<NftInfo>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    // load a page of nfts
+    let nfts = staked_nfts()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, nft_info)| nft_info))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // only calc pending reward when there is an active nft in the page
+    if nfts.iter().all(|nft| nft.is_end_reward) {
+        return Ok(nfts);
+    }
+
+    // pending reward is only calculated for nfts of the page
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+    let accrual = query_reward_accrual(deps, &campaign_info, env.block.time.seconds())?;
+
+    nfts.into_iter()
+        .map(|nft| accrued_nft(deps.storage, &accrual, nft))
+        .collect()
 }
 
 fn query_total_pending_reward(deps: Deps, env: Env) -> Result<Uint128, ContractError> {
@@ -1466,96 +2989,469 @@ fn query_total_pending_reward(deps: Deps, env: Env) -> Result<Uint128, ContractE
     // total = pending in nft + pending in staker
     let mut total_pending_reward: Uint128 = Uint128::zero();
 
-    // max time to calc = campaign_info.end_time
-    let mut current_time = env.block.time.seconds();
-    if campaign_info.end_time < env.block.time.seconds() {
-        current_time = campaign_info.end_time;
+    // pending reward in active nft
+    for nft in query_active_nfts(deps, &env, &campaign_info)? {
+        total_pending_reward = add_reward(total_pending_reward, nft.pending_reward)?;
     }
-    // update pending reward for all nft
-    let terms = campaign_info.clone().lockup_term;
-    let time_calc_nft = campaign_info.time_calc_nft;
-    let reward_per_second = campaign_info.reward_per_second;
 
-    // load nfts
-    let mut nfts_load = Vec::new();
-    let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
+    // pending reward in nft is end reward
+    let nfts_storage = staked_nfts().range(deps.storage, None, None, Order::Ascending);
     for item in nfts_storage {
         let (_, nft_info) = item?;
-        nfts_load.push(nft_info);
-    }
-
-    for term in terms {
-        let mut nft_list = nfts_load
-            .clone()
-            .into_iter()
-            .filter(|nft| nft.lockup_term.value == term.value)
-            .collect::<Vec<_>>();
-        nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
-
-        let mut time_calc: u64 = time_calc_nft;
-        let mut nft_count = nft_list
-            .clone()
-            .into_iter()
-            .filter(|nft| !nft.is_end_reward)
-            .collect::<Vec<_>>()
-            .len() as u128;
-        let mut reward = Uint128::zero();
-        for nft in nft_list.iter_mut() {
-            if !nft.is_end_reward {
-                if nft.end_time <= current_time {
-                    // calc in time_calc -> nft.end_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        nft.end_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // increase reward for next nft
-                    reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                    nft_count -= 1; // update count nft for next calc reward
-                    time_calc = nft.end_time; // update time_calc
-                    nft.is_end_reward = true; // nft stake timeout
-                } else {
-                    // calc in time_calc -> current_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        current_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // add reward previous and current reward
-                    let accumulate_reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, accumulate_reward).unwrap()
-                }
-            }
-            // pending reward in nft
-            total_pending_reward = add_reward(total_pending_reward, nft.pending_reward).unwrap();
+        if nft_info.is_end_reward {
+            total_pending_reward = add_reward(total_pending_reward, nft_info.pending_reward)?;
         }
     }
+
     // get pending reward in staker
     let stakers_info = STAKERS_INFO.range(deps.storage, None, None, Order::Ascending);
     for item in stakers_info {
         let (_, value) = item?;
-        total_pending_reward = add_reward(total_pending_reward, value.reward_debt).unwrap();
+        total_pending_reward = add_reward(total_pending_reward, value.reward_debt)?;
     }
 
     Ok(total_pending_reward)
 }
 
-fn query_token_ids(deps: Deps) -> Result<Vec<String>, ContractError> {
-    let token_ids = TOKEN_IDS.load(deps.storage)?;
+fn query_token_ids(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<String>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let start = start_after.map(Bound::exclusive);
+
+    let token_ids = staked_nfts()
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
 
     Ok(token_ids)
 }
+
+fn query_stakers(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<StakerInfoResult>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = match start_after {
+        Some(addr) => Some(Bound::exclusive(deps.api.addr_validate(&addr)?)),
+        None => None,
+    };
+
+    // load a page of stakers
+    let stakers = STAKERS_INFO
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // pending reward is only calculated for nfts of stakers in the page
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+    let accrual = query_reward_accrual(deps, &campaign_info, env.block.time.seconds())?;
+
+    let mut result = vec![];
+    for (owner, staker_asset) in stakers {
+        // reward_debt includes pending reward in staked nfts
+        let mut reward_debt = staker_asset.reward_debt;
+        let mut token_ids = vec![];
+        for nft in load_staker_nfts(deps.storage, &owner)? {
+            let nft = accrued_nft(deps.storage, &accrual, nft)?;
+            reward_debt = add_reward(reward_debt, nft.pending_reward)?;
+            if token_ids.len() < MAX_LIMIT as usize {
+                token_ids.push(nft.token_id);
+            }
+        }
+
+        result.push(StakerInfoResult {
+            owner,
+            token_ids,
+            reward_debt,
+            reward_claimed: staker_asset.reward_claimed,
+        });
+    }
+
+    Ok(result)
+}
+
+fn query_staker_dashboard(
+    deps: Deps,
+    env: Env,
+    owner: Addr,
+) -> Result<StakerDashboardResult, ContractError> {
+    let staker_asset: StakerRewardAssetInfo = STAKERS_INFO
+        .may_load(deps.storage, owner.clone())?
+        .unwrap_or(StakerRewardAssetInfo {
+            reward_debt: Uint128::zero(),
+            reward_claimed: Uint128::zero(),
+        });
+
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+    let current_time = min(env.block.time.seconds(), campaign_info.end_time);
+
+    // pending reward of nfts at current time
+    let accrual = query_reward_accrual(deps, &campaign_info, env.block.time.seconds())?;
+
+    // project pending reward of running nfts to the end of campaign,
+    // each nft stops at its end_time so this is the reward at unlock time
+    let mut projection = accrual.clone();
+    accrue_reward(
+        deps.storage,
+        &campaign_info,
+        &mut projection,
+        campaign_info.end_time,
+        None,
+    )?;
+
+    let mut dashboard = StakerDashboardResult {
+        owner: owner.clone(),
+        nfts: vec![],
+        reward_debt: staker_asset.reward_debt,
+        reward_claimed: staker_asset.reward_claimed,
+        reward_per_second: Uint128::zero(),
+        claimable_reward: staker_asset.reward_debt,
+        projected_reward: staker_asset.reward_debt,
+    };
+
+    for staked_nft in load_staker_nfts(deps.storage, &owner)? {
+        let nft = accrued_nft(deps.storage, &accrual, staked_nft.clone())?;
+        let unlock_time = if nft.lockup_term.is_flexible() {
+            nft.start_time
+        } else {
+            min(nft.end_time, campaign_info.end_time)
+        };
+
+        // reward of term is shared between its running nfts by weight
+        let (reward_per_second, projected_reward) = if nft.is_end_reward {
+            (Uint128::zero(), nft.pending_reward)
+        } else {
+            let total_weight = accrual.term_states[&nft.lockup_term.value]
+                .total_weight
+                .u128();
+            let reward_per_second = weighted_reward(
+                calc_weighted_reward_in_time(
+                    0,
+                    1,
+                    campaign_info.reward_per_second,
+                    nft.lockup_term.percent,
+                    total_weight,
+                )?,
+                &nft,
+            )?;
+            let projected_reward =
+                accrued_nft(deps.storage, &projection, staked_nft)?.pending_reward;
+            (reward_per_second, projected_reward)
+        };
+
+        dashboard.reward_per_second = add_reward(dashboard.reward_per_second, reward_per_second)?;
+        dashboard.claimable_reward = add_reward(dashboard.claimable_reward, nft.pending_reward)?;
+        dashboard.projected_reward = add_reward(dashboard.projected_reward, projected_reward)?;
+        dashboard.nfts.push(NftDashboard {
+            token_id: nft.token_id,
+            lockup_term: nft.lockup_term,
+            is_end_reward: nft.is_end_reward,
+            unlock_time,
+            time_until_unlock: unlock_time.saturating_sub(current_time),
+            reward_per_second,
+            pending_reward: nft.pending_reward,
+            projected_reward,
+        });
+    }
+
+    Ok(dashboard)
+}
+
+// simulation time must not be before current time, default is current time
+fn simulation_time(env: &Env, at_time: Option<u64>) -> Result<u64, ContractError> {
+    let current_time = env.block.time.seconds();
+    match at_time {
+        Some(at_time) if at_time < current_time => Err(ContractError::InvalidTimeToSimulate {}),
+        Some(at_time) => Ok(at_time),
+        None => Ok(current_time),
+    }
+}
+
+fn query_simulate_stake(
+    deps: Deps,
+    env: Env,
+    nfts: Vec<NftStake>,
+    at_time: Option<u64>,
+) -> Result<Vec<SimulateStakeResult>, ContractError> {
+    let at_time = simulation_time(&env, at_time)?;
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+
+    let current_time = env.block.time.seconds();
+
+    // the reward token must be added to campaign before staking nft
+    if campaign_info.reward_token.amount == Uint128::zero() {
+        return Err(ContractError::EmptyReward {});
+    }
+
+    // only start_time < current_time && current_time < end_time can stake nft
+    if campaign_info.start_time >= current_time || campaign_info.end_time <= current_time {
+        return Err(ContractError::InvalidTimeToStakeNft {});
+    }
+
+    // update pending reward for previous staking nft like execute_stake_nft
+    let mut accrual = query_reward_accrual(deps, &campaign_info, current_time)?;
+    accrual.time = current_time;
+
+    // staker is unknown, only token weight is applied
+    let merkle_roots = MERKLE_ROOTS.may_load(deps.storage)?.unwrap_or_default();
+    for (i, nft) in nfts.iter().enumerate() {
+        if nfts[..i].iter().any(|n| n.token_id == nft.token_id)
+            || staked_nfts().has(deps.storage, nft.token_id.clone())
+        {
+            return Err(ContractError::DuplicateTokenId {
+                token_id: nft.token_id.clone(),
+            });
+        }
+
+        // check invalid lockup_term
+        let lockup_term = campaign_info
+            .lockup_term
+            .iter()
+            .find(|term| term.value == nft.lockup_term)
+            .cloned()
+            .ok_or(ContractError::InvalidLockupTerm {})?;
+        if is_term_full(deps.storage, &lockup_term)? {
+            return Err(ContractError::LockupTermFull {
+                lockup_term: lockup_term.value,
+            });
+        }
+
+        let scratch_nft = NftInfo {
+            token_id: nft.token_id.clone(),
+            owner: env.contract.address.clone(),
+            pending_reward: Uint128::zero(),
+            lockup_term: lockup_term.clone(),
+            is_end_reward: false,
+            start_time: current_time,
+            end_time: nft_end_time(&campaign_info, &lockup_term, current_time),
+            weight: nft_weight(&merkle_roots, nft, BASE_WEIGHT)?,
+        };
+        let state = accrual.term_states.entry(lockup_term.value).or_default();
+        state.total_weight += Uint128::from(scratch_nft.weight);
+        state.nft_count += 1;
+        let reward_index = state.reward_index;
+        accrual
+            .scratch_nfts
+            .insert(nft.token_id.clone(), (scratch_nft, reward_index));
+    }
+
+    // calculate pending reward of scratch nfts at simulation time
+    accrue_reward(deps.storage, &campaign_info, &mut accrual, at_time, None)?;
+
+    let mut result = vec![];
+    for nft in nfts.iter() {
+        let (scratch_nft, _) = accrual.scratch_nfts[&nft.token_id].clone();
+        let scratch_nft = accrued_nft(deps.storage, &accrual, scratch_nft)?;
+        result.push(SimulateStakeResult {
+            token_id: scratch_nft.token_id,
+            lockup_term: scratch_nft.lockup_term,
+            start_time: scratch_nft.start_time,
+            end_time: scratch_nft.end_time,
+            pending_reward: scratch_nft.pending_reward,
+        });
+    }
+
+    Ok(result)
+}
+
+fn query_simulate_unstake(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    at_time: Option<u64>,
+) -> Result<SimulateUnstakeResult, ContractError> {
+    let at_time = simulation_time(&env, at_time)?;
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+
+    let nft_info = match staked_nfts().may_load(deps.storage, token_id.clone())? {
+        Some(nft_info) => nft_info,
+        None => return Err(ContractError::EmptyNft { token_id }),
+    };
+
+    // update pending reward for staking nft at simulation time like execute_unstake_nft
+    let accrual = query_reward_accrual(deps, &campaign_info, at_time)?;
+    let nft_info = accrued_nft(deps.storage, &accrual, nft_info)?;
+
+    let staker = STAKERS_INFO.load(deps.storage, nft_info.owner.clone())?;
+    let staking_rules = STAKING_RULES.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(SimulateUnstakeResult {
+        token_id,
+        owner: nft_info.owner,
+        can_unstake: (nft_info.is_end_reward || nft_info.lockup_term.is_flexible())
+            && at_time >= nft_info.start_time + staking_rules.min_hold_time,
+        reward: nft_info.pending_reward,
+        reward_debt: add_reward(staker.reward_debt, nft_info.pending_reward)?,
+    })
+}
+
+fn query_simulate_claim(
+    deps: Deps,
+    env: Env,
+    owner: Addr,
+    at_time: Option<u64>,
+) -> Result<SimulateClaimResult, ContractError> {
+    let at_time = simulation_time(&env, at_time)?;
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+
+    // Only stakers could claim rewards in this campaign
+    let staker_info = match STAKERS_INFO.may_load(deps.storage, owner.clone())? {
+        Some(staker_info) => staker_info,
+        None => return Err(ContractError::InvalidClaim {}),
+    };
+
+    // update pending reward for staking nft at simulation time like execute_claim_reward
+    let accrual = query_reward_accrual(deps, &campaign_info, at_time)?;
+
+    // pending reward in nft is transferred to staker
+    let mut claimable_reward = staker_info.reward_debt;
+    for nft in load_staker_nfts(deps.storage, &owner)? {
+        let nft = accrued_nft(deps.storage, &accrual, nft)?;
+        claimable_reward = add_reward(claimable_reward, nft.pending_reward)?;
+    }
+
+    Ok(SimulateClaimResult {
+        owner,
+        claimable_reward,
+    })
+}
+
+fn query_reward_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<Vec<RewardSnapshot>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let snapshots = REWARD_HISTORY
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, snapshot)| snapshot))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(snapshots)
+}
+
+fn query_staker_ledger(
+    deps: Deps,
+    owner: Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<Vec<LedgerEntry>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let entries = STAKER_LEDGER
+        .prefix(owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, entry)| entry))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(entries)
+}
+
+fn query_reward_recipient(deps: Deps, owner: Addr) -> Result<Addr, ContractError> {
+    Ok(REWARD_RECIPIENTS
+        .may_load(deps.storage, owner.clone())?
+        .unwrap_or(owner))
+}
+
+fn query_operators(
+    deps: Deps,
+    env: Env,
+    owner: Addr,
+    include_expired: bool,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<OperatorsResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|operator| Bound::ExclusiveRaw(operator.into_bytes()));
+
+    let operators = OPERATORS
+        .prefix(owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| {
+            include_expired || matches!(item, Ok((_, expires)) if !expires.is_expired(&env.block))
+        })
+        .take(limit)
+        .map(|item| {
+            item.map(|(spender, expires)| Approval {
+                spender: spender.to_string(),
+                expires,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(OperatorsResponse { operators })
+}
+
+fn query_unbonding_nfts(deps: Deps, owner: Addr) -> Result<Vec<UnbondingNft>, ContractError> {
+    let nfts = unbonding_nfts()
+        .idx
+        .owner
+        .prefix(owner)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, nft)| nft))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(nfts)
+}
+
+fn query_token_deposits(deps: Deps, owner: Addr) -> Result<Vec<TokenDeposit>, ContractError> {
+    // deposits of owner are staked or unbonding
+    let mut token_ids = load_staker_nfts(deps.storage, &owner)?
+        .into_iter()
+        .map(|nft| nft.token_id)
+        .collect::<Vec<_>>();
+    token_ids.extend(
+        unbonding_nfts()
+            .idx
+            .owner
+            .prefix(owner)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, nft)| nft.token_id))
+            .collect::<StdResult<Vec<_>>>()?,
+    );
+
+    let mut deposits = vec![];
+    for token_id in token_ids {
+        if let Some(amount) = TOKEN_DEPOSITS.may_load(deps.storage, token_id.clone())? {
+            deposits.push(TokenDeposit { token_id, amount });
+        }
+    }
+    Ok(deposits)
+}
+
+fn query_eligibility(
+    deps: Deps,
+    staker: Addr,
+    token_ids: Vec<String>,
+) -> Result<EligibilityResult, ContractError> {
+    let config = ELIGIBILITY_CONFIG
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+
+    let token_ids = token_ids
+        .into_iter()
+        .map(|token_id| {
+            Ok(TokenEligibility {
+                eligible: is_eligible_token_id(deps.storage, &config, &token_id)?,
+                token_id,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(EligibilityResult {
+        staker_eligible: is_eligible_staker(deps.storage, &config, &staker)?,
+        staker,
+        token_ids,
+    })
+}
@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{ConversionOverflowError, DivideByZeroError, OverflowError, StdError};
+use hex::FromHexError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,6 +7,15 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    DivideByZero(#[from] DivideByZeroError),
+
+    #[error("{0}")]
+    ConversionOverflow(#[from] ConversionOverflowError),
+
     #[error("##Unauthorized##")]
     Unauthorized {},
 
@@ -30,6 +40,21 @@ pub enum ContractError {
     #[error("## Invalid LockupTerm ##")]
     InvalidLockupTerm {},
 
+    #[error("## Duplicate lockup term {lockup_term} ##")]
+    DuplicateLockupTerm { lockup_term: u64 },
+
+    #[error("## Total percent of lockup terms must be at most 100 ##")]
+    InvalidTermPercent {},
+
+    #[error("## Invalid term policy ##")]
+    InvalidTermPolicy {},
+
+    #[error("## Invalid set bonus ##")]
+    InvalidSetBonus {},
+
+    #[error("## Lockup term {lockup_term} is full ##")]
+    LockupTermFull { lockup_term: u64 },
+
     #[error("## Insufficient balance ##")]
     InsufficientBalance {},
 
@@ -63,6 +88,72 @@ pub enum ContractError {
     #[error("## Empty reward pool ##")]
     EmptyReward {},
 
+    #[error("## Simulation time must not be before current time ##")]
+    InvalidTimeToSimulate {},
+
+    #[error("## Expiration must be in the future ##")]
+    InvalidExpiration {},
+
+    #[error("## No nft to stake ##")]
+    EmptyNfts {},
+
+    #[error("## Staker has not registered a reward recipient ##")]
+    EmptyRewardRecipient {},
+
+    #[error("## Nft must be staked until {unlock_time:?} ##")]
+    MinHoldTime { unlock_time: u64 },
+
+    #[error("## Nft {token_id:?} cannot be staked again until {restake_time:?} ##")]
+    RestakeCooldown { token_id: String, restake_time: u64 },
+
+    #[error("## Nft {token_id:?} is unbonding until {release_time:?} ##")]
+    Unbonding { token_id: String, release_time: u64 },
+
+    #[error("## Staker is not eligible for this campaign ##")]
+    IneligibleStaker {},
+
+    #[error("## Nft {token_id:?} is not eligible for this campaign ##")]
+    IneligibleNft { token_id: String },
+
+    #[error("{0}")]
+    Hex(#[from] FromHexError),
+
+    #[error("## Merkle proof verification failed ##")]
+    VerificationFailed {},
+
+    #[error("## Weight and bonus multiplier must be greater than 0 ##")]
+    InvalidWeight {},
+
+    #[error("## Weight overflows u64 ##")]
+    WeightOverflow {},
+
+    #[error("## Token staking is not enabled in this campaign ##")]
+    TokenStakingDisabled {},
+
+    #[error("## Amount per nft of token staking must be greater than 0 ##")]
+    InvalidTokenStaking {},
+
+    #[error("## Only soft staked nfts can be slashed ##")]
+    NotSoftStaked {},
+
+    #[error("## Nft {token_id:?} is still owned by its staker ##")]
+    NotSlashable { token_id: String },
+
+    #[error("## Send receipt of {token_id:?} to unstake ##")]
+    ReceiptRequired { token_id: String },
+
+    #[error("## Receipts are not issued for soft staked nfts ##")]
+    InvalidReceipt {},
+
     #[error("## Empty token_id: {token_id:?} ##")]
-    EmptyNft { token_id: String},
+    EmptyNft { token_id: String },
+
+    #[error("## Duplicate token_id: {token_id:?} ##")]
+    DuplicateTokenId { token_id: String },
+
+    #[error("## Token_id {token_id:?} is reserved for token deposits ##")]
+    ReservedTokenId { token_id: String },
+
+    #[error("## Limit must be greater than 0 ##")]
+    InvalidLimit {},
 }
@@ -0,0 +1,364 @@
+//! Custom events emitted by the stakify contracts.
+//!
+//! Every event is created with `Event::new("stakify-<name>")`, the chain prefixes it with
+//! `wasm-` so indexers receive `wasm-stakify-<name>`. Each event carries a `schema_version`
+//! attribute and always emits every key of its schema, keys are never renamed or removed.
+//!
+//! Schema version 1:
+//!
+//! - `wasm-stakify-instantiate`: owner, campaign_name, allowed_collection, reward_token,
+//!   limit_per_staker, lockup_terms, start_time, end_time
+//! - `wasm-stakify-add-reward`: owner, reward_token, amount, total_reward, reward_per_second
+//! - `wasm-stakify-stake` (one per nft): staker, token_id, term, start_time, end_time,
+//!   weight
+//! - `wasm-stakify-stake-tokens` (one per deposit): staker, token_id, token, amount, term,
+//!   start_time, end_time, weight
+//! - `wasm-stakify-unstake`: staker, token_id, term, amount, release_time
+//! - `wasm-stakify-transfer-position`: token_id, from, to
+//! - `wasm-stakify-set-receipt-collection`: receipt_collection
+//! - `wasm-stakify-slash`: staker, token_id, term, amount, slasher
+//! - `wasm-stakify-claim`: staker, recipient, reward_token, amount
+//! - `wasm-stakify-withdraw-reward`: owner, reward_token, amount
+//! - `wasm-stakify-update-campaign`: owner, campaign_name, limit_per_staker, lockup_terms,
+//!   start_time, end_time, reward_per_second
+//! - `wasm-stakify-set-reward-recipient`: staker, recipient
+//! - `wasm-stakify-approve-all`: staker, operator, expires
+//! - `wasm-stakify-revoke-all`: staker, operator
+//! - `wasm-stakify-settle`: checkpoint, remaining
+//! - `wasm-stakify-update-staking-rules`: min_hold_time, restake_cooldown, unbonding_period
+//! - `wasm-stakify-withdraw-nft`: staker, token_id
+//! - `wasm-stakify-update-eligibility`: token_allowlist_enabled, staker_allowlist_enabled,
+//!   allowed_token_ranges, denied_token_ranges
+//! - `wasm-stakify-update-token-id-list`: list, added, removed
+//! - `wasm-stakify-update-staker-list`: list, added, removed
+//! - `wasm-stakify-register-merkle-roots`: staker_bonus_root, token_weight_root
+//! - `wasm-stakify-update-set-bonuses`: set_bonuses
+//! - `wasm-stakify-factory-update-config`: owner, campaign_code_id, min_term, max_term,
+//!   allowed_terms, allow_flexible, receipt_code_id, max_active_campaigns
+//! - `wasm-stakify-factory-create-campaign`: owner, campaign_name, allowed_collection,
+//!   reward_token, start_time, end_time, verified_reward_token, verified_collection
+//! - `wasm-stakify-factory-campaign-created`: campaign_key, campaign_addr, owner
+//! - `wasm-stakify-factory-receipt-created`: campaign_addr, receipt_collection
+//! - `wasm-stakify-factory-register-nfts`: campaign_addr, token_ids
+//! - `wasm-stakify-factory-unregister-nfts`: campaign_addr, token_ids
+//! - `wasm-stakify-factory-set-governance`: group_addr, quorum, voting_period, timelock
+//! - `wasm-stakify-factory-propose`: proposal_id, proposer, expires_at
+//! - `wasm-stakify-factory-vote`: proposal_id, voter, weight, yes_weight, passed
+//! - `wasm-stakify-factory-execute-proposal`: proposal_id
+//! - `wasm-stakify-factory-update-verified-assets`: require_verified, added, removed
+//! - `wasm-stakify-factory-set-creator-profile`: creator, display_name, verified
+//! - `wasm-stakify-factory-claim-many`: claimer, campaign_ids
+//! - `wasm-stakify-factory-claim-result` (one per campaign): campaign_id, success, error
+//!
+//! `term` is the lockup term value in seconds, `amount` is a raw `Uint128` of the reward token,
+//! `lockup_terms` is a comma separated list of `<value>:<percent>`, `expires` is the `Display`
+//! of cw721 `Expiration`, `checkpoint` is the time reward is accrued until and `remaining` the
+//! number of expired nfts left for the next `Settle`, counted up to its limit. `release_time` is
+//! when the unstaked nft is returned, equal to the unstake time if there is no unbonding period.
+//! Token_id ranges are a comma separated list of `<min>-<max>`, `list` is `allow` or `deny`,
+//! `added` and `removed` are comma separated token_ids or addresses. Attribute values must not
+//! be empty, so empty lists and unset values are `none`: unregistered merkle roots,
+//! `receipt_code_id` if receipts are disabled, `display_name` of a creator verified without a
+//! profile and `error` of a successful claim. `max_active_campaigns` 0 is unlimited.
+//! A position with a receipt belongs to the holder of the receipt with the same `token_id`,
+//! `transfer-position` is emitted when it follows its receipt.
+//! The `token_id` of a token deposit is `cw20:<n>` and its `amount` is of the staked `token`.
+//! `weight` is the share of the nft in its term reward, 100 is the base weight. `set_bonuses` is
+//! a comma separated list of `<rule>:<multiplier>`, the rule is `nfts=<count>` or
+//! `traits=<trait_type>+<trait_type>`.
+
+use cosmwasm_std::{Addr, Event, Uint128};
+use cw721::Expiration;
+
+use crate::state::{
+    CampaignInfo, EligibilityConfig, ListKind, LockupTerm, MerkleRoots, NftInfo, SetBonus, SetRule,
+    StakingRules, TokenIdRange, TokenInfo, UnbondingNft,
+};
+
+pub const EVENT_SCHEMA_VERSION: &str = "1";
+pub const EVENT_PREFIX: &str = "stakify-";
+
+// base event of every action, shared with the factory contract
+pub fn stakify_event(name: &str) -> Event {
+    Event::new(format!("{}{}", EVENT_PREFIX, name))
+        .add_attribute("schema_version", EVENT_SCHEMA_VERSION)
+}
+
+fn lockup_terms_attr(lockup_term: &[LockupTerm]) -> String {
+    lockup_term
+        .iter()
+        .map(|term| format!("{}:{}", term.value, term.percent))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+pub fn instantiate_event(campaign_info: &CampaignInfo) -> Event {
+    stakify_event("instantiate").add_attributes([
+        ("owner", campaign_info.owner.to_string()),
+        ("campaign_name", campaign_info.campaign_name.clone()),
+        (
+            "allowed_collection",
+            campaign_info.allowed_collection.to_string(),
+        ),
+        ("reward_token", campaign_info.reward_token.info.to_string()),
+        (
+            "limit_per_staker",
+            campaign_info.limit_per_staker.to_string(),
+        ),
+        (
+            "lockup_terms",
+            lockup_terms_attr(&campaign_info.lockup_term),
+        ),
+        ("start_time", campaign_info.start_time.to_string()),
+        ("end_time", campaign_info.end_time.to_string()),
+    ])
+}
+
+pub fn add_reward_event(campaign_info: &CampaignInfo, amount: Uint128) -> Event {
+    stakify_event("add-reward").add_attributes([
+        ("owner", campaign_info.owner.to_string()),
+        ("reward_token", campaign_info.reward_token.info.to_string()),
+        ("amount", amount.to_string()),
+        ("total_reward", campaign_info.total_reward.to_string()),
+        (
+            "reward_per_second",
+            campaign_info.reward_per_second.to_string(),
+        ),
+    ])
+}
+
+pub fn stake_event(nft_info: &NftInfo) -> Event {
+    stakify_event("stake").add_attributes([
+        ("staker", nft_info.owner.to_string()),
+        ("token_id", nft_info.token_id.clone()),
+        ("term", nft_info.lockup_term.value.to_string()),
+        ("start_time", nft_info.start_time.to_string()),
+        ("end_time", nft_info.end_time.to_string()),
+        ("weight", nft_info.weight.to_string()),
+    ])
+}
+
+pub fn stake_tokens_event(nft_info: &NftInfo, token: &Addr, amount: Uint128) -> Event {
+    stakify_event("stake-tokens").add_attributes([
+        ("staker", nft_info.owner.to_string()),
+        ("token_id", nft_info.token_id.clone()),
+        ("token", token.to_string()),
+        ("amount", amount.to_string()),
+        ("term", nft_info.lockup_term.value.to_string()),
+        ("start_time", nft_info.start_time.to_string()),
+        ("end_time", nft_info.end_time.to_string()),
+        ("weight", nft_info.weight.to_string()),
+    ])
+}
+
+pub fn unstake_event(nft_info: &NftInfo, release_time: u64) -> Event {
+    stakify_event("unstake").add_attributes([
+        ("staker", nft_info.owner.to_string()),
+        ("token_id", nft_info.token_id.clone()),
+        ("term", nft_info.lockup_term.value.to_string()),
+        ("amount", nft_info.pending_reward.to_string()),
+        ("release_time", release_time.to_string()),
+    ])
+}
+
+pub fn claim_event(
+    staker: &Addr,
+    recipient: &Addr,
+    reward_token: &TokenInfo,
+    amount: Uint128,
+) -> Event {
+    stakify_event("claim").add_attributes([
+        ("staker", staker.to_string()),
+        ("recipient", recipient.to_string()),
+        ("reward_token", reward_token.to_string()),
+        ("amount", amount.to_string()),
+    ])
+}
+
+pub fn withdraw_reward_event(campaign_info: &CampaignInfo, amount: Uint128) -> Event {
+    stakify_event("withdraw-reward").add_attributes([
+        ("owner", campaign_info.owner.to_string()),
+        ("reward_token", campaign_info.reward_token.info.to_string()),
+        ("amount", amount.to_string()),
+    ])
+}
+
+pub fn update_campaign_event(campaign_info: &CampaignInfo) -> Event {
+    stakify_event("update-campaign").add_attributes([
+        ("owner", campaign_info.owner.to_string()),
+        ("campaign_name", campaign_info.campaign_name.clone()),
+        (
+            "limit_per_staker",
+            campaign_info.limit_per_staker.to_string(),
+        ),
+        (
+            "lockup_terms",
+            lockup_terms_attr(&campaign_info.lockup_term),
+        ),
+        ("start_time", campaign_info.start_time.to_string()),
+        ("end_time", campaign_info.end_time.to_string()),
+        (
+            "reward_per_second",
+            campaign_info.reward_per_second.to_string(),
+        ),
+    ])
+}
+
+pub fn set_reward_recipient_event(staker: &Addr, recipient: &Addr) -> Event {
+    stakify_event("set-reward-recipient").add_attributes([
+        ("staker", staker.to_string()),
+        ("recipient", recipient.to_string()),
+    ])
+}
+
+pub fn approve_all_event(staker: &Addr, operator: &Addr, expires: &Expiration) -> Event {
+    stakify_event("approve-all").add_attributes([
+        ("staker", staker.to_string()),
+        ("operator", operator.to_string()),
+        ("expires", expires.to_string()),
+    ])
+}
+
+pub fn revoke_all_event(staker: &Addr, operator: &Addr) -> Event {
+    stakify_event("revoke-all").add_attributes([
+        ("staker", staker.to_string()),
+        ("operator", operator.to_string()),
+    ])
+}
+
+pub fn settle_event(checkpoint: u64, remaining: u64) -> Event {
+    stakify_event("settle").add_attributes([
+        ("checkpoint", checkpoint.to_string()),
+        ("remaining", remaining.to_string()),
+    ])
+}
+
+pub fn update_staking_rules_event(staking_rules: &StakingRules) -> Event {
+    stakify_event("update-staking-rules").add_attributes([
+        ("min_hold_time", staking_rules.min_hold_time.to_string()),
+        (
+            "restake_cooldown",
+            staking_rules.restake_cooldown.to_string(),
+        ),
+        (
+            "unbonding_period",
+            staking_rules.unbonding_period.to_string(),
+        ),
+    ])
+}
+
+pub fn transfer_position_event(token_id: &str, from: &Addr, to: &Addr) -> Event {
+    stakify_event("transfer-position").add_attributes([
+        ("token_id", token_id.to_string()),
+        ("from", from.to_string()),
+        ("to", to.to_string()),
+    ])
+}
+
+pub fn set_receipt_collection_event(receipt_collection: &Addr) -> Event {
+    stakify_event("set-receipt-collection")
+        .add_attribute("receipt_collection", receipt_collection.to_string())
+}
+
+pub fn slash_event(nft_info: &NftInfo, slasher: &Addr) -> Event {
+    stakify_event("slash").add_attributes([
+        ("staker", nft_info.owner.to_string()),
+        ("token_id", nft_info.token_id.clone()),
+        ("term", nft_info.lockup_term.value.to_string()),
+        ("amount", nft_info.pending_reward.to_string()),
+        ("slasher", slasher.to_string()),
+    ])
+}
+
+pub fn withdraw_nft_event(unbonding_nft: &UnbondingNft) -> Event {
+    stakify_event("withdraw-nft").add_attributes([
+        ("staker", unbonding_nft.owner.to_string()),
+        ("token_id", unbonding_nft.token_id.clone()),
+    ])
+}
+
+fn token_ranges_attr(ranges: &[TokenIdRange]) -> String {
+    ranges
+        .iter()
+        .map(|range| format!("{}-{}", range.min, range.max))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// attribute values must not be empty, empty lists and unset values are emitted as "none"
+pub fn or_none(value: String) -> String {
+    if value.is_empty() {
+        "none".to_string()
+    } else {
+        value
+    }
+}
+
+pub fn update_eligibility_event(config: &EligibilityConfig) -> Event {
+    stakify_event("update-eligibility").add_attributes([
+        (
+            "token_allowlist_enabled",
+            config.token_allowlist_enabled.to_string(),
+        ),
+        (
+            "staker_allowlist_enabled",
+            config.staker_allowlist_enabled.to_string(),
+        ),
+        (
+            "allowed_token_ranges",
+            or_none(token_ranges_attr(&config.allowed_token_ranges)),
+        ),
+        (
+            "denied_token_ranges",
+            or_none(token_ranges_attr(&config.denied_token_ranges)),
+        ),
+    ])
+}
+
+pub fn update_list_event(name: &str, list: &ListKind, add: &[String], remove: &[String]) -> Event {
+    let list = match list {
+        ListKind::Allow => "allow",
+        ListKind::Deny => "deny",
+    };
+    stakify_event(name).add_attributes([
+        ("list", list.to_string()),
+        ("added", or_none(add.join(","))),
+        ("removed", or_none(remove.join(","))),
+    ])
+}
+
+pub fn register_merkle_roots_event(merkle_roots: &MerkleRoots) -> Event {
+    stakify_event("register-merkle-roots").add_attributes([
+        (
+            "staker_bonus_root",
+            or_none(merkle_roots.staker_bonus_root.clone().unwrap_or_default()),
+        ),
+        (
+            "token_weight_root",
+            or_none(merkle_roots.token_weight_root.clone().unwrap_or_default()),
+        ),
+    ])
+}
+
+fn set_rule_attr(rule: &SetRule) -> String {
+    match rule {
+        SetRule::Count { count } => format!("nfts={}", count),
+        SetRule::Traits { groups } => format!(
+            "traits={}",
+            groups
+                .iter()
+                .map(|group| group.trait_type.clone())
+                .collect::<Vec<_>>()
+                .join("+")
+        ),
+    }
+}
+
+pub fn update_set_bonuses_event(set_bonuses: &[SetBonus]) -> Event {
+    let set_bonuses = set_bonuses
+        .iter()
+        .map(|bonus| format!("{}:{}", set_rule_attr(&bonus.rule), bonus.multiplier))
+        .collect::<Vec<_>>()
+        .join(",");
+    stakify_event("update-set-bonuses").add_attribute("set_bonuses", or_none(set_bonuses))
+}
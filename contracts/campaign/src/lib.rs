@@ -1,8 +1,12 @@
 pub mod contract;
 mod error;
+pub mod events;
 pub mod msg;
 pub mod state;
 // mod contract_test;
 pub mod utils;
 
 pub use crate::error::ContractError;
+
+#[cfg(test)]
+mod tests;
@@ -1,8 +1,14 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20ReceiveMsg;
+use cw721::{Cw721ReceiveMsg, Expiration, OperatorsResponse};
 
 use crate::state::{
-    AssetToken, CampaignInfo, CampaignInfoUpdate, LockupTerm, NftInfo, NftStake, StakedInfoResult,
+    AssetToken, CampaignInfo, CampaignInfoUpdate, EligibilityConfig, EligibilityResult,
+    ExclusivityRules, LedgerEntry, ListKind, LockupTerm, MerkleRoots, NftInfo, NftStake,
+    RewardSnapshot, SetBonus, SimulateClaimResult, SimulateStakeResult, SimulateUnstakeResult,
+    StakedInfoResult, StakerBonusProof, StakerDashboardResult, StakerInfoResult, StakingRules,
+    TermPolicy, TokenDeposit, TokenIdRange, TokenStaking, UnbondingNft,
 };
 
 #[cw_serde]
@@ -16,10 +22,16 @@ pub struct InstantiateMsg {
     pub limit_per_staker: u64,
     pub reward_token_info: AssetToken, // reward token
     pub allowed_collection: String,    // staking collection nft
-    pub lockup_term: Vec<LockupTerm>,  // flexible, 15days, 30days, 60days
+    pub lockup_term: Vec<LockupTerm>,  // flexible (value 0), 15days, 30days, 60days
 
     pub start_time: u64, // start time must be from T + 1
     pub end_time: u64,   // max 3 years
+
+    pub term_policy: Option<TermPolicy>, // custom durations if not set
+    pub token_staking: Option<TokenStaking>, // cw20 token staked alongside nfts
+    pub factory: Option<String>,         // factory keeping registry of staked nfts
+    pub exclusivity: Option<ExclusivityRules>, // opt into exclusivity of factory registry
+    pub soft_staking: Option<bool>,      // nfts stay in wallets of stakers if true
 }
 
 #[cw_serde]
@@ -27,14 +39,19 @@ pub enum ExecuteMsg {
     AddRewardToken {
         amount: Uint128,
     },
-    // user can stake 1 or many nfts to this campaign
+    // user can stake 1 or many nfts to this campaign, bonus is required if staker bonus root is registered
     StakeNfts {
         nfts: Vec<NftStake>,
+        bonus: Option<StakerBonusProof>,
     },
 
-    // user can claim reward
+    // user can stake cw20 tokens by sending them with Cw20HookMsg
+    Receive(Cw20ReceiveMsg),
+
+    // user can claim reward, an operator claims on behalf of owner
     ClaimReward {
         amount: Uint128,
+        owner: Option<String>,
     },
 
     WithdrawReward {},
@@ -47,6 +64,120 @@ pub enum ExecuteMsg {
     UpdateCampaign {
         campaign_info_update: CampaignInfoUpdate,
     },
+
+    // claimed reward is sent to recipient, none resets it to the staker
+    SetRewardRecipient {
+        recipient: Option<String>,
+    },
+
+    // allow operator to claim and unstake on behalf of staker, like cw721 ApproveAll
+    ApproveAll {
+        operator: String,
+        expires: Option<Expiration>,
+    },
+
+    RevokeAll {
+        operator: String,
+    },
+
+    // replace set bonuses, only before reward is added
+    UpdateSetBonuses {
+        set_bonuses: Vec<SetBonus>,
+    },
+
+    // update min hold time, re-stake cooldown and unbonding period, only before reward is added
+    UpdateStakingRules {
+        staking_rules: StakingRules,
+    },
+
+    // return an unstaked nft to its owner at the end of unbonding period
+    WithdrawNft {
+        token_id: String,
+    },
+
+    // enable allowlists and replace token_id ranges, none keeps the current value
+    UpdateEligibility {
+        token_allowlist_enabled: Option<bool>,
+        staker_allowlist_enabled: Option<bool>,
+        allowed_token_ranges: Option<Vec<TokenIdRange>>,
+        denied_token_ranges: Option<Vec<TokenIdRange>>,
+    },
+
+    // add or remove explicit token_ids of allowlist or denylist
+    UpdateTokenIdList {
+        list: ListKind,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+
+    // add or remove stakers of allowlist or denylist
+    UpdateStakerList {
+        list: ListKind,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+
+    // register merkle roots of "address:bonus_multiplier" and "token_id:weight" leaves,
+    // only before reward is added
+    RegisterMerkleRoots {
+        staker_bonus_root: Option<String>,
+        token_weight_root: Option<String>,
+    },
+
+    // anyone can advance the reward checkpoint, settling at most limit expired nfts per call.
+    // limit must be greater than 0
+    Settle {
+        limit: Option<u32>,
+    },
+
+    // factory sets receipt collection minted by campaign once
+    SetReceiptCollection {
+        receipt_collection: String,
+    },
+
+    // holder of receipt unstakes, transfers or claims its position by sending the receipt with
+    // ReceiptHookMsg
+    ReceiveNft(Cw721ReceiveMsg),
+
+    // anyone can slash a soft staked nft which is no longer owned by its staker
+    Slash {
+        token_id: String,
+    },
+
+    // anyone can claim all reward of owner to its registered reward recipient,
+    // factory claims on behalf of owner without registered recipient
+    ClaimFor {
+        owner: String,
+    },
+}
+
+// callbacks to the factory keeping registry of staked nfts
+#[cw_serde]
+pub enum FactoryCallbackMsg {
+    RegisterNfts { token_ids: Vec<String> },
+    UnregisterNfts { token_ids: Vec<String> },
+    UpdateEndTime { end_time: u64 },
+    RecordClaim { amount: Uint128 },
+}
+
+#[cw_serde]
+pub enum ReceiptHookMsg {
+    // unstake the position of the received receipt
+    Unstake {},
+    // move the position of the received receipt to recipient, the receipt is sent to recipient
+    Transfer { recipient: String },
+    // move the position of the received receipt to its holder and claim all reward of the holder,
+    // the receipt is returned to the holder
+    Claim {},
+}
+
+#[cw_serde]
+pub enum Cw20HookMsg {
+    // stake the received tokens as one deposit, bonus proof is required if staker bonus root is registered
+    StakeTokens {
+        lockup_term: u64,
+        bonus: Option<StakerBonusProof>,
+    },
 }
 
 #[cw_serde]
@@ -64,14 +195,119 @@ pub enum QueryMsg {
     #[returns(StakedInfoResult)]
     NftStaked { owner: Addr },
 
+    // list staked nfts ordered by token_id, default 30 and max 100 items per page
     #[returns(Vec<NftInfo>)]
     Nfts {
+        start_after: Option<String>,
         limit: Option<u32>,
     },
 
     #[returns(Uint128)]
     TotalPendingReward {},
 
+    // list staked token_ids, default 30 and max 100 items per page
     #[returns(Vec<String>)]
-    TokenIds {},
+    TokenIds {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    // list stakers ordered by address, default 30 and max 100 items per page,
+    // each with its first 100 staked token_ids
+    #[returns(Vec<StakerInfoResult>)]
+    Stakers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    // staked nfts of owner with unlock time, accrual rate and projected reward
+    #[returns(StakerDashboardResult)]
+    StakerDashboard { owner: Addr },
+
+    // reward of nfts if they are staked now, calculated at at_time (default current time)
+    #[returns(Vec<SimulateStakeResult>)]
+    SimulateStake {
+        nfts: Vec<NftStake>,
+        at_time: Option<u64>,
+    },
+
+    // outcome of UnStakeNft at at_time (default current time)
+    #[returns(SimulateUnstakeResult)]
+    SimulateUnstake {
+        token_id: String,
+        at_time: Option<u64>,
+    },
+
+    // outcome of ClaimReward at at_time (default current time)
+    #[returns(SimulateClaimResult)]
+    SimulateClaim { owner: Addr, at_time: Option<u64> },
+
+    // reward snapshots ordered by time, default 30 and max 100 items per page
+    #[returns(Vec<RewardSnapshot>)]
+    RewardHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(StakingRules)]
+    StakingRules {},
+
+    #[returns(Vec<SetBonus>)]
+    SetBonuses {},
+
+    #[returns(TermPolicy)]
+    TermPolicy {},
+
+    // unbonding nfts of owner
+    #[returns(Vec<UnbondingNft>)]
+    UnbondingNfts { owner: Addr },
+
+    #[returns(MerkleRoots)]
+    MerkleRoots {},
+
+    #[returns(Option<TokenStaking>)]
+    TokenStaking {},
+
+    #[returns(Option<ExclusivityRules>)]
+    ExclusivityRules {},
+
+    #[returns(bool)]
+    SoftStaking {},
+
+    #[returns(Option<Addr>)]
+    ReceiptCollection {},
+
+    // token deposits of owner which are staked or unbonding
+    #[returns(Vec<TokenDeposit>)]
+    TokenDeposits { owner: Addr },
+
+    #[returns(EligibilityConfig)]
+    EligibilityConfig {},
+
+    // check if staker can stake token_ids
+    #[returns(EligibilityResult)]
+    Eligibility {
+        staker: Addr,
+        token_ids: Vec<String>,
+    },
+
+    #[returns(Addr)]
+    RewardRecipient { owner: Addr },
+
+    // operators of owner, default 30 and max 100 items per page
+    #[returns(OperatorsResponse)]
+    Operators {
+        owner: Addr,
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    // reward ledger of staker ordered by entry id, default 30 and max 100 items per page
+    #[returns(Vec<LedgerEntry>)]
+    StakerLedger {
+        owner: Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
@@ -1,8 +1,9 @@
 use std::fmt;
 
 use cosmwasm_schema::cw_serde; // attribute macro to (de)serialize and make schemas
-use cosmwasm_std::{Addr, Uint128}; // address type
-use cw_storage_plus::{Item, Map}; // analog of Singletons for storage
+use cosmwasm_std::{Addr, Uint128, Uint256}; // address type
+use cw721::Expiration;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex}; // analog of Singletons for storage
 
 #[cw_serde]
 pub enum TokenInfo {
@@ -19,6 +20,35 @@ impl fmt::Display for TokenInfo {
     }
 }
 
+#[cw_serde]
+pub struct Trait {
+    pub display_type: Option<String>,
+    pub trait_type: String,
+    pub value: String,
+}
+
+// see: https://docs.opensea.io/docs/metadata-standards
+#[cw_serde]
+#[derive(Default)]
+pub struct Metadata {
+    pub image: Option<String>,
+    pub image_data: Option<String>,
+    pub external_url: Option<String>,
+    pub description: Option<String>,
+    pub name: Option<String>,
+    pub attributes: Option<Vec<Trait>>,
+    pub background_color: Option<String>,
+    pub animation_url: Option<String>,
+    pub youtube_url: Option<String>,
+    /// This is how much the minter takes as a cut when sold
+    /// royalties are owed on this token if it is Some
+    pub royalty_percentage: Option<u64>,
+    /// The payment address, may be different to or the same
+    /// as the minter addr
+    /// question: how do we validate this?
+    pub royalty_payment_address: Option<String>,
+}
+
 #[cw_serde]
 pub struct AssetToken {
     pub info: TokenInfo,
@@ -31,27 +61,61 @@ impl fmt::Display for AssetToken {
     }
 }
 
-pub enum Term {
-    _15days,
-    _30days,
-    _60days,
+pub const MAX_TIME_VALID: u64 = 94608000; // 3 years
+
+// allowed durations of lockup terms, configured in factory
+#[cw_serde]
+pub struct TermPolicy {
+    pub min_term: u64,
+    pub max_term: u64,
+    pub allowed_terms: Vec<u64>, // any duration in [min_term, max_term] if empty
+    pub allow_flexible: bool,    // term with value 0
 }
 
-impl Term {
-    pub fn from_value(s: &u64) -> Option<Self> {
-        match s {
-            1296000 => Some(Term::_15days), // 86400 * 15
-            2592000 => Some(Term::_30days), // 86400 * 30
-            5184000 => Some(Term::_60days), // 86400 * 60
-            _ => None,
+impl Default for TermPolicy {
+    // custom durations up to the max duration of campaign
+    fn default() -> Self {
+        TermPolicy {
+            min_term: 1,
+            max_term: MAX_TIME_VALID,
+            allowed_terms: vec![],
+            allow_flexible: true,
         }
     }
 }
 
+impl TermPolicy {
+    pub fn is_valid(&self) -> bool {
+        self.min_term > 0
+            && self.min_term <= self.max_term
+            && self
+                .allowed_terms
+                .iter()
+                .all(|term| (self.min_term..=self.max_term).contains(term))
+    }
+
+    pub fn allows(&self, value: u64) -> bool {
+        if value == 0 {
+            return self.allow_flexible;
+        }
+        (self.min_term..=self.max_term).contains(&value)
+            && (self.allowed_terms.is_empty() || self.allowed_terms.contains(&value))
+    }
+}
+
 #[cw_serde]
 pub struct LockupTerm {
     pub value: u64,
     pub percent: Uint128,
+    pub max_nfts: Option<u64>,       // max nfts staked in term
+    pub max_reward: Option<Uint128>, // max reward paid to nfts of term
+}
+
+impl LockupTerm {
+    // flexible term has no lockup, nft earns until it is unstaked or campaign ends
+    pub fn is_flexible(&self) -> bool {
+        self.value == 0
+    }
 }
 
 impl fmt::Display for LockupTerm {
@@ -93,7 +157,6 @@ pub enum UpdateCampaign {
 
 #[cw_serde]
 pub struct StakerRewardAssetInfo {
-    pub token_ids: Vec<String>,
     pub reward_debt: Uint128, // can claim reward.
     pub reward_claimed: Uint128,
 }
@@ -107,27 +170,328 @@ pub struct NftInfo {
     pub is_end_reward: bool,
     pub start_time: u64,
     pub end_time: u64,
+    pub weight: u64, // share of term reward, BASE_WEIGHT = 1x
+}
+
+// reward state of lockup term at time_calc_nft
+#[cw_serde]
+#[derive(Default)]
+pub struct TermState {
+    pub total_weight: Uint128, // weight of nfts earning reward
+    pub nft_count: u64,        // nfts earning reward
+    pub reward_index: Uint256, // scaled reward accrued by an nft with BASE_WEIGHT since the first stake
+}
+
+#[cw_serde]
+pub struct TermFill {
+    pub lockup_term: u64,
+    pub nft_count: u64, // nfts staked in lockup term
+    pub max_nfts: Option<u64>,
+    pub accrued_reward: Uint128, // reward accrued by nfts of lockup term until time_calc_nft
+    pub max_reward: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct TermNftCount {
+    pub lockup_term: u64,
+    pub nft_count: u64, // nfts earning reward in lockup term
+}
+
+#[cw_serde]
+pub struct RewardSnapshot {
+    pub time: u64,
+    pub reward_per_second: Uint128,
+    pub nft_counts: Vec<TermNftCount>,
+    pub total_accrued: Uint128, // total reward accrued to nfts until time
+}
+
+#[cw_serde]
+pub enum LedgerAction {
+    Claim,
+    Settlement, // pending reward of unstaked nft moved to reward_debt
+    Penalty,    // reward forfeited by staker
+}
+
+#[cw_serde]
+pub struct LedgerEntry {
+    pub id: u64,
+    pub action: LedgerAction,
+    pub token_id: Option<String>,
+    pub amount: Uint128,
+    pub time: u64,
+    pub height: u64,
+}
+
+// nft with trait_type of one of values
+#[cw_serde]
+pub struct TraitGroup {
+    pub trait_type: String,
+    pub values: Vec<String>,
+}
+
+impl TraitGroup {
+    pub fn matches(&self, traits: &[Trait]) -> bool {
+        traits
+            .iter()
+            .any(|t| t.trait_type == self.trait_type && self.values.contains(&t.value))
+    }
+}
+
+#[cw_serde]
+pub enum SetRule {
+    // staking at least count nfts
+    Count { count: u64 },
+    // staking one nft of each trait group
+    Traits { groups: Vec<TraitGroup> },
+}
+
+// set bonus multiplies weight of all nfts of staker, BASE_WEIGHT = 1x
+#[cw_serde]
+pub struct SetBonus {
+    pub rule: SetRule,
+    pub multiplier: u64,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct StakingRules {
+    pub min_hold_time: u64,    // seconds an nft must be staked before unstake
+    pub restake_cooldown: u64, // seconds after unstake before the token_id can be staked again
+    pub unbonding_period: u64, // seconds an unstaked nft is held before it can be withdrawn
+}
+
+#[cw_serde]
+pub struct UnbondingNft {
+    pub token_id: String,
+    pub owner: Addr,
+    pub release_time: u64,
+}
+
+#[cw_serde]
+pub enum ListKind {
+    Allow,
+    Deny,
+}
+
+#[cw_serde]
+pub struct TokenIdRange {
+    pub min: u64, // inclusive
+    pub max: u64, // inclusive
+}
+
+impl TokenIdRange {
+    // non numeric token_id is not in any range
+    pub fn contains(&self, token_id: &str) -> bool {
+        token_id
+            .parse::<u64>()
+            .map_or(false, |id| self.min <= id && id <= self.max)
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct EligibilityConfig {
+    pub token_allowlist_enabled: bool, // only allowed token_ids can be staked
+    pub staker_allowlist_enabled: bool, // only allowed stakers can stake
+    pub allowed_token_ranges: Vec<TokenIdRange>,
+    pub denied_token_ranges: Vec<TokenIdRange>,
 }
 
 #[cw_serde]
 pub struct NftStake {
     pub token_id: String,
     pub lockup_term: u64,
+    pub weight_proof: Option<TokenWeightProof>, // required if token weight root is registered
+}
+
+// proof of (token_id, weight) leaf
+#[cw_serde]
+pub struct TokenWeightProof {
+    pub weight: u64,
+    pub proof: Vec<String>,
+}
+
+// proof of (address, bonus_multiplier) leaf
+#[cw_serde]
+pub struct StakerBonusProof {
+    pub bonus_multiplier: u64,
+    pub proof: Vec<String>,
+}
+
+// cw20 token which can be staked alongside nfts through Receive,
+// a deposit of amount_per_nft tokens weighs as much as one nft
+#[cw_serde]
+pub struct TokenStaking {
+    pub contract_addr: Addr,
+    pub amount_per_nft: Uint128,
+}
+
+#[cw_serde]
+pub struct TokenDeposit {
+    pub token_id: String, // staked position of the deposit
+    pub amount: Uint128,
+}
+
+// rules against nfts moving between campaigns of the same collection, checked by the factory
+#[cw_serde]
+pub struct ExclusivityRules {
+    // nft unstaked from another campaign can be staked after switch_cooldown and
+    // nft unstaked from this campaign can join another campaign after switch_cooldown
+    pub switch_cooldown: u64,
+}
+
+// hex encoded sha256 merkle roots, like cw20-merkle-airdrop
+#[cw_serde]
+#[derive(Default)]
+pub struct MerkleRoots {
+    pub staker_bonus_root: Option<String>,
+    pub token_weight_root: Option<String>,
 }
 
 // campaign info
 pub const CAMPAIGN_INFO: Item<CampaignInfo> = Item::new("campaign_info");
 
-// Mapping from staker address to staked nft.
+// Mapping from staker address to reward of staker.
 pub const STAKERS_INFO: Map<Addr, StakerRewardAssetInfo> = Map::new("stakers_info");
 
-pub const STAKERS: Map<u64, Addr> = Map::new("staker");
+// number of nft staked
+pub const TOTAL_NFT_STAKED: Item<u64> = Item::new("total_nft_staked");
+
+pub struct NftIndexes<'a> {
+    // staked nfts of an owner
+    pub owner: MultiIndex<'a, Addr, NftInfo, String>,
+    // staked nfts of a lockup term
+    pub lockup_term: MultiIndex<'a, u64, NftInfo, String>,
+}
+
+impl<'a> IndexList<NftInfo> for NftIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<NftInfo>> + '_> {
+        let v: Vec<&dyn Index<NftInfo>> = vec![&self.owner, &self.lockup_term];
+        Box::new(v.into_iter())
+    }
+}
+
+// checkpoint of reward rate and nft count of each lockup term, keyed by time of state change
+pub const REWARD_HISTORY: Map<u64, RewardSnapshot> = Map::new("reward_history");
+
+// reward accrued by nfts of each lockup term, capped at max_reward of term
+pub const TERM_REWARDS: Map<u64, Uint128> = Map::new("term_rewards");
+
+// nfts staked in each lockup term, including nfts which stopped earning
+pub const TERM_NFT_COUNTS: Map<u64, u64> = Map::new("term_nft_counts");
+// set bonuses of campaign, the highest satisfied bonus applies
+pub const SET_BONUSES: Item<Vec<SetBonus>> = Item::new("set_bonuses");
+
+// set multiplier of staker, recomputed on every stake and unstake
+pub const SET_MULTIPLIERS: Map<Addr, u64> = Map::new("set_multipliers");
+
+// weight of staked nft before set bonus
+pub const NFT_BASE_WEIGHTS: Map<String, u64> = Map::new("nft_base_weights");
+
+// traits of staked nft, only saved if a trait set is configured
+pub const NFT_TRAITS: Map<String, Vec<Trait>> = Map::new("nft_traits");
+
+// staking rules of campaign, no rule if not set
+pub const STAKING_RULES: Item<StakingRules> = Item::new("staking_rules");
+
+// time of last unstake of token_id
+pub const LAST_UNSTAKE_TIME: Map<String, u64> = Map::new("last_unstake_time");
+
+pub struct UnbondingNftIndexes<'a> {
+    // unbonding nfts of an owner
+    pub owner: MultiIndex<'a, Addr, UnbondingNft, String>,
+}
+
+impl<'a> IndexList<UnbondingNft> for UnbondingNftIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<UnbondingNft>> + '_> {
+        let v: Vec<&dyn Index<UnbondingNft>> = vec![&self.owner];
+        Box::new(v.into_iter())
+    }
+}
+
+// unstaked nfts waiting for the end of unbonding period
+pub fn unbonding_nfts<'a>() -> IndexedMap<'a, String, UnbondingNft, UnbondingNftIndexes<'a>> {
+    let indexes = UnbondingNftIndexes {
+        owner: MultiIndex::new(
+            |_pk, nft| nft.owner.clone(),
+            "unbonding_nfts",
+            "unbonding_nfts__owner",
+        ),
+    };
+    IndexedMap::new("unbonding_nfts", indexes)
+}
+
+// lockup term policy of campaign, set by factory at instantiate
+pub const TERM_POLICY: Item<TermPolicy> = Item::new("term_policy");
+
+// factory keeping registry of staked nfts, no callbacks if not set
+pub const FACTORY: Item<Addr> = Item::new("factory");
+
+pub const EXCLUSIVITY_RULES: Item<ExclusivityRules> = Item::new("exclusivity_rules");
+
+// nfts stay in wallets of stakers and are only registered
+pub const SOFT_STAKING: Item<bool> = Item::new("soft_staking");
+
+// cw721 receipts of staked positions minted by campaign, set once by factory
+pub const RECEIPT_COLLECTION: Item<Addr> = Item::new("receipt_collection");
+
+// cw20 token staking, disabled if not set
+pub const TOKEN_STAKING: Item<TokenStaking> = Item::new("token_staking");
+
+// amount of token deposits by position, kept until the tokens are returned
+pub const TOKEN_DEPOSITS: Map<String, Uint128> = Map::new("token_deposits");
+
+// number of token deposits, used for position of the next deposit
+pub const TOKEN_DEPOSIT_COUNT: Item<u64> = Item::new("token_deposit_count");
+
+// merkle roots of staker bonus and token weight, no proof required if not set
+pub const MERKLE_ROOTS: Item<MerkleRoots> = Item::new("merkle_roots");
+
+// allowlist and denylist settings of campaign, everyone is eligible if not set
+pub const ELIGIBILITY_CONFIG: Item<EligibilityConfig> = Item::new("eligibility_config");
+
+// explicit token_id lists
+pub const ALLOWED_TOKEN_IDS: Map<String, bool> = Map::new("allowed_token_ids");
+pub const DENIED_TOKEN_IDS: Map<String, bool> = Map::new("denied_token_ids");
+
+// explicit staker lists
+pub const ALLOWED_STAKERS: Map<Addr, bool> = Map::new("allowed_stakers");
+pub const DENIED_STAKERS: Map<Addr, bool> = Map::new("denied_stakers");
+
+// append-only reward ledger, keyed by (staker, entry id)
+pub const STAKER_LEDGER: Map<(Addr, u64), LedgerEntry> = Map::new("staker_ledger");
+
+// address receiving claimed reward of staker, staker itself if not set
+pub const REWARD_RECIPIENTS: Map<Addr, Addr> = Map::new("reward_recipients");
+
+// operators allowed to claim and unstake on behalf of staker, keyed by (staker, operator)
+pub const OPERATORS: Map<(Addr, Addr), Expiration> = Map::new("operators");
+
+// number of ledger entries of staker
+pub const STAKER_LEDGER_COUNT: Map<Addr, u64> = Map::new("staker_ledger_count");
 
-// list token_id nft
-pub const TOKEN_IDS: Item<Vec<String>> = Item::new("token_ids");
+// reward state of each lockup term
+pub const TERM_STATES: Map<u64, TermState> = Map::new("term_states");
+
+// reward_index of lockup term when pending reward of nft was saved, only nfts earning reward
+pub const NFT_REWARD_INDEXES: Map<String, Uint256> = Map::new("nft_reward_indexes");
+
+// nfts earning reward by (time they stop earning, token_id), value is the lockup term.
+// nfts stop earning at their end_time or the end_time of campaign, whichever is first
+pub const NFT_EXPIRATIONS: Map<(u64, String), u64> = Map::new("nft_expirations");
 
 // list nft staked
-pub const NFTS: Map<String, NftInfo> = Map::new("nfts");
+pub fn staked_nfts<'a>() -> IndexedMap<'a, String, NftInfo, NftIndexes<'a>> {
+    let indexes = NftIndexes {
+        owner: MultiIndex::new(|_pk, nft| nft.owner.clone(), "nfts", "nfts__owner"),
+        lockup_term: MultiIndex::new(
+            |_pk, nft| nft.lockup_term.value,
+            "nfts",
+            "nfts__lockup_term",
+        ),
+    };
+    IndexedMap::new("nfts", indexes)
+}
 
 // result query
 #[cw_serde]
@@ -147,6 +511,7 @@ pub struct CampaignInfoResult {
     pub time_calc_nft: u64,
     pub start_time: u64,
     pub end_time: u64,
+    pub term_fills: Vec<TermFill>,
 }
 
 #[cw_serde]
@@ -154,6 +519,75 @@ pub struct StakedInfoResult {
     pub nfts: Vec<NftInfo>,
     pub reward_debt: Uint128, // can claim reward.
     pub reward_claimed: Uint128,
+    pub set_multiplier: u64, // highest set bonus of staker, BASE_WEIGHT = no bonus
+}
+
+#[cw_serde]
+pub struct StakerInfoResult {
+    pub owner: Addr,
+    pub token_ids: Vec<String>, // at most 100, ordered by token_id
+    pub reward_debt: Uint128,   // include pending reward of staked nfts
+    pub reward_claimed: Uint128,
+}
+
+#[cw_serde]
+pub struct NftDashboard {
+    pub token_id: String,
+    pub lockup_term: LockupTerm,
+    pub is_end_reward: bool,
+    pub unlock_time: u64, // min(end_time of nft, end_time of campaign), start_time if flexible
+    pub time_until_unlock: u64,
+    pub reward_per_second: Uint128, // current accrual rate of nft
+    pub pending_reward: Uint128,
+    pub projected_reward: Uint128, // pending reward at end_time if no nft is staked or unstaked
+}
+
+#[cw_serde]
+pub struct StakerDashboardResult {
+    pub owner: Addr,
+    pub nfts: Vec<NftDashboard>,
+    pub reward_debt: Uint128,
+    pub reward_claimed: Uint128,
+    pub reward_per_second: Uint128, // current accrual rate of all nfts
+    pub claimable_reward: Uint128,  // reward_debt + pending reward of nfts
+    pub projected_reward: Uint128,  // reward_debt + projected reward of nfts
+}
+
+#[cw_serde]
+pub struct SimulateStakeResult {
+    pub token_id: String,
+    pub lockup_term: LockupTerm,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub pending_reward: Uint128, // reward of nft at simulation time
+}
+
+#[cw_serde]
+pub struct SimulateUnstakeResult {
+    pub token_id: String,
+    pub owner: Addr,
+    pub can_unstake: bool,
+    pub reward: Uint128,      // pending reward of nft moved to staker
+    pub reward_debt: Uint128, // reward of staker after unstake
+}
+
+#[cw_serde]
+pub struct SimulateClaimResult {
+    pub owner: Addr,
+    pub claimable_reward: Uint128, // max amount of ClaimReward
+}
+
+#[cw_serde]
+pub struct TokenEligibility {
+    pub token_id: String,
+    pub eligible: bool,
+}
+
+#[cw_serde]
+pub struct EligibilityResult {
+    pub staker: Addr,
+    pub staker_eligible: bool,
+    pub token_ids: Vec<TokenEligibility>,
 }
 
 #[cw_serde]
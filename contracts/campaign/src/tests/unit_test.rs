@@ -0,0 +1,286 @@
+#![cfg(test)]
+mod tests {
+    // reward of a term is accrued in a scaled index which is divided only at payout
+    mod reward_index {
+        use crate::utils::{calc_index_reward, calc_weighted_reward_in_time, BASE_WEIGHT};
+        use crate::ContractError;
+        use cosmwasm_std::{Uint128, Uint256};
+
+        // - 3 nfts share 1 reward per second of a term with 100 percent
+        // 	- each 1s period accrues 1 / 3 reward per nft, which is 0 once divided
+        // 	- after 30 periods, each nft is paid 9 reward from the index instead of 0
+        #[test]
+        fn index_keeps_precision() {
+            let total_weight = 3 * BASE_WEIGHT as u128;
+            let period_reward =
+                calc_weighted_reward_in_time(0, 1, Uint128::one(), Uint128::new(100), total_weight)
+                    .unwrap();
+            assert!(calc_index_reward(period_reward, BASE_WEIGHT as u128)
+                .unwrap()
+                .is_zero());
+
+            let mut reward_index = Uint256::zero();
+            for _ in 0..30 {
+                reward_index += period_reward;
+            }
+            assert_eq!(
+                calc_index_reward(reward_index, BASE_WEIGHT as u128).unwrap(),
+                Uint128::new(9)
+            );
+            // accrued at once, each nft is paid 10 of the 30 reward of 30s
+            let reward_index = calc_weighted_reward_in_time(
+                0,
+                30,
+                Uint128::one(),
+                Uint128::new(100),
+                total_weight,
+            )
+            .unwrap();
+            assert_eq!(
+                calc_index_reward(reward_index, BASE_WEIGHT as u128).unwrap(),
+                Uint128::new(10)
+            );
+        }
+
+        // - nfts of weight 100 and 200 share 30.000 reward of 10s with 30 percent
+        // 	- 9.000 reward is paid 3.000 / 6.000 by weight
+        #[test]
+        fn index_is_shared_by_weight() {
+            let reward_index =
+                calc_weighted_reward_in_time(20, 30, Uint128::new(3_000), Uint128::new(30), 300)
+                    .unwrap();
+            assert_eq!(
+                calc_index_reward(reward_index, 100).unwrap(),
+                Uint128::new(3_000)
+            );
+            assert_eq!(
+                calc_index_reward(reward_index, 200).unwrap(),
+                Uint128::new(6_000)
+            );
+
+            // time before start accrues nothing
+            let reward_index =
+                calc_weighted_reward_in_time(30, 20, Uint128::new(3_000), Uint128::new(30), 300)
+                    .unwrap();
+            assert!(reward_index.is_zero());
+        }
+
+        #[test]
+        fn overflow_is_an_error() {
+            let err = calc_weighted_reward_in_time(0, u64::MAX, Uint128::MAX, Uint128::MAX, 100)
+                .unwrap_err();
+            assert!(matches!(err, ContractError::Overflow(_)));
+
+            let err = calc_index_reward(Uint256::MAX, 2).unwrap_err();
+            assert!(matches!(err, ContractError::Overflow(_)));
+
+            // reward of an index above Uint128 cannot be paid
+            let err = calc_index_reward(Uint256::MAX / Uint256::from(2u128), 2).unwrap_err();
+            assert!(matches!(err, ContractError::ConversionOverflow(_)));
+        }
+    }
+
+    // a token_id is staked once, whatever the staking mode
+    mod duplicate_token_ids {
+        use crate::contract::{execute, instantiate};
+        use crate::msg::{ExecuteMsg, InstantiateMsg};
+        use crate::state::{AssetToken, LockupTerm, NftStake, TokenInfo, CAMPAIGN_INFO};
+        use crate::ContractError;
+        use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+        use cosmwasm_std::Uint128;
+
+        #[test]
+        fn token_id_repeated_in_stake() {
+            let mut deps = mock_dependencies();
+            let env = mock_env();
+            let now = env.block.time.seconds();
+            for soft_staking in [false, true] {
+                instantiate(
+                    deps.as_mut(),
+                    env.clone(),
+                    mock_info("owner", &[]),
+                    InstantiateMsg {
+                        owner: "owner".to_string(),
+                        campaign_name: "campaign name".to_string(),
+                        campaign_image: "campaign image".to_string(),
+                        campaign_description: "campaign description".to_string(),
+                        limit_per_staker: 0,
+                        reward_token_info: AssetToken {
+                            info: TokenInfo::Token {
+                                contract_addr: "token".to_string(),
+                            },
+                            amount: Uint128::zero(),
+                        },
+                        allowed_collection: "collection".to_string(),
+                        lockup_term: vec![LockupTerm {
+                            value: 10,
+                            percent: Uint128::new(100),
+                            max_nfts: None,
+                            max_reward: None,
+                        }],
+                        start_time: now - 10,
+                        end_time: now + 100,
+                        term_policy: None,
+                        token_staking: None,
+                        factory: None,
+                        exclusivity: None,
+                        soft_staking: Some(soft_staking),
+                    },
+                )
+                .unwrap();
+                CAMPAIGN_INFO
+                    .update(deps.as_mut().storage, |mut campaign_info| {
+                        campaign_info.reward_token.amount = Uint128::new(1_000);
+                        Ok::<_, ContractError>(campaign_info)
+                    })
+                    .unwrap();
+
+                // rejected before the collection is queried for owner of token_id
+                let nft = NftStake {
+                    token_id: "1".to_string(),
+                    lockup_term: 10,
+                    weight_proof: None,
+                };
+                let err = execute(
+                    deps.as_mut(),
+                    env.clone(),
+                    mock_info("staker", &[]),
+                    ExecuteMsg::StakeNfts {
+                        nfts: vec![nft.clone(), nft],
+                        bonus: None,
+                    },
+                )
+                .unwrap_err();
+                assert_eq!(
+                    err.to_string(),
+                    "## Duplicate token_id: \"1\" ##".to_string()
+                );
+            }
+        }
+    }
+
+    // token_id ranges of eligibility config are inclusive and numeric
+    mod eligibility_ranges {
+        use crate::state::TokenIdRange;
+
+        #[test]
+        fn range_contains_token_id() {
+            let range = TokenIdRange { min: 5, max: 10 };
+            for (token_id, contained) in [
+                ("4", false),
+                ("5", true),
+                ("7", true),
+                ("007", true),
+                ("10", true),
+                ("11", false),
+                ("-5", false),
+                ("cw20:5", false),
+                ("", false),
+            ] {
+                assert_eq!(range.contains(token_id), contained, "{}", token_id);
+            }
+
+            let single = TokenIdRange { min: 0, max: 0 };
+            assert!(single.contains("0"));
+            assert!(!single.contains("1"));
+        }
+    }
+
+    // leaves of merkle roots are `<staker>:<multiplier>` and `<token_id>:<weight>`
+    mod merkle_proof {
+        use crate::utils::verify_merkle_proof;
+        use crate::ContractError;
+        use sha2::Digest;
+
+        fn leaf_hash(leaf: &str) -> [u8; 32] {
+            sha2::Sha256::digest(leaf.as_bytes()).into()
+        }
+
+        // merkle root of two leaves and the proof of the first leaf
+        fn merkle_tree(leaves: [&str; 2]) -> (String, Vec<String>) {
+            let mut hashes = [leaf_hash(leaves[0]), leaf_hash(leaves[1])];
+            let proof = vec![hex::encode(hashes[1])];
+            hashes.sort_unstable();
+            let root: [u8; 32] = sha2::Sha256::digest(hashes.concat()).into();
+            (hex::encode(root), proof)
+        }
+
+        #[test]
+        fn proof_of_leaf() {
+            let (root, proof) = merkle_tree(["1:300", "2:100"]);
+            verify_merkle_proof(&root, "1:300", &proof).unwrap();
+
+            // same token_id and weight with another delimiter is another leaf
+            for leaf in ["1,300", "1-300", "1300", "1:200", "2:300"] {
+                let err = verify_merkle_proof(&root, leaf, &proof).unwrap_err();
+                assert!(
+                    matches!(err, ContractError::VerificationFailed {}),
+                    "{}",
+                    leaf
+                );
+            }
+
+            // proof and root must be hex encoded
+            let err = verify_merkle_proof(&root, "1:300", &["proof".to_string()]).unwrap_err();
+            assert!(matches!(err, ContractError::Hex(_)));
+        }
+    }
+
+    // set multiplier of staker is the highest multiplier of satisfied set bonuses
+    mod set_multipliers {
+        use crate::state::{SetBonus, SetRule, Trait, TraitGroup};
+        use crate::utils::{calc_set_multiplier, BASE_WEIGHT};
+
+        fn nft_traits(traits: &[(&str, &str)]) -> Vec<Trait> {
+            traits
+                .iter()
+                .map(|(trait_type, value)| Trait {
+                    display_type: None,
+                    trait_type: trait_type.to_string(),
+                    value: value.to_string(),
+                })
+                .collect()
+        }
+
+        #[test]
+        fn highest_satisfied_bonus() {
+            let set_bonuses = vec![
+                SetBonus {
+                    rule: SetRule::Count { count: 3 },
+                    multiplier: 150,
+                },
+                SetBonus {
+                    rule: SetRule::Traits {
+                        groups: vec![
+                            TraitGroup {
+                                trait_type: "background".to_string(),
+                                values: vec!["gold".to_string()],
+                            },
+                            TraitGroup {
+                                trait_type: "eyes".to_string(),
+                                values: vec!["laser".to_string(), "blue".to_string()],
+                            },
+                        ],
+                    },
+                    multiplier: 200,
+                },
+            ];
+            let gold = nft_traits(&[("background", "gold"), ("eyes", "red")]);
+            let laser = nft_traits(&[("background", "gray"), ("eyes", "laser")]);
+            let gold_laser = nft_traits(&[("background", "gold"), ("eyes", "laser")]);
+
+            assert_eq!(calc_set_multiplier(&[], 5, &[]), BASE_WEIGHT);
+            assert_eq!(
+                calc_set_multiplier(&set_bonuses, 2, &[gold.clone(), gold.clone()]),
+                BASE_WEIGHT
+            );
+            assert_eq!(
+                calc_set_multiplier(&set_bonuses, 3, &[gold.clone(), gold.clone(), gold.clone()]),
+                150
+            );
+            // trait groups may be matched by different nfts
+            assert_eq!(calc_set_multiplier(&set_bonuses, 2, &[gold, laser]), 200);
+            assert_eq!(calc_set_multiplier(&set_bonuses, 1, &[gold_laser]), 200);
+        }
+    }
+}
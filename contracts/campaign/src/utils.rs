@@ -1,5 +1,11 @@
-use cosmwasm_std::{Uint128, OverflowError, DivideByZeroError};
+use cosmwasm_std::{Uint128, Uint256, OverflowError};
+use sha2::Digest;
 
+use crate::state::{SetBonus, SetRule, Trait};
+use crate::ContractError;
+
+/// Weight of an nft without bonus
+pub const BASE_WEIGHT: u64 = 100;
 
 /// Calculates the reward amount
 pub fn add_reward(current_reward: Uint128, calc_reward: Uint128) -> Result<Uint128, OverflowError> {
@@ -12,25 +18,82 @@ pub fn sub_reward(current_reward: Uint128, calc_reward: Uint128) -> Result<Uint1
         .checked_sub(calc_reward)
 }
 
-pub fn calc_reward_in_time(
+/// Scale of reward indexes, reward is only divided by it at payout to keep precision
+pub const REWARD_INDEX_SCALE: u128 = 1_000_000_000_000;
+
+/// Calculates the scaled reward of an nft with BASE_WEIGHT when the term is shared by total_weight
+pub fn calc_weighted_reward_in_time(
     start_time: u64,
     end_time: u64,
     reward_per_second: Uint128,
     percent: Uint128,
-    nft_count: u128,
-) -> Result<Uint128, DivideByZeroError> {
-    let diff_time = end_time.checked_sub(start_time).unwrap_or(0);
+    total_weight: u128,
+) -> Result<Uint256, ContractError> {
+    let diff_time = end_time.saturating_sub(start_time);
+
+    let mul_reward = Uint256::from(diff_time)
+        .checked_mul(reward_per_second.into())?
+        .checked_mul(percent.into())?
+        .checked_mul(Uint256::from(BASE_WEIGHT))?
+        .checked_mul(Uint256::from(REWARD_INDEX_SCALE))?;
+
+    let divisor = Uint256::from(100u128).checked_mul(Uint256::from(total_weight))?;
+
+    Ok(mul_reward.checked_div(divisor)?)
+}
+
+/// Calculates the reward of weight from a scaled reward of an nft with BASE_WEIGHT
+pub fn calc_index_reward(index_reward: Uint256, weight: u128) -> Result<Uint128, ContractError> {
+    let reward = index_reward
+        .checked_mul(Uint256::from(weight))?
+        .checked_div(Uint256::from(BASE_WEIGHT as u128 * REWARD_INDEX_SCALE))?;
+
+    Ok(Uint128::try_from(reward)?)
+}
+
+/// Calculates the highest multiplier of set bonuses satisfied by nft_count nfts with nft_traits,
+/// a trait group is matched by any of the nfts
+pub fn calc_set_multiplier(
+    set_bonuses: &[SetBonus],
+    nft_count: u64,
+    nft_traits: &[Vec<Trait>],
+) -> u64 {
+    let mut multiplier = BASE_WEIGHT;
+    for bonus in set_bonuses {
+        let satisfied = match &bonus.rule {
+            SetRule::Count { count } => nft_count >= *count,
+            SetRule::Traits { groups } => groups
+                .iter()
+                .all(|group| nft_traits.iter().any(|traits| group.matches(traits))),
+        };
+        if satisfied {
+            multiplier = multiplier.max(bonus.multiplier);
+        }
+    }
+    multiplier
+}
 
-    let mul_reward = Uint128::from(diff_time)
-        .checked_mul(reward_per_second)
-        .and_then(|res| res.checked_mul(percent))
-        .unwrap();
+/// Verifies a sha256 merkle proof of leaf against hex encoded root, like cw20-merkle-airdrop
+pub fn verify_merkle_proof(
+    merkle_root: &str,
+    leaf: &str,
+    proof: &[String],
+) -> Result<(), ContractError> {
+    let hash: [u8; 32] = sha2::Sha256::digest(leaf.as_bytes()).into();
 
-    let divisor = Uint128::from(100 as u128)
-        .checked_mul(Uint128::from(nft_count))
-        .unwrap();
+    let hash = proof.iter().try_fold(hash, |hash, p| {
+        let mut proof_buf = [0; 32];
+        hex::decode_to_slice(p, &mut proof_buf)?;
+        let mut hashes = [hash, proof_buf];
+        hashes.sort_unstable();
+        Ok::<[u8; 32], ContractError>(sha2::Sha256::digest(hashes.concat()).into())
+    })?;
 
-    let final_reward = mul_reward.checked_div(divisor);
+    let mut root_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(merkle_root, &mut root_buf)?;
+    if root_buf != hash {
+        return Err(ContractError::VerificationFailed {});
+    }
 
-    final_reward
+    Ok(())
 }